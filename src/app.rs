@@ -1,13 +1,35 @@
 use crate::docker::client::DockerClient;
+use crate::docker::compose::ComposeSource;
+use crate::docker::filesystem::VOLUME_MOUNT_PATH;
+use crate::docker::run_config::RunConfig;
+use crate::utils::expand_home;
 use crate::event::{AppEvent, Event, EventHandler};
+use crate::ui::common::size_string;
+use crate::ui::compose_view::{ComposeAction, ComposeView};
 use crate::ui::container_info_block::{ContainerData, ContainerInfoBlock};
-use crate::ui::container_table::{ContainerTable, ContainerTableRow};
-use crate::ui::image_table::{ImageTable, ImageTableRow};
+use crate::ui::container_table::{ContainerTable, ContainerTableRow, GroupAction};
+use crate::ui::daemon_info_block::{DaemonData, DaemonInfoBlock};
+use crate::ui::dependents_panel::{DependencyTarget, DependentsTable};
+use crate::ui::file_browser::FileBrowser;
+use crate::ui::image_table::ImageTable;
 use crate::ui::info_block::ScrollableInfoBlock;
 use crate::ui::network_table::{NetworkTable, NetworkTableRow};
 use crate::ui::resource_table::ResourceTable;
+use crate::ui::system_view::{PruneTarget, SystemView};
+use crate::ui::transfer_status::TransferStatus;
+use crate::ui::network_form::NetworkConnection;
+use crate::ui::network_info_block::{NetworkData, NetworkInfoBlock};
+use crate::ui::network_topology::NetworkTopology;
+use crate::ui::node_table::{NodeTable, NodeTableRow};
+use crate::ui::service_table::{ServiceTable, ServiceTableRow};
+use crate::ui::swarm_object_table::{NewSwarmObject, SwarmObjectKind, SwarmObjectTable, SwarmObjectTableRow};
+use crate::ui::task_table::TaskTable;
+use crate::ui::volume_info_block::{VolumeData, VolumeInfoBlock};
 use crate::ui::volume_table::{VolumeTable, VolumeTableRow};
-use color_eyre::eyre::Result;
+use bollard::query_parameters::CommitContainerOptions;
+use bollard::secret::{ContainerUpdateBody, NetworkCreateRequest, NodeSpecAvailabilityEnum, VolumeCreateOptions};
+use color_eyre::eyre::{Result, eyre};
+use futures::future::join_all;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::palette::tailwind;
@@ -19,7 +41,11 @@ use ratatui::{
     crossterm::event::{KeyCode, KeyEvent, KeyModifiers},
 };
 use strum::IntoEnumIterator;
-use strum_macros::{Display, EnumIter, FromRepr};
+use strum_macros::{Display, EnumIter};
+
+const TRANSFER_IN_PROGRESS_ERR: &str = "Another transfer is in progress";
+
+type ProgressFn = Box<dyn Fn(u64) + Send + Sync>;
 
 pub struct App {
     running: bool,
@@ -29,56 +55,125 @@ pub struct App {
     container_table: ContainerTable,
     container_info: Option<Box<dyn ScrollableInfoBlock<Data = ContainerData>>>,
     volume_table: VolumeTable,
+    volume_info: Option<Box<dyn ScrollableInfoBlock<Data = VolumeData>>>,
+    network_info: Option<Box<dyn ScrollableInfoBlock<Data = NetworkData>>>,
+    network_topology: Option<NetworkTopology>,
+    daemon_info: Option<Box<dyn ScrollableInfoBlock<Data = DaemonData>>>,
+    compose_view: Option<ComposeView>,
+    engine_version: Option<String>,
+    is_swarm_manager: bool,
     network_table: NetworkTable,
     image_table: ImageTable,
+    service_table: ServiceTable,
+    task_table: TaskTable,
+    node_table: NodeTable,
+    secret_table: SwarmObjectTable,
+    config_table: SwarmObjectTable,
+    system_view: SystemView,
+    transfer: Option<TransferStatus>,
+    /// The container and directory an upload in progress copies to, listed again once it is done.
+    upload_target: Option<(String, String)>,
+    file_browser: Option<FileBrowser>,
 }
 
 impl App {
-    pub fn new() -> Result<Self> {
+    pub async fn new() -> Result<Self> {
         Ok(Self {
             running: true,
             events: EventHandler::new(),
-            docker_client: DockerClient::new()?,
+            docker_client: DockerClient::new().await?,
             selected_tab: SelectedTab::default(),
             container_table: ContainerTable::default(),
             container_info: None,
             volume_table: VolumeTable::default(),
+            volume_info: None,
+            network_info: None,
+            network_topology: None,
+            daemon_info: None,
+            compose_view: None,
+            engine_version: None,
+            is_swarm_manager: false,
             network_table: NetworkTable::default(),
             image_table: ImageTable::default(),
+            service_table: ServiceTable::default(),
+            task_table: TaskTable::default(),
+            node_table: NodeTable::default(),
+            secret_table: SwarmObjectTable::new(SwarmObjectKind::Secret),
+            config_table: SwarmObjectTable::new(SwarmObjectKind::Config),
+            system_view: SystemView::default(),
+            transfer: None,
+            upload_target: None,
+            file_browser: None,
         })
     }
 
     pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         self.update_containers().await?;
+        self.engine_version = self.docker_client.version().await.ok().and_then(|version| version.version);
+        self.is_swarm_manager = self.docker_client.info().await.ok()
+            .and_then(|info| info.swarm)
+            .and_then(|swarm| swarm.control_available)
+            .unwrap_or(false);
 
         while self.running {
             terminal.draw(|frame| self.draw(frame, frame.area()))?;
             self.process_next_event().await?;
         }
+
+        self.close_file_browser().await;
         Ok(())
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) {
         use Constraint::{Length, Min};
         let vertical = Layout::vertical([Length(1), Length(1), Min(0)]);
-        let [header_area, _, inner_area] = vertical.areas(area);
+        let [header_area, status_area, inner_area] = vertical.areas(area);
 
-        let header_horizontal = Layout::horizontal([Min(0), Length(6)]);
+        let title = get_title(self.engine_version.as_deref());
+        let header_horizontal = Layout::horizontal([Min(0), Length(title.width() as u16)]);
         let [tabs_area, title_area] = header_horizontal.areas(header_area);
 
-        if let Some(info_block) = self.container_info.as_mut() {
+        if let Some(file_browser) = self.file_browser.as_mut() {
+            // Copies run in the background, their progress is shown above the browser.
+            match &self.transfer {
+                Some(transfer) => {
+                    let [status_area, browser_area] = Layout::vertical([Length(1), Min(0)]).areas(area);
+                    transfer.render(frame, status_area);
+                    file_browser.draw(frame, browser_area);
+                }
+                None => file_browser.draw(frame, area),
+            }
+        } else if let Some(info_block) = self.container_info.as_mut() {
+            let _ = info_block.draw(frame, area);
+        } else if let Some(info_block) = self.volume_info.as_mut() {
+            let _ = info_block.draw(frame, area);
+        } else if let Some(info_block) = self.network_info.as_mut() {
+            let _ = info_block.draw(frame, area);
+        } else if let Some(topology) = self.network_topology.as_mut() {
+            topology.draw(frame, area);
+        } else if let Some(info_block) = self.daemon_info.as_mut() {
             let _ = info_block.draw(frame, area);
         } else {
-            render_title(frame, title_area);
+            frame.render_widget(title, title_area);
             self.render_tabs(frame, tabs_area);
-            let _ = self.render_selected_tab(frame, inner_area);
+            if let Some(transfer) = &self.transfer {
+                transfer.render(frame, status_area);
+            }
+            // The compose view replaces the tab content only, so a running up or pull stays visible.
+            match self.compose_view.as_mut() {
+                Some(compose_view) => compose_view.draw(frame, inner_area),
+                None => {
+                    let _ = self.render_selected_tab(frame, inner_area);
+                }
+            }
         }
     }
 
     fn render_tabs(&mut self, frame: &mut Frame, area: Rect) {
-        let titles = SelectedTab::iter().map(SelectedTab::title);
+        let tabs = self.visible_tabs();
+        let titles = tabs.iter().map(|tab| tab.title());
         let hightlight_style = (Color::default(), tailwind::SLATE.c700);
-        let selected_tab_index = self.selected_tab as usize;
+        let selected_tab_index = tabs.iter().position(|tab| *tab == self.selected_tab).unwrap_or_default();
 
         let tabs = Tabs::new(titles)
             .highlight_style(hightlight_style)
@@ -95,6 +190,12 @@ impl App {
             SelectedTab::Volumes => self.volume_table.draw(frame, area)?,
             SelectedTab::Networks => self.network_table.draw(frame, area)?,
             SelectedTab::Images => self.image_table.draw(frame, area)?,
+            SelectedTab::Services => self.service_table.draw(frame, area)?,
+            SelectedTab::Tasks => self.task_table.draw(frame, area)?,
+            SelectedTab::Nodes => self.node_table.draw(frame, area)?,
+            SelectedTab::Secrets => self.secret_table.draw(frame, area)?,
+            SelectedTab::Configs => self.config_table.draw(frame, area)?,
+            SelectedTab::System => self.system_view.draw(frame, area)?,
         }
         Ok(())
     }
@@ -119,14 +220,71 @@ impl App {
                 AppEvent::StopContainer(id) => self.docker_client.stop_container(&id).await?,
                 AppEvent::KillContainer(id) => self.docker_client.kill_container(&id).await?,
                 AppEvent::RemoveContainer(id) => self.remove_container(id).await?,
+                AppEvent::ContainerGroupAction(action, key, ids) => self.run_group_action(action, key, ids).await?,
                 AppEvent::GoToContainerDetails(id) => self.go_to_container_info(id).await?,
+                AppEvent::CommitContainer(options) => self.commit_container(*options),
+                AppEvent::ContainerCommitted(id, reference) => self.show_committed_image(id, reference).await?,
+                AppEvent::ExportContainer(id, path) => self.export_container(id, path),
+                AppEvent::RenameContainer(id, name) => self.rename_container(id, name).await?,
+                AppEvent::UpdateContainer(id, update) => self.update_container(id, *update).await?,
+                AppEvent::ShowComposeProject(project) => self.show_compose_project(project).await?,
+                AppEvent::UpdateComposeProject => self.update_compose_project().await?,
+                AppEvent::ComposeAction(action) => self.run_compose_action(action),
                 AppEvent::UpdateVolumes => self.update_volumes().await?,
+                AppEvent::UpdateVolumeInfo(name) => self.update_volume_details(name).await?,
+                AppEvent::GoToVolumeDetails(name) => self.go_to_volume_info(name).await?,
+                AppEvent::CreateVolume(options) => self.create_volume(*options).await?,
+                AppEvent::BrowseVolume(name) => self.browse_volume(name).await?,
+                AppEvent::ListDirectory(id, path) => self.list_directory(id, path).await,
+                AppEvent::ReadFile(id, path) => self.read_file(id, path).await,
+                AppEvent::DownloadPath(id, path, dest) => self.download_path(id, path, dest),
+                AppEvent::UploadPath(id, source, dest) => self.upload_path(id, source, dest),
+                AppEvent::BrowseContainer(id) => self.browse_container(id).await,
+                AppEvent::CloseFileBrowser => self.close_file_browser().await,
                 AppEvent::RemoveVolume(name, force) => self.remove_volume(name, force).await?,
                 AppEvent::UpdateNetworks => self.update_networks().await?,
+                AppEvent::CreateNetwork(options) => self.create_network(*options).await?,
+                AppEvent::ConnectNetwork(connection) => self.connect_network(connection).await?,
+                AppEvent::DisconnectNetwork(network, container, force) => {
+                    self.disconnect_network(network, container, force).await?
+                }
+                AppEvent::UpdateNetworkInfo(id) => self.update_network_details(id).await?,
+                AppEvent::GoToNetworkDetails(id) => self.go_to_network_info(id).await?,
+                AppEvent::ShowNetworkTopology => self.show_network_topology().await?,
+                AppEvent::UpdateNetworkTopology => self.update_network_topology().await?,
                 AppEvent::RemoveNetwork(name) => self.remove_network(name).await?,
                 AppEvent::UpdateImages => self.update_images().await?,
+                AppEvent::UpdateImageUsage => self.update_image_usage().await?,
+                AppEvent::PreviewImagePrune => self.preview_image_prune().await?,
+                AppEvent::PruneImages(dangling_only) => self.prune_images(dangling_only).await?,
+                AppEvent::UpdateServices => self.update_services().await?,
+                AppEvent::ShowServiceTasks(id) => self.show_service_tasks(id).await?,
+                AppEvent::ScaleService(id, replicas) => self.scale_service(id, replicas).await?,
+                AppEvent::UpdateServiceImage(id, image) => self.update_service_image(id, image).await?,
+                AppEvent::RollbackService(id) => self.rollback_service(id).await?,
+                AppEvent::UpdateTasks => self.update_tasks().await?,
+                AppEvent::UpdateNodes => self.update_nodes().await?,
+                AppEvent::SetNodeAvailability(id, availability) => self.set_node_availability(id, availability).await?,
+                AppEvent::UpdateSwarmObjects(kind) => self.update_swarm_objects(kind).await?,
+                AppEvent::CreateSwarmObject(kind, object) => self.create_swarm_object(kind, *object).await?,
+                AppEvent::RemoveSwarmObject(kind, id) => self.remove_swarm_object(kind, id).await?,
+                AppEvent::UpdateSystem => self.update_system().await?,
+                AppEvent::ShowDaemonInfo => self.show_daemon_info().await?,
+                AppEvent::UpdateDaemonInfo => self.update_daemon_info().await?,
+                AppEvent::SystemPrune(target) => self.system_prune(target).await?,
+                AppEvent::BackupVolume(name, path) => self.backup_volume(name, path),
+                AppEvent::RestoreVolume(name, path, replace) => self.restore_volume(name, path, replace),
+                AppEvent::CloneVolume(source, target, rewire, remove_source) => {
+                    self.clone_volume(source, target, rewire, remove_source)
+                }
+                AppEvent::SaveImages(names, path) => self.save_images(names, path),
+                AppEvent::LoadImages(path) => self.load_images(path),
+                AppEvent::RunContainer(config) => self.run_container(config).await?,
+                AppEvent::ShowDependents(target) => self.show_dependents(target).await?,
+                AppEvent::TransferProgress(bytes) => self.update_transfer(bytes),
+                AppEvent::TransferFinished(result) => self.finish_transfer(result).await?,
                 AppEvent::RemoveImage(id, force) => self.remove_image(id, force).await?,
-                AppEvent::Back => self.container_info = None,
+                AppEvent::Back => self.back(),
             },
         }
         Ok(())
@@ -137,11 +295,45 @@ impl App {
             return Ok(Some(AppEvent::Quit));
         }
 
+        if self.transfer.as_ref().is_some_and(|t| !t.is_running()) {
+            self.transfer = None;
+        }
+
+        if let Some(file_browser) = self.file_browser.as_mut() {
+            return Ok(file_browser.handle_key_event(key_event));
+        }
+
         if let Some(info) = self.container_info.as_mut() {
             return info.handle_key_event(key_event);
         }
 
+        if let Some(info) = self.volume_info.as_mut() {
+            return info.handle_key_event(key_event);
+        }
+
+        if let Some(info) = self.network_info.as_mut() {
+            return info.handle_key_event(key_event);
+        }
+
+        if let Some(topology) = self.network_topology.as_mut() {
+            return Ok(topology.handle_key_event(key_event));
+        }
+
+        if let Some(info) = self.daemon_info.as_mut() {
+            return info.handle_key_event(key_event);
+        }
+
+        if let Some(compose_view) = self.compose_view.as_mut() {
+            return Ok(compose_view.handle_key_event(key_event));
+        }
+
+        // An open form takes every key, so the tab shortcuts below must not be caught.
+        if self.is_tab_form_open() {
+            return self.handle_tab_key_event(key_event);
+        }
+
         let event = match key_event.code {
+            KeyCode::Char('v' | 'V') => Some(AppEvent::ShowDaemonInfo),
             KeyCode::Right | KeyCode::Char('l' | 'L') => {
                 self.next_tab();
                 None
@@ -150,23 +342,59 @@ impl App {
                 self.previous_tab();
                 None
             }
-            _ => match self.selected_tab {
-                SelectedTab::Containers => self.container_table.handle_key_event(key_event)?,
-                SelectedTab::Volumes => self.volume_table.handle_key_event(key_event)?,
-                SelectedTab::Networks => self.network_table.handle_key_event(key_event)?,
-                SelectedTab::Images => self.image_table.handle_key_event(key_event)?,
-            },
+            _ => self.handle_tab_key_event(key_event)?,
         };
 
         Ok(event)
     }
 
+    fn handle_tab_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        match self.selected_tab {
+            SelectedTab::Containers => self.container_table.handle_key_event(key_event),
+            SelectedTab::Volumes => self.volume_table.handle_key_event(key_event),
+            SelectedTab::Networks => self.network_table.handle_key_event(key_event),
+            SelectedTab::Images => self.image_table.handle_key_event(key_event),
+            SelectedTab::Services => self.service_table.handle_key_event(key_event),
+            SelectedTab::Tasks => self.task_table.handle_key_event(key_event),
+            SelectedTab::Nodes => self.node_table.handle_key_event(key_event),
+            SelectedTab::Secrets => self.secret_table.handle_key_event(key_event),
+            SelectedTab::Configs => self.config_table.handle_key_event(key_event),
+            SelectedTab::System => self.system_view.handle_key_event(key_event),
+        }
+    }
+
+    fn is_tab_form_open(&self) -> bool {
+        match self.selected_tab {
+            SelectedTab::Volumes => self.volume_table.is_form_open(),
+            SelectedTab::Networks => self.network_table.is_form_open(),
+            SelectedTab::Images => self.image_table.is_form_open(),
+            SelectedTab::Services => self.service_table.is_form_open(),
+            SelectedTab::Nodes => self.node_table.is_form_open(),
+            SelectedTab::Secrets => self.secret_table.is_form_open(),
+            SelectedTab::Configs => self.config_table.is_form_open(),
+            _ => false,
+        }
+    }
+
+    /// The tabs shown in the header, the swarm ones only when the daemon is a swarm manager and
+    /// configs only when they can be reached.
+    fn visible_tabs(&self) -> Vec<SelectedTab> {
+        SelectedTab::iter()
+            .filter(|tab| self.is_swarm_manager || !tab.is_swarm())
+            .filter(|tab| !matches!(tab, SelectedTab::Configs) || self.docker_client.supports_configs())
+            .collect()
+    }
+
     fn next_tab(&mut self) {
-        self.selected_tab = self.selected_tab.next()
+        let tabs = self.visible_tabs();
+        let index = tabs.iter().position(|tab| *tab == self.selected_tab).unwrap_or_default();
+        self.selected_tab = tabs.get(index + 1).copied().unwrap_or(self.selected_tab);
     }
 
     fn previous_tab(&mut self) {
-        self.selected_tab = self.selected_tab.previous()
+        let tabs = self.visible_tabs();
+        let index = tabs.iter().position(|tab| *tab == self.selected_tab).unwrap_or_default();
+        self.selected_tab = tabs.get(index.saturating_sub(1)).copied().unwrap_or(self.selected_tab);
     }
 
     async fn go_to_container_info(&mut self, container_id: String) -> Result<()> {
@@ -178,16 +406,127 @@ impl App {
         Ok(())
     }
 
+    /// Commits the container and shows the new image in the images tab.
+    /// Commits in the background, as it copies the whole filesystem of the container and pauses
+    /// it meanwhile by default. The new image is highlighted once it exists.
+    fn commit_container(&mut self, options: CommitContainerOptions) {
+        let reference = format!("{}:{}", options.repo.as_deref().unwrap_or_default(), options.tag.as_deref().unwrap_or_default());
+        let sender = self.events.sender();
+
+        let started = self.spawn_transfer(format!("Committing {reference}"), move |client, _| async move {
+            let id = client.commit_container(options).await?;
+            sender.send(AppEvent::ContainerCommitted(id, reference.clone()));
+            Ok(format!("Committed {reference}"))
+        });
+
+        if !started && let Some(info) = self.container_info.as_mut() {
+            info.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    async fn show_committed_image(&mut self, id: String, reference: String) -> Result<()> {
+        self.update_images().await?;
+        self.image_table.show_committed(&id, &reference);
+        Ok(())
+    }
+
+    async fn rename_container(&mut self, id: String, name: String) -> Result<()> {
+        let result = self.docker_client.rename_container(&id, &name).await;
+        self.refresh_container_details(id, result).await
+    }
+
+    /// Applies the new restart policy and limits, which the details then show.
+    async fn update_container(&mut self, id: String, update: ContainerUpdateBody) -> Result<()> {
+        let result = self.docker_client.update_container(&id, update).await;
+        self.refresh_container_details(id, result).await
+    }
+
+    async fn refresh_container_details(&mut self, id: String, result: Result<()>) -> Result<()> {
+        if let Err(e) = result {
+            if let Some(info) = self.container_info.as_mut() {
+                info.show_err(&e.to_string());
+            }
+            return Ok(());
+        }
+
+        self.update_containers().await?;
+        self.update_container_details(id).await
+    }
+
+    fn export_container(&mut self, id: String, path: String) {
+        let label = format!("Exporting {} to {path}", &id[..id.len().min(12)]);
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let bytes = client.export_container(&id, &path, progress).await?;
+            Ok(format!("Exported {} to {path} ({})", &id[..id.len().min(12)], size_string(bytes as i64)))
+        });
+
+        if !started && let Some(info) = self.container_info.as_mut() {
+            info.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    async fn go_to_volume_info(&mut self, name: String) -> Result<()> {
+        if let Some(data) = self.get_volume_data(name).await {
+            let mut volume_info_block = VolumeInfoBlock::default();
+            volume_info_block.update_data(data);
+            self.volume_info = Some(Box::new(volume_info_block));
+        }
+        Ok(())
+    }
+
+    /// Closes the detail view currently shown, container details being drawn on top of the others.
+    fn back(&mut self) {
+        if self.container_info.is_some() {
+            self.container_info = None;
+        } else {
+            self.volume_info = None;
+            self.network_info = None;
+            self.network_topology = None;
+            self.daemon_info = None;
+            self.compose_view = None;
+        }
+    }
+
     fn tick(&mut self) -> Result<Option<AppEvent>> {
+        if self.file_browser.is_some() {
+            return Ok(None);
+        }
+
         if let Some(info) = self.container_info.as_mut() {
             return info.tick();
         }
 
+        if let Some(info) = self.volume_info.as_mut() {
+            return info.tick();
+        }
+
+        if let Some(info) = self.network_info.as_mut() {
+            return info.tick();
+        }
+
+        if let Some(topology) = self.network_topology.as_mut() {
+            return Ok(topology.tick());
+        }
+
+        if let Some(info) = self.daemon_info.as_mut() {
+            return info.tick();
+        }
+
+        if let Some(compose_view) = self.compose_view.as_mut() {
+            return Ok(compose_view.tick());
+        }
+
         let event = match self.selected_tab {
             SelectedTab::Containers => self.container_table.tick()?,
             SelectedTab::Volumes => self.volume_table.tick()?,
             SelectedTab::Networks => self.network_table.tick()?,
             SelectedTab::Images => self.image_table.tick()?,
+            SelectedTab::Services => self.service_table.tick()?,
+            SelectedTab::Tasks => self.task_table.tick()?,
+            SelectedTab::Nodes => self.node_table.tick()?,
+            SelectedTab::Secrets => self.secret_table.tick()?,
+            SelectedTab::Configs => self.config_table.tick()?,
+            SelectedTab::System => self.system_view.tick()?,
         };
 
         Ok(event)
@@ -205,10 +544,40 @@ impl App {
     }
 
     async fn update_container_details(&mut self, container_id: String) -> Result<()> {
-        if let Some(data) = self.get_container_data(container_id).await {
-            if let Some(info_block) = self.container_info.as_mut() {
-                info_block.update_data(data);
-            }
+        if let Some(data) = self.get_container_data(container_id).await
+            && let Some(info_block) = self.container_info.as_mut() {
+            info_block.update_data(data);
+        }
+        Ok(())
+    }
+
+    async fn get_volume_data(&self, name: String) -> Option<VolumeData> {
+        let volume = self.docker_client.inspect_volume(&name).await.ok()?;
+        let usage = self.docker_client.system_df().await.ok().and_then(|df| df.volumes).unwrap_or_default();
+        Some(VolumeData::from(volume, usage.iter().find(|v| v.name == name)))
+    }
+
+    async fn update_volume_details(&mut self, name: String) -> Result<()> {
+        if let Some(data) = self.get_volume_data(name).await
+            && let Some(info_block) = self.volume_info.as_mut() {
+            info_block.update_data(data);
+        }
+        Ok(())
+    }
+
+    async fn go_to_network_info(&mut self, id: String) -> Result<()> {
+        if let Ok(network) = self.docker_client.inspect_network(&id).await {
+            let mut network_info_block = NetworkInfoBlock::default();
+            network_info_block.update_data(NetworkData::from(network));
+            self.network_info = Some(Box::new(network_info_block));
+        }
+        Ok(())
+    }
+
+    async fn update_network_details(&mut self, id: String) -> Result<()> {
+        if let Ok(network) = self.docker_client.inspect_network(&id).await
+            && let Some(info_block) = self.network_info.as_mut() {
+            info_block.update_data(NetworkData::from(network));
         }
         Ok(())
     }
@@ -227,10 +596,96 @@ impl App {
         Ok(())
     }
 
+    /// Runs `action` on every container of a compose project or service at once.
+    async fn run_group_action(&mut self, action: GroupAction, key: String, ids: Vec<String>) -> Result<()> {
+        let client = &self.docker_client;
+        let results = join_all(ids.iter().map(|id| async move {
+            match action {
+                GroupAction::Start => client.start_container(id).await,
+                GroupAction::Stop => client.stop_container(id).await,
+                GroupAction::Restart => client.restart_container(id).await,
+                GroupAction::Remove => client.remove_container(id).await,
+            }
+        }))
+        .await;
+
+        let failed = results.iter().filter_map(|result| result.as_ref().err()).collect::<Vec<_>>();
+        match failed.first() {
+            Some(e) => self.container_table.show_err(&format!("{} of {} failed: {e}", failed.len(), ids.len())),
+            None => self.container_table.show_group_result(action, &key, ids.len()),
+        }
+        self.update_containers().await
+    }
+
+    async fn show_compose_project(&mut self, project: String) -> Result<()> {
+        self.compose_view = Some(ComposeView::new(project));
+        self.update_compose_project().await
+    }
+
+    async fn update_compose_project(&mut self) -> Result<()> {
+        let Some(project) = self.compose_view.as_ref().map(|view| view.project_name().to_string()) else {
+            return Ok(());
+        };
+
+        let mut containers = vec![];
+        for summary in self.docker_client.list_project_containers(&project).await.unwrap_or_default() {
+            if let Ok(container) = self.docker_client.inspect_container(summary.id.as_deref().unwrap_or_default()).await {
+                containers.push(container);
+            }
+        }
+
+        let source = containers.iter()
+            .find_map(|c| c.config.as_ref()?.labels.as_ref().and_then(ComposeSource::from_labels));
+        if let Some(compose_view) = self.compose_view.as_mut() {
+            compose_view.update(source, containers);
+        }
+        Ok(())
+    }
+
+    fn run_compose_action(&mut self, action: ComposeAction) {
+        let Some(compose_view) = self.compose_view.as_ref() else { return };
+        let name = compose_view.project_name().to_string();
+        let project = compose_view.project().cloned();
+
+        let started = match (action, project) {
+            (ComposeAction::Down, _) => self.spawn_transfer(format!("Taking down {name}"), move |client, _| async move {
+                client.compose_down(&name).await
+            }),
+            (ComposeAction::Up, Some(project)) => self.spawn_transfer(format!("Bringing up {name}"), move |client, _| async move {
+                let summary = client.compose_up(&project).await?;
+                Ok(format!("Up {}: {summary}", project.name))
+            }),
+            (ComposeAction::Pull, Some(project)) => self.spawn_transfer(format!("Pulling the images of {name}"), move |client, _| async move {
+                client.compose_pull(&project).await
+            }),
+            (ComposeAction::Recreate(service), Some(project)) => {
+                self.spawn_transfer(format!("Recreating {service} of {name}"), move |client, _| async move {
+                    client.compose_recreate(&project, &service).await
+                })
+            }
+            (_, None) => return,
+        };
+
+        if !started && let Some(compose_view) = self.compose_view.as_mut() {
+            compose_view.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    async fn run_container(&mut self, config: RunConfig) -> Result<()> {
+        match self.docker_client.run_container(config).await {
+            Ok(id) => {
+                self.update_containers().await?;
+                self.go_to_container_info(id).await?;
+            }
+            Err(e) => self.image_table.show_err(&format!("Run failed: {e}")),
+        }
+        Ok(())
+    }
+
     async fn update_containers(&mut self) -> Result<()> {
         if let Ok(result) = self.docker_client.list_containers().await {
             let containers = ContainerTableRow::from_list(result);
-            self.container_table.update_with_items(containers);
+            self.container_table.update_containers(containers);
         }
         Ok(())
     }
@@ -240,6 +695,10 @@ impl App {
             let volumes = VolumeTableRow::from_list(result);
             self.volume_table.update_with_items(volumes);
         }
+
+        if let Some(target) = self.volume_table.dependents_target() {
+            self.show_dependents(target).await?;
+        }
         Ok(())
     }
 
@@ -248,17 +707,221 @@ impl App {
             let networks = NetworkTableRow::from_list(result);
             self.network_table.update_with_items(networks);
         }
+
+        if let Some(target) = self.network_table.dependents_target() {
+            self.show_dependents(target).await?;
+        }
         Ok(())
     }
 
     async fn update_images(&mut self) -> Result<()> {
         if let Ok(result) = self.docker_client.list_images().await {
-            let images = ImageTableRow::from_list(result);
-            self.image_table.update_with_items(images);
+            self.image_table.update_images(result);
+        }
+
+        if let Some(target) = self.image_table.dependents_target() {
+            self.show_dependents(target).await?;
+        }
+        Ok(())
+    }
+
+    async fn update_image_usage(&mut self) -> Result<()> {
+        if let Some(images) = self.docker_client.system_df().await.ok().and_then(|df| df.images) {
+            self.image_table.update_usage(images);
+        }
+        self.update_images().await
+    }
+
+    /// The preview is built from fresh data, as the usage is otherwise only refreshed now and then.
+    async fn preview_image_prune(&mut self) -> Result<()> {
+        self.update_image_usage().await?;
+        self.image_table.open_prune_preview();
+        Ok(())
+    }
+
+    async fn show_dependents(&mut self, target: DependencyTarget) -> Result<()> {
+        if let Ok(containers) = self.docker_client.list_containers().await {
+            match target {
+                DependencyTarget::Image(_) => self.image_table.update_dependents(target, containers),
+                DependencyTarget::Volume(_) => self.volume_table.update_dependents(target, containers),
+                DependencyTarget::Network(_) => self.network_table.update_dependents(target, containers),
+            }
+        }
+        Ok(())
+    }
+
+    async fn create_volume(&mut self, options: VolumeCreateOptions) -> Result<()> {
+        match self.docker_client.create_volume(options).await {
+            Ok(volume) => {
+                self.update_volumes().await?;
+                self.volume_table.show_created(&volume.name);
+            }
+            Err(e) => self.volume_table.show_err(&e.to_string()),
+        }
+        Ok(())
+    }
+
+    async fn create_network(&mut self, options: NetworkCreateRequest) -> Result<()> {
+        let name = options.name.clone();
+        match self.docker_client.create_network(options).await {
+            Ok(_) => {
+                self.update_networks().await?;
+                self.network_table.show_created(&name);
+            }
+            Err(e) => self.network_table.show_err(&e.to_string()),
+        }
+        Ok(())
+    }
+
+    async fn show_network_topology(&mut self) -> Result<()> {
+        self.network_topology = Some(NetworkTopology::default());
+        self.update_network_topology().await
+    }
+
+    async fn update_network_topology(&mut self) -> Result<()> {
+        if let Ok(containers) = self.docker_client.list_containers().await
+            && let Some(topology) = self.network_topology.as_mut() {
+            topology.update(containers);
+        }
+        Ok(())
+    }
+
+    async fn connect_network(&mut self, connection: NetworkConnection) -> Result<()> {
+        let NetworkConnection { network, container, alias, ip } = connection;
+        let result = self.docker_client.connect_network(&network, &container, &alias, &ip).await;
+        self.finish_network_change(result, network, container).await
+    }
+
+    async fn disconnect_network(&mut self, network: String, container: String, force: bool) -> Result<()> {
+        let result = self.docker_client.disconnect_network(&network, &container, force).await;
+        self.finish_network_change(result, network, container).await
+    }
+
+    /// Refreshes the detail view the (dis)connect was started from and reports a failure there.
+    async fn finish_network_change(&mut self, result: Result<()>, network: String, container: String) -> Result<()> {
+        if self.container_info.is_some() {
+            self.update_container_details(container).await?;
+            if let (Err(e), Some(info)) = (result, self.container_info.as_mut()) {
+                info.show_err(&e.to_string());
+            }
+        } else {
+            self.update_network_details(network).await?;
+            if let (Err(e), Some(info)) = (result, self.network_info.as_mut()) {
+                info.show_err(&e.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    async fn browse_volume(&mut self, name: String) -> Result<()> {
+        match self.docker_client.start_helper(vec![format!("{name}:{VOLUME_MOUNT_PATH}:ro")]).await {
+            Ok(id) => {
+                let title = format!("Volume: {name}");
+                self.file_browser = Some(FileBrowser::new(title, id.clone(), VOLUME_MOUNT_PATH, true));
+                self.list_directory(id, VOLUME_MOUNT_PATH.to_string()).await;
+            }
+            Err(e) => self.volume_table.show_err(&format!("Failed to start helper container: {e}")),
         }
         Ok(())
     }
 
+    /// Lists a directory of the browsed container. Helper containers are listed with a command,
+    /// other containers from their archive, read once when browsing starts, along with their
+    /// changes compared to the image.
+    async fn list_directory(&mut self, container_id: String, path: String) {
+        let client = &self.docker_client;
+        let Some(file_browser) = self.file_browser.as_mut() else {
+            return;
+        };
+
+        let result = if file_browser.is_helper() {
+            client.list_directory(&container_id, &path).await
+        } else {
+            file_browser.update_changes(client.container_changes(&container_id).await.unwrap_or_default());
+
+            let root = file_browser.root().to_string();
+            let loaded = match file_browser.needs_tree() {
+                true => client.list_archive_tree(&container_id, &root).await.map(|tree| file_browser.update_tree(&root, tree)),
+                false => Ok(()),
+            };
+            loaded.and_then(|_| file_browser.tree_entries(&path).ok_or_else(|| eyre!("No such directory: {path}")))
+        };
+
+        match result {
+            Ok(entries) => file_browser.update_entries(path, entries),
+            Err(e) => file_browser.show_err(&e.to_string()),
+        }
+    }
+
+    /// Reads the archive of `dir` again after an upload to it, so the cached tree shows the copy.
+    async fn reload_directory_tree(&mut self, container_id: &str, dir: &str) {
+        let result = self.docker_client.list_archive_tree(container_id, dir).await;
+        if let Some(file_browser) = self.file_browser.as_mut() {
+            match result {
+                Ok(tree) => file_browser.update_tree(dir, tree),
+                Err(e) => file_browser.show_err(&e.to_string()),
+            }
+        }
+    }
+
+    async fn read_file(&mut self, container_id: String, path: String) {
+        let result = self.docker_client.read_file(&container_id, &path).await;
+        if let Some(file_browser) = self.file_browser.as_mut() {
+            match result {
+                Ok(content) => file_browser.show_file(path, content),
+                Err(e) => file_browser.show_err(&e.to_string()),
+            }
+        }
+    }
+
+    /// Browses the filesystem of a container, running or not.
+    async fn browse_container(&mut self, id: String) {
+        let name = match self.docker_client.inspect_container(&id).await {
+            Ok(container) => container.name.unwrap_or_default().trim_start_matches('/').to_string(),
+            Err(_) => id.clone(),
+        };
+
+        self.file_browser = Some(FileBrowser::new(format!("Container: {name}"), id.clone(), "/", false));
+        self.list_directory(id, "/".to_string()).await;
+    }
+
+    fn download_path(&mut self, container_id: String, path: String, dest: String) {
+        let dest = expand_home(&dest);
+        let label = format!("Downloading {path} to {dest}");
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let bytes = client.download_path(&container_id, &path, &dest, progress).await?;
+            Ok(format!("Downloaded {path} to {dest} ({})", size_string(bytes as i64)))
+        });
+
+        if !started && let Some(file_browser) = self.file_browser.as_mut() {
+            file_browser.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    fn upload_path(&mut self, container_id: String, source: String, dest: String) {
+        let source = expand_home(&source);
+        let label = format!("Uploading {source} to {dest}");
+        let target = (container_id.clone(), dest.clone());
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let bytes = client.upload_path(&container_id, &source, &dest, progress).await?;
+            Ok(format!("Uploaded {source} to {dest} ({})", size_string(bytes as i64)))
+        });
+
+        if started {
+            self.upload_target = Some(target);
+        } else if let Some(file_browser) = self.file_browser.as_mut() {
+            file_browser.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    /// Closes the file browser, removing its helper container if it started one.
+    async fn close_file_browser(&mut self) {
+        if let Some(file_browser) = self.file_browser.take()
+            && file_browser.is_helper() {
+            let _ = self.docker_client.remove_container(file_browser.container_id()).await;
+        }
+    }
+
     async fn remove_volume(&mut self, name: String, force: bool) -> Result<()> {
         if let Err(e) = self.docker_client.remove_volume(&name, force).await {
             self.volume_table.show_remove_volume_err(e.to_string());
@@ -273,6 +936,337 @@ impl App {
         Ok(())
     }
 
+    async fn prune_images(&mut self, dangling_only: bool) -> Result<()> {
+        match self.docker_client.prune_images(dangling_only).await {
+            Ok(response) => {
+                let deleted_count = response.images_deleted
+                    .map_or(0, |items| items.iter().filter(|i| i.deleted.is_some()).count());
+                self.image_table.show_prune_result(deleted_count, response.space_reclaimed.unwrap_or(0));
+                self.update_image_usage().await?;
+            }
+            Err(e) => self.image_table.show_prune_err(e.to_string()),
+        }
+        Ok(())
+    }
+
+    async fn get_daemon_data(&self) -> Option<DaemonData> {
+        let info = self.docker_client.info().await.ok()?;
+        let version = self.docker_client.version().await.ok()?;
+        Some(DaemonData::from(info, version, self.docker_client.api_version()))
+    }
+
+    async fn show_daemon_info(&mut self) -> Result<()> {
+        if let Some(data) = self.get_daemon_data().await {
+            let mut daemon_info_block = DaemonInfoBlock::default();
+            daemon_info_block.update_data(data);
+            self.daemon_info = Some(Box::new(daemon_info_block));
+        }
+        Ok(())
+    }
+
+    async fn update_daemon_info(&mut self) -> Result<()> {
+        if let Some(data) = self.get_daemon_data().await
+            && let Some(info_block) = self.daemon_info.as_mut() {
+            info_block.update_data(data);
+        }
+        Ok(())
+    }
+
+    async fn update_services(&mut self) -> Result<()> {
+        if let Ok(services) = self.docker_client.list_services().await {
+            self.service_table.update_with_items(ServiceTableRow::from_list(services));
+        }
+        Ok(())
+    }
+
+    async fn show_service_tasks(&mut self, id: String) -> Result<()> {
+        let name = self.service_table.service_name(&id).unwrap_or_else(|| id.clone());
+        self.task_table.filter_service(Some((id, name)));
+        self.selected_tab = SelectedTab::Tasks;
+        self.update_tasks().await
+    }
+
+    async fn scale_service(&mut self, id: String, replicas: i64) -> Result<()> {
+        let result = self.docker_client.scale_service(&id, replicas).await;
+        self.finish_service_update(result, &id, &format!("to {replicas} replica(s)"), "Scaled").await
+    }
+
+    async fn update_service_image(&mut self, id: String, image: String) -> Result<()> {
+        let result = self.docker_client.update_service_image(&id, &image).await;
+        self.finish_service_update(result, &id, &format!("to {image}"), "Updating").await
+    }
+
+    async fn rollback_service(&mut self, id: String) -> Result<()> {
+        let result = self.docker_client.rollback_service(&id).await;
+        self.finish_service_update(result, &id, "to its previous spec", "Rolling back").await
+    }
+
+    /// Reports a service update in the services tab, e.g. "Scaled web to 3 replica(s)".
+    async fn finish_service_update(&mut self, result: Result<()>, id: &str, change: &str, action: &str) -> Result<()> {
+        let name = self.service_table.service_name(id).unwrap_or_else(|| id.to_string());
+        match result {
+            Ok(()) => self.service_table.show_notice(&format!("{action} {name} {change}")),
+            Err(e) => self.service_table.show_err(&e.to_string()),
+        }
+        self.update_services().await
+    }
+
+    async fn update_tasks(&mut self) -> Result<()> {
+        let client = &self.docker_client;
+        if let Ok(tasks) = client.list_tasks(self.task_table.service_id()).await {
+            let services = client.list_services().await.unwrap_or_default();
+            let nodes = client.list_nodes().await.unwrap_or_default();
+            self.task_table.update_tasks(tasks, &services, &nodes);
+        }
+        Ok(())
+    }
+
+    async fn update_nodes(&mut self) -> Result<()> {
+        if let Ok(nodes) = self.docker_client.list_nodes().await {
+            self.node_table.update_with_items(NodeTableRow::from_list(nodes));
+        }
+        Ok(())
+    }
+
+    async fn set_node_availability(&mut self, id: String, availability: NodeSpecAvailabilityEnum) -> Result<()> {
+        match self.docker_client.set_node_availability(&id, availability).await {
+            Ok(()) => self.node_table.show_availability(&id, availability),
+            Err(e) => self.node_table.show_err(&e.to_string()),
+        }
+        self.update_nodes().await
+    }
+
+    fn swarm_object_table(&mut self, kind: SwarmObjectKind) -> &mut SwarmObjectTable {
+        match kind {
+            SwarmObjectKind::Secret => &mut self.secret_table,
+            SwarmObjectKind::Config => &mut self.config_table,
+        }
+    }
+
+    async fn update_swarm_objects(&mut self, kind: SwarmObjectKind) -> Result<()> {
+        let client = &self.docker_client;
+        let services = client.list_services().await.unwrap_or_default();
+        let rows = match kind {
+            SwarmObjectKind::Secret => client.list_secrets().await.map(|s| SwarmObjectTableRow::from_secrets(s, &services)),
+            SwarmObjectKind::Config => client.list_configs().await.map(|c| SwarmObjectTableRow::from_configs(c, &services)),
+        };
+
+        match rows {
+            Ok(rows) => self.swarm_object_table(kind).update_with_items(rows),
+            Err(e) => self.swarm_object_table(kind).show_err(&e.to_string()),
+        }
+        Ok(())
+    }
+
+    async fn create_swarm_object(&mut self, kind: SwarmObjectKind, object: NewSwarmObject) -> Result<()> {
+        let data = match (&object.file, object.value) {
+            (Some(path), _) => std::fs::read(expand_home(path)).map_err(|e| format!("Can't read {path}: {e}")),
+            (None, value) => Ok(value.unwrap_or_default().into_bytes()),
+        };
+
+        let client = &self.docker_client;
+        let result = match (kind, data) {
+            (_, Err(e)) => Err(e),
+            (SwarmObjectKind::Secret, Ok(data)) => client.create_secret(&object.name, &data, object.labels).await.map_err(|e| e.to_string()),
+            (SwarmObjectKind::Config, Ok(data)) => client.create_config(&object.name, &data, object.labels).await.map_err(|e| e.to_string()),
+        };
+
+        self.update_swarm_objects(kind).await?;
+        match result {
+            Ok(_) => self.swarm_object_table(kind).show_created(&object.name),
+            Err(e) => self.swarm_object_table(kind).show_err(&e),
+        }
+        Ok(())
+    }
+
+    async fn remove_swarm_object(&mut self, kind: SwarmObjectKind, id: String) -> Result<()> {
+        let result = match kind {
+            SwarmObjectKind::Secret => self.docker_client.remove_secret(&id).await,
+            SwarmObjectKind::Config => self.docker_client.remove_config(&id).await,
+        };
+
+        match result {
+            Ok(()) => self.swarm_object_table(kind).show_removed(&id),
+            Err(e) => self.swarm_object_table(kind).show_err(&e.to_string()),
+        }
+        self.update_swarm_objects(kind).await
+    }
+
+    async fn update_system(&mut self) -> Result<()> {
+        if let Ok(df) = self.docker_client.system_df().await {
+            let networks = self.docker_client.list_networks().await.unwrap_or_default();
+            self.system_view.update(df, networks);
+        }
+        Ok(())
+    }
+
+    async fn system_prune(&mut self, target: PruneTarget) -> Result<()> {
+        let client = &self.docker_client;
+        let result = match target {
+            PruneTarget::StoppedContainers => client.prune_containers().await
+                .map(|r| (r.containers_deleted.unwrap_or_default().len(), r.space_reclaimed)),
+            PruneTarget::DanglingImages | PruneTarget::UnusedImages => {
+                client.prune_images(target == PruneTarget::DanglingImages).await.map(|r| {
+                    let deleted_count = r.images_deleted.map_or(0, |items| items.iter().filter(|i| i.deleted.is_some()).count());
+                    (deleted_count, r.space_reclaimed)
+                })
+            }
+            PruneTarget::UnusedVolumes => client.prune_volumes().await
+                .map(|r| (r.volumes_deleted.unwrap_or_default().len(), r.space_reclaimed)),
+            PruneTarget::UnusedNetworks => client.prune_networks().await
+                .map(|r| (r.networks_deleted.unwrap_or_default().len(), None)),
+            PruneTarget::BuildCache => client.prune_build_cache().await
+                .map(|r| (r.caches_deleted.unwrap_or_default().len(), r.space_reclaimed)),
+        };
+
+        match result {
+            Ok((deleted_count, space_reclaimed)) => {
+                self.system_view.show_prune_result(target, deleted_count, space_reclaimed.unwrap_or(0));
+                self.update_system().await?;
+            }
+            Err(e) => self.system_view.show_err(&format!("Prune failed: {e}")),
+        }
+        Ok(())
+    }
+
+    fn backup_volume(&mut self, name: String, path: String) {
+        let label = format!("Backing up volume {name} to {path}");
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let (bytes, checksum) = client.backup_volume(&name, &path, progress).await?;
+            Ok(format!("Backed up {name} to {path} ({}, sha256 {})", size_string(bytes as i64), &checksum[..12]))
+        });
+
+        if !started {
+            self.volume_table.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    fn restore_volume(&mut self, name: String, path: String, replace: bool) {
+        let label = format!("Restoring {path} into volume {name}");
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let is_verified = client.restore_volume(&name, &path, replace, progress).await?;
+            let checksum = if is_verified { "checksum verified" } else { "no checksum file" };
+            Ok(format!("Restored {path} into {name} ({checksum})"))
+        });
+
+        if !started {
+            self.volume_table.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    fn clone_volume(&mut self, source: String, target: String, rewire: bool, remove_source: bool) {
+        let action = if remove_source { "Renaming" } else { "Cloning" };
+        let label = format!("{action} volume {source} to {target}");
+        let started = self.spawn_transfer(label, move |client, _| async move {
+            client.clone_volume(&source, &target).await?;
+            let mut msg = format!("Copied {source} to {target}");
+
+            if rewire {
+                let (rewired, skipped) = client.rewire_volume(&source, &target).await?;
+                if !rewired.is_empty() {
+                    msg.push_str(&format!(", rewired {}", rewired.join(", ")));
+                }
+                if !skipped.is_empty() {
+                    msg.push_str(&format!(", skipped running {}", skipped.join(", ")));
+                }
+            }
+
+            if remove_source {
+                match client.remove_volume(&source, false).await {
+                    Ok(()) => msg.push_str(&format!(", removed {source}")),
+                    Err(_) => msg.push_str(&format!(", kept {source} as it is still in use")),
+                }
+            }
+
+            Ok(msg)
+        });
+
+        if !started {
+            self.volume_table.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    fn save_images(&mut self, names: Vec<String>, path: String) {
+        let label = format!("Saving {} image(s) to {path}", names.len());
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let bytes = client.export_images(&names, &path, progress).await?;
+            Ok(format!("Saved {} image(s) to {path} ({})", names.len(), size_string(bytes as i64)))
+        });
+
+        if !started {
+            self.image_table.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    fn load_images(&mut self, path: String) {
+        let label = format!("Loading images from {path}");
+        let started = self.spawn_transfer(label, move |client, progress| async move {
+            let images = client.import_images(&path, progress).await?;
+            Ok(match images.is_empty() {
+                true => format!("Loaded images from {path}"),
+                false => format!("Loaded {}", images.join(", ")),
+            })
+        });
+
+        if !started {
+            self.image_table.show_err(TRANSFER_IN_PROGRESS_ERR);
+        }
+    }
+
+    /// Runs `task` in the background, reporting its progress and result in the transfer status line.
+    /// Returns `false` without running it if another transfer is still in progress.
+    fn spawn_transfer<F, Fut>(&mut self, label: String, task: F) -> bool
+    where
+        F: FnOnce(DockerClient, ProgressFn) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<String>> + Send + 'static,
+    {
+        if self.transfer.as_ref().is_some_and(TransferStatus::is_running) {
+            return false;
+        }
+
+        self.transfer = Some(TransferStatus::new(label));
+
+        let client = self.docker_client.clone();
+        let sender = self.events.sender();
+        tokio::spawn(async move {
+            let progress_sender = sender.clone();
+            let progress: ProgressFn = Box::new(move |bytes| progress_sender.send(AppEvent::TransferProgress(bytes)));
+            let result = task(client, progress).await.map_err(|e| e.to_string());
+            sender.send(AppEvent::TransferFinished(result));
+        });
+
+        true
+    }
+
+    fn update_transfer(&mut self, bytes: u64) {
+        if let Some(transfer) = self.transfer.as_mut() {
+            transfer.update(bytes);
+        }
+    }
+
+    async fn finish_transfer(&mut self, result: Result<String, String>) -> Result<()> {
+        if let Some(transfer) = self.transfer.as_mut() {
+            transfer.finish(result);
+        }
+        // Only an upload changes what the browser shows, and only below the target directory.
+        let browsed = self.file_browser.as_ref()
+            .map(|f| (f.container_id().to_string(), f.path().to_string(), f.is_helper()));
+        if let Some((id, dest)) = self.upload_target.take()
+            && let Some((browsed_id, path, is_helper)) = browsed
+            && id == browsed_id {
+            if !is_helper {
+                self.reload_directory_tree(&id, &dest).await;
+            }
+            if !is_helper || path == dest {
+                self.list_directory(id, path).await;
+            }
+        }
+        self.update_containers().await?;
+        self.update_images().await?;
+        self.update_compose_project().await?;
+        self.update_volumes().await
+    }
+
     async fn remove_image(&mut self, id: String, force: bool) -> Result<()> {
         if let Err(e) = self.docker_client.remove_image(&id, force).await {
             self.image_table.show_remove_image_err(e.to_string());
@@ -281,12 +1275,13 @@ impl App {
     }
 }
 
-fn render_title(frame: &mut Frame, area: Rect) {
-    let title = " crabd".bold();
-    frame.render_widget(title, area);
+/// The app name with the engine version, the <V> hint pointing to the daemon info panel.
+fn get_title(engine_version: Option<&str>) -> Line<'static> {
+    let engine = engine_version.map_or(" <V> engine info ".to_string(), |v| format!(" <V> engine {v} "));
+    Line::from_iter([engine.fg(tailwind::SLATE.c400), "| ".fg(tailwind::SLATE.c400), "crabd ".bold()])
 }
 
-#[derive(Default, Display, FromRepr, EnumIter, Clone, Copy)]
+#[derive(Default, Display, EnumIter, Clone, Copy, PartialEq)]
 enum SelectedTab {
     #[default]
     #[strum(to_string = "Containers")]
@@ -300,6 +1295,24 @@ enum SelectedTab {
 
     #[strum(to_string = "Images")]
     Images,
+
+    #[strum(to_string = "Services")]
+    Services,
+
+    #[strum(to_string = "Tasks")]
+    Tasks,
+
+    #[strum(to_string = "Nodes")]
+    Nodes,
+
+    #[strum(to_string = "Secrets")]
+    Secrets,
+
+    #[strum(to_string = "Configs")]
+    Configs,
+
+    #[strum(to_string = "System")]
+    System,
 }
 
 impl SelectedTab {
@@ -310,15 +1323,7 @@ impl SelectedTab {
             .into()
     }
 
-    fn next(self) -> Self {
-        let current_index = self as usize;
-        let next_index = current_index.saturating_add(1);
-        Self::from_repr(next_index).unwrap_or(self)
-    }
-
-    fn previous(self) -> Self {
-        let current_index = self as usize;
-        let previous_index = current_index.saturating_sub(1);
-        Self::from_repr(previous_index).unwrap_or(self)
+    fn is_swarm(self) -> bool {
+        matches!(self, Self::Services | Self::Tasks | Self::Nodes | Self::Secrets | Self::Configs)
     }
 }
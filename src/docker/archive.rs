@@ -0,0 +1,454 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Read, Write},
+    path::{Component, Path},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use color_eyre::eyre::{OptionExt, Result, eyre};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use futures::{Stream, StreamExt, stream};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+pub const ARCHIVE_EXTENSIONS: [&str; 3] = [".tar", ".tar.gz", ".tgz"];
+/// Minimum number of bytes between two progress callbacks, so that large transfers
+/// do not flood the event queue.
+pub const PROGRESS_STEP: u64 = 1024 * 1024;
+
+pub enum ArchiveWriter {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl ArchiveWriter {
+    /// Creates the file at `path`, compressing the content if `gzip` is set.
+    pub fn create(path: &str, gzip: bool) -> Result<Self> {
+        let writer = BufWriter::new(File::create(path)?);
+
+        if gzip {
+            Ok(Self::Gzip(GzEncoder::new(writer, Compression::default())))
+        } else {
+            Ok(Self::Plain(writer))
+        }
+    }
+
+    pub fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        match self {
+            Self::Plain(w) => w.write_all(buf)?,
+            Self::Gzip(w) => w.write_all(buf)?,
+        }
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<()> {
+        match self {
+            Self::Plain(mut w) => w.flush()?,
+            Self::Gzip(w) => w.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+pub fn is_gzip_path(path: &str) -> bool {
+    path.ends_with(".gz") || path.ends_with(".tgz")
+}
+
+/// Writes every chunk of `stream` to `path` and returns the number of bytes received. The
+/// content goes to `{path}.partial` first and only replaces `path` once the stream is complete,
+/// so a failed transfer leaves an existing file untouched. It is compressed if `path` ends with
+/// `.gz` or `.tgz`.
+pub async fn write_stream_to_file<E>(
+    stream: impl Stream<Item = Result<Bytes, E>>,
+    path: &str,
+    progress: impl Fn(u64),
+) -> Result<u64>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let partial_path = format!("{path}.partial");
+    let result = async {
+        let mut writer = ArchiveWriter::create(&partial_path, is_gzip_path(path))?;
+        let mut stream = std::pin::pin!(stream);
+        let mut total = 0;
+        let mut reported = 0;
+
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            writer.write_all(&chunk)?;
+
+            total += chunk.len() as u64;
+            if total - reported >= PROGRESS_STEP {
+                progress(total);
+                reported = total;
+            }
+        }
+
+        writer.finish()?;
+        std::fs::rename(&partial_path, path)?;
+        Ok(total)
+    }.await;
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial_path);
+    }
+    result
+}
+
+/// Streams the raw content of the file at `path` in chunks, reporting the number of bytes read.
+/// A read error ends the stream with that error, so the receiver never takes a truncated
+/// file for a complete one.
+pub async fn read_file_as_stream(
+    path: &str,
+    progress: impl Fn(u64) + Send + 'static,
+) -> Result<impl Stream<Item = std::io::Result<Bytes>> + Send + 'static> {
+    let file = tokio::fs::File::open(path).await?;
+
+    Ok(stream::unfold(Some((file, 0, 0, progress)), |state| async move {
+        let (mut file, total, reported, progress) = state?;
+        let mut buf = vec![0; CHUNK_SIZE];
+        match file.read(&mut buf).await {
+            Ok(0) => None,
+            Err(e) => Some((Err(e), None)),
+            Ok(n) => {
+                buf.truncate(n);
+                let total = total + n as u64;
+                let reported = if total - reported >= PROGRESS_STEP {
+                    progress(total);
+                    total
+                } else {
+                    reported
+                };
+                Some((Ok(Bytes::from(buf)), Some((file, total, reported, progress))))
+            }
+        }
+    }))
+}
+
+/// Returns the content of the first regular file in the tar archive.
+pub fn read_first_file(archive: &[u8]) -> Result<Vec<u8>> {
+    let mut archive = tar::Archive::new(archive);
+    let mut entry = archive.entries()?
+        .filter_map(|entry| entry.ok())
+        .find(|entry| entry.header().entry_type().is_file())
+        .ok_or_eyre("No regular file in archive")?;
+
+    let mut content = vec![];
+    entry.read_to_end(&mut content)?;
+    Ok(content)
+}
+
+/// Returns the top-level name of the first entry in the tar archive at `path`, skipping `.`
+/// entries, or `None` if the archive has none.
+pub fn archive_top_level(path: &str) -> Result<Option<String>> {
+    let file = File::open(path)?;
+    let reader: Box<dyn Read> = if is_gzip_path(path) { Box::new(GzDecoder::new(file)) } else { Box::new(file) };
+
+    for entry in tar::Archive::new(reader).entries()? {
+        let top_level = entry?.path()?.components().find_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        });
+        if top_level.is_some() {
+            return Ok(top_level);
+        }
+    }
+    Ok(None)
+}
+
+/// Extracts the tar archive received from `stream` into `dest_dir` and returns its size.
+pub async fn unpack_stream<E>(
+    stream: impl Stream<Item = Result<Bytes, E>>,
+    dest_dir: &str,
+    progress: impl Fn(u64),
+) -> Result<u64>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    // The archive is buffered on disk first, so large files never have to fit in memory.
+    let temp_path = temp_archive_path()?;
+
+    let result = async {
+        let size = write_stream_to_file(stream, &temp_path, progress).await?;
+        std::fs::create_dir_all(dest_dir)?;
+        tar::Archive::new(File::open(&temp_path)?).unpack(Path::new(dest_dir))?;
+        Ok(size)
+    }.await;
+
+    let _ = std::fs::remove_file(&temp_path);
+    result
+}
+
+/// A path in the temp directory for an archive that is removed once used.
+pub fn temp_archive_path() -> Result<String> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+    let temp_path = std::env::temp_dir().join(format!("crabd-{}-{nanos}.tar", std::process::id()));
+    Ok(temp_path.to_string_lossy().to_string())
+}
+
+/// Packs the file or directory at `path` into a tar archive on a blocking thread and streams it
+/// in chunks as it is written, reporting the number of bytes packed. The content is archived
+/// under its own name so it is extracted as is, and symbolic links are archived as links, not
+/// followed. The returned task gives the archive size, or the packing error that also ends the
+/// stream. It stops quietly if the stream is dropped, leaving the receiver to report why.
+pub fn pack_path(
+    path: &str,
+    progress: impl Fn(u64) + Send + 'static,
+) -> Result<(impl Stream<Item = std::io::Result<Bytes>> + Send + 'static, tokio::task::JoinHandle<Result<u64>>)> {
+    let source = Path::new(path).to_path_buf();
+    let name = source.file_name().ok_or_else(|| eyre!("Can't copy {path}, it has no file name"))?.to_owned();
+    let (sender, receiver) = tokio::sync::mpsc::channel(16);
+
+    let packer = tokio::task::spawn_blocking(move || -> Result<u64> {
+        let writer = ChannelWriter { sender: sender.clone(), buf: vec![], total: 0, reported: 0, progress };
+        let result = (|| -> std::io::Result<u64> {
+            let mut builder = tar::Builder::new(writer);
+            builder.follow_symlinks(false);
+
+            if source.is_dir() {
+                builder.append_dir_all(&name, &source)?;
+            } else {
+                builder.append_path_with_name(&source, &name)?;
+            }
+
+            let mut writer = builder.into_inner()?;
+            writer.flush()?;
+            Ok(writer.total)
+        })();
+
+        match result {
+            Err(_) if sender.is_closed() => Ok(0),
+            Err(e) => {
+                let _ = sender.blocking_send(Err(std::io::Error::new(e.kind(), e.to_string())));
+                Err(e.into())
+            }
+            Ok(total) => Ok(total),
+        }
+    });
+
+    let stream = stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|chunk| (chunk, receiver))
+    });
+    Ok((stream, packer))
+}
+
+/// Blocking writer sending what is written to an async stream in chunks.
+struct ChannelWriter<F> {
+    sender: tokio::sync::mpsc::Sender<std::io::Result<Bytes>>,
+    buf: Vec<u8>,
+    total: u64,
+    reported: u64,
+    progress: F,
+}
+
+impl<F: Fn(u64)> ChannelWriter<F> {
+    fn send_buf(&mut self) -> std::io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = Bytes::from(std::mem::take(&mut self.buf));
+        self.total += chunk.len() as u64;
+        self.sender.blocking_send(Ok(chunk))
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::BrokenPipe, "The receiver stopped reading"))?;
+
+        if self.total - self.reported >= PROGRESS_STEP {
+            (self.progress)(self.total);
+            self.reported = self.total;
+        }
+        Ok(())
+    }
+}
+
+impl<F: Fn(u64)> Write for ChannelWriter<F> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        if self.buf.len() >= CHUNK_SIZE {
+            self.send_buf()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.send_buf()
+    }
+}
+
+/// Reads the tar archive received from `stream` and returns the path, type and size of its
+/// entries. Only the headers are kept, the content of the files is read past.
+pub async fn read_archive_entries<E>(
+    stream: impl Stream<Item = Result<Bytes, E>>,
+) -> Result<Vec<(String, tar::EntryType, u64)>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let (sender, receiver) = tokio::sync::mpsc::channel(16);
+
+    let parser = tokio::task::spawn_blocking(move || -> Result<Vec<(String, tar::EntryType, u64)>> {
+        let mut archive = tar::Archive::new(ChannelReader { receiver, chunk: Bytes::new() });
+        let mut entries = vec![];
+        for entry in archive.entries()? {
+            let entry = entry?;
+            let path = entry.path()?.to_string_lossy().to_string();
+            entries.push((path, entry.header().entry_type(), entry.header().size()?));
+        }
+        Ok(entries)
+    });
+
+    let mut stream = std::pin::pin!(stream);
+    while let Some(chunk) = stream.next().await {
+        if sender.send(chunk?).await.is_err() {
+            break;
+        }
+    }
+    drop(sender);
+
+    parser.await?
+}
+
+/// Blocking reader over the chunks of an archive sent from an async stream.
+struct ChannelReader {
+    receiver: tokio::sync::mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk.split_to(n));
+        Ok(n)
+    }
+}
+
+fn checksum_path(path: &str) -> String {
+    format!("{path}.sha256")
+}
+
+fn file_sha256(path: &str) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; CHUNK_SIZE];
+
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Writes the SHA-256 of the file next to it, in the `sha256sum` format.
+pub fn write_checksum_file(path: &str) -> Result<String> {
+    let checksum = file_sha256(path)?;
+    let file_name = Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    std::fs::write(checksum_path(path), format!("{checksum}  {file_name}\n"))?;
+    Ok(checksum)
+}
+
+/// Checks the file against its checksum file, returning `false` if there is none to check against.
+pub fn verify_checksum_file(path: &str) -> Result<bool> {
+    let Ok(content) = std::fs::read_to_string(checksum_path(path)) else {
+        return Ok(false);
+    };
+
+    let expected = content.split_whitespace().next().unwrap_or_default();
+    if file_sha256(path)? != expected {
+        return Err(eyre!("Checksum mismatch for {path}"));
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a tar archive with an empty file at each of `paths` and returns its path.
+    fn write_archive(paths: &[&str], extension: &str) -> String {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let path = std::env::temp_dir().join(format!("crabd-{}-{nanos}{extension}", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let mut writer = ArchiveWriter::create(&path, is_gzip_path(&path)).unwrap();
+        let mut builder = tar::Builder::new(vec![]);
+        for entry in paths {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(0);
+            header.set_mode(0o644);
+            builder.append_data(&mut header, entry, std::io::empty()).unwrap();
+        }
+        writer.write_all(&builder.into_inner().unwrap()).unwrap();
+        writer.finish().unwrap();
+        path
+    }
+
+    fn top_level(paths: &[&str], extension: &str) -> Option<String> {
+        let path = write_archive(paths, extension);
+        let top_level = archive_top_level(&path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        top_level
+    }
+
+    #[test]
+    fn archive_top_level_reads_first_entry() {
+        assert_eq!(top_level(&["volume/data/a.txt", "volume/b.txt"], ".tar"), Some("volume".to_string()));
+        assert_eq!(top_level(&["a.txt"], ".tar"), Some("a.txt".to_string()));
+    }
+
+    #[test]
+    fn archive_top_level_skips_current_dir() {
+        assert_eq!(top_level(&["./data/a.txt"], ".tar"), Some("data".to_string()));
+    }
+
+    #[test]
+    fn archive_top_level_reads_gzip() {
+        assert_eq!(top_level(&["volume/a.txt"], ".tar.gz"), Some("volume".to_string()));
+        assert_eq!(top_level(&["volume/a.txt"], ".tgz"), Some("volume".to_string()));
+    }
+
+    #[test]
+    fn archive_top_level_of_empty_archive() {
+        assert_eq!(top_level(&[], ".tar"), None);
+    }
+
+    #[tokio::test]
+    async fn pack_path_streams_the_directory_under_its_name() {
+        let dir = std::env::temp_dir().join(format!("crabd-pack-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/a.txt"), vec![7; 3 * CHUNK_SIZE]).unwrap();
+
+        let (archive, packer) = pack_path(&dir.to_string_lossy(), |_| {}).unwrap();
+        let mut archive = std::pin::pin!(archive);
+        let mut content = vec![];
+        while let Some(chunk) = archive.next().await {
+            content.extend_from_slice(&chunk.unwrap());
+        }
+        let size = packer.await.unwrap().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let name = dir.file_name().unwrap().to_string_lossy().to_string();
+        let mut entries = tar::Archive::new(content.as_slice());
+        let file = entries.entries().unwrap()
+            .map(|entry| entry.unwrap())
+            .find(|entry| entry.path().unwrap().to_string_lossy() == format!("{name}/sub/a.txt"))
+            .unwrap();
+        assert_eq!(file.header().size().unwrap(), 3 * CHUNK_SIZE as u64);
+        assert!(size > 3 * CHUNK_SIZE as u64);
+    }
+
+    #[test]
+    fn pack_path_refuses_paths_without_name() {
+        assert!(pack_path("/", |_| {}).is_err());
+    }
+}
@@ -1,14 +1,46 @@
-use bollard::Docker;
-use bollard::container::{
-    InspectContainerOptions, KillContainerOptions, ListContainersOptions, RemoveContainerOptions,
-    RestartContainerOptions, StopContainerOptions,
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use base64::prelude::{BASE64_STANDARD, Engine};
+use bollard::{Docker, body_try_stream};
+use bollard::container::LogOutput;
+use bollard::exec::{CreateExecOptions, StartExecResults};
+use bollard::models::{ContainerCreateBody, ContainerSummary};
+use bollard::query_parameters::{
+    CommitContainerOptions, CreateContainerOptions, CreateImageOptions, DownloadFromContainerOptions,
+    ImportImageOptions, InspectContainerOptions, InspectNetworkOptions, InspectServiceOptions,
+    KillContainerOptions, ListContainersOptions, ListImagesOptions, ListNetworksOptions, ListNodesOptions,
+    ListSecretsOptions, ListServicesOptions, ListTasksOptions, ListVolumesOptions, PruneBuildOptions,
+    PruneContainersOptions, PruneImagesOptions, PruneNetworksOptions, PruneVolumesOptions,
+    RemoveContainerOptions, RemoveImageOptions, RemoveVolumeOptions, RenameContainerOptions,
+    RestartContainerOptions, StartContainerOptions, StopContainerOptions, UpdateNodeOptions,
+    UpdateServiceOptions, UploadToContainerOptions,
+};
+use bollard::secret::{
+    BuildPruneResponse, Config, ConfigSpec, ContainerConfig, ContainerInspectResponse, ContainerPruneResponse,
+    ContainerUpdateBody, EndpointIpamConfig, EndpointSettings, HostConfig, ImagePruneResponse, ImageSummary,
+    Network, NetworkConnectRequest, NetworkCreateRequest, NetworkDisconnectRequest, NetworkPruneResponse, Node,
+    NodeSpecAvailabilityEnum, Secret, SecretSpec, Service, ServiceSpec, ServiceSpecModeReplicated,
+    SystemDataUsageResponse, SystemInfo, SystemVersion, Task, Volume, VolumeCreateOptions, VolumeListResponse,
+    VolumePruneResponse,
 };
-use bollard::image::{ListImagesOptions, RemoveImageOptions};
-use bollard::models::ContainerSummary;
-use bollard::network::ListNetworksOptions;
-use bollard::secret::{ContainerInspectResponse, ImageSummary, Network, VolumeListResponse};
-use bollard::volume::{ListVolumesOptions, RemoveVolumeOptions};
-use color_eyre::eyre::Result;
+use color_eyre::eyre::{Result, eyre};
+use futures::{StreamExt, TryStreamExt};
+
+use super::archive::{
+    archive_top_level, pack_path, read_archive_entries, read_file_as_stream, read_first_file, unpack_stream,
+    verify_checksum_file, write_checksum_file, write_stream_to_file,
+};
+use super::compose::{
+    CONTAINER_NUMBER_LABEL, ComposeProject, ComposeService, NETWORK_LABEL, ONEOFF_LABEL, PROJECT_LABEL, SERVICE_LABEL, VOLUME_LABEL,
+};
+use super::filesystem::{
+    COPY_SOURCE_PATH, COPY_TARGET_PATH, FileChange, FileEntry, HELPER_LABEL, RESTORE_STAGING_DIR, VOLUME_MOUNT_PATH, helper_image,
+    list_directory_command, parse_archive_tree, parse_directory_listing,
+};
+use super::recreate::{recreate_config, replace_volume};
+use super::run_config::RunConfig;
+use super::swarm_config;
+use crate::utils::is_container_running;
 
 #[derive(Clone)]
 pub struct DockerClient {
@@ -16,16 +48,32 @@ pub struct DockerClient {
 }
 
 impl DockerClient {
-    pub fn new() -> Result<Self> {
-        Ok(Self {
-            client: Docker::connect_with_local_defaults()?,
-        })
+    /// Connects to the local daemon and negotiates the API version, so engines older than the
+    /// client are talked to in a version they support. If the daemon can't be reached yet, the
+    /// client's default version is kept.
+    pub async fn new() -> Result<Self> {
+        let client = Docker::connect_with_local_defaults()?;
+        let client = client.clone().negotiate_version().await.unwrap_or(client);
+        Ok(Self { client })
+    }
+
+    /// The API version requests are made with, after negotiation.
+    pub fn api_version(&self) -> String {
+        self.client.client_version().to_string()
+    }
+
+    pub async fn info(&self) -> Result<SystemInfo> {
+        Ok(self.client.info().await?)
+    }
+
+    pub async fn version(&self) -> Result<SystemVersion> {
+        Ok(self.client.version().await?)
     }
 
     pub async fn list_containers(&self) -> Result<Vec<ContainerSummary>> {
         Ok(self
             .client
-            .list_containers(Some(ListContainersOptions::<String> {
+            .list_containers(Some(ListContainersOptions {
                 all: true,
                 ..Default::default()
             }))
@@ -39,6 +87,13 @@ impl DockerClient {
         Ok(())
     }
 
+    pub async fn start_container(&self, container_id: &str) -> Result<()> {
+        self.client
+            .start_container(container_id, None::<StartContainerOptions>)
+            .await?;
+        Ok(())
+    }
+
     pub async fn restart_container(&self, container_id: &str) -> Result<()> {
         self.client
             .restart_container(container_id, None::<RestartContainerOptions>)
@@ -48,11 +103,22 @@ impl DockerClient {
 
     pub async fn kill_container(&self, container_id: &str) -> Result<()> {
         self.client
-            .kill_container(container_id, None::<KillContainerOptions<String>>)
+            .kill_container(container_id, None::<KillContainerOptions>)
             .await?;
         Ok(())
     }
 
+    pub async fn rename_container(&self, container_id: &str, name: &str) -> Result<()> {
+        self.client.rename_container(container_id, RenameContainerOptions { name: name.to_string() }).await?;
+        Ok(())
+    }
+
+    /// Changes the restart policy and resource limits of a container without recreating it.
+    pub async fn update_container(&self, container_id: &str, update: ContainerUpdateBody) -> Result<()> {
+        self.client.update_container(container_id, update).await?;
+        Ok(())
+    }
+
     pub async fn remove_container(&self, container_id: &str) -> Result<()> {
         self.client
             .remove_container(
@@ -66,6 +132,11 @@ impl DockerClient {
         Ok(())
     }
 
+    /// Creates an image from the container's changes and returns the image ID.
+    pub async fn commit_container(&self, options: CommitContainerOptions) -> Result<String> {
+        Ok(self.client.commit_container(options, ContainerConfig::default()).await?.id)
+    }
+
     pub async fn inspect_container(&self, container_id: &str) -> Result<ContainerInspectResponse> {
         Ok(self
             .client
@@ -73,8 +144,30 @@ impl DockerClient {
             .await?)
     }
 
+    /// Creates a container from `config`, starts it and returns its ID. A container that fails
+    /// to start is removed, so that running it again doesn't hit a name conflict.
+    pub async fn run_container(&self, config: RunConfig) -> Result<String> {
+        let name = Some(config.name.clone()).filter(|name| !name.is_empty());
+        let options = Some(CreateContainerOptions { name, ..Default::default() });
+        let response = self.client.create_container(options, config.into_container_config()).await?;
+
+        if let Err(e) = self.client.start_container(&response.id, None::<StartContainerOptions>).await {
+            let _ = self.remove_container(&response.id).await;
+            return Err(e.into());
+        }
+        Ok(response.id)
+    }
+
     pub async fn list_volumes(&self) -> Result<VolumeListResponse> {
-        Ok(self.client.list_volumes(Some(ListVolumesOptions::<String>::default())).await?)
+        Ok(self.client.list_volumes(None::<ListVolumesOptions>).await?)
+    }
+
+    pub async fn create_volume(&self, options: VolumeCreateOptions) -> Result<Volume> {
+        Ok(self.client.create_volume(options).await?)
+    }
+
+    pub async fn inspect_volume(&self, name: &str) -> Result<Volume> {
+        Ok(self.client.inspect_volume(name).await?)
     }
 
     pub async fn remove_volume(&self, name: &str, force: bool) -> Result<()> {
@@ -82,7 +175,39 @@ impl DockerClient {
     }
 
     pub async fn list_networks(&self) -> Result<Vec<Network>> {
-        Ok(self.client.list_networks(Some(ListNetworksOptions::<String>::default())).await?)
+        Ok(self.client.list_networks(None::<ListNetworksOptions>).await?)
+    }
+
+    pub async fn create_network(&self, options: NetworkCreateRequest) -> Result<String> {
+        Ok(self.client.create_network(options).await?.id)
+    }
+
+    pub async fn inspect_network(&self, id: &str) -> Result<Network> {
+        let options = InspectNetworkOptions { verbose: false, scope: None };
+        Ok(self.client.inspect_network(id, Some(options)).await?)
+    }
+
+    /// Attaches a running or stopped container, optionally with an alias and a static IPv4 or IPv6 address.
+    pub async fn connect_network(&self, network: &str, container: &str, alias: &str, ip: &str) -> Result<()> {
+        let ipam_config = (!ip.is_empty()).then(|| match ip.contains(':') {
+            true => EndpointIpamConfig { ipv6_address: Some(ip.to_string()), ..Default::default() },
+            false => EndpointIpamConfig { ipv4_address: Some(ip.to_string()), ..Default::default() },
+        });
+
+        let options = NetworkConnectRequest {
+            container: Some(container.to_string()),
+            endpoint_config: Some(EndpointSettings {
+                aliases: Some(vec![alias.to_string()]).filter(|_| !alias.is_empty()),
+                ipam_config,
+                ..Default::default()
+            }),
+        };
+        Ok(self.client.connect_network(network, options).await?)
+    }
+
+    pub async fn disconnect_network(&self, network: &str, container: &str, force: bool) -> Result<()> {
+        let options = NetworkDisconnectRequest { container: Some(container.to_string()), force: Some(force) };
+        Ok(self.client.disconnect_network(network, options).await?)
     }
 
     pub async fn remove_network(&self, name: &str) -> Result<()> {
@@ -90,7 +215,7 @@ impl DockerClient {
     }
 
     pub async fn list_images(&self) -> Result<Vec<ImageSummary>> {
-        let options = Some(ListImagesOptions::<String> { all: true, ..Default::default() });
+        let options = Some(ListImagesOptions { all: true, ..Default::default() });
         Ok(self.client.list_images(options).await?)
     }
 
@@ -99,4 +224,670 @@ impl DockerClient {
         self.client.remove_image(id, options, None).await?;
         Ok(())
     }
+
+    pub async fn prune_images(&self, dangling_only: bool) -> Result<ImagePruneResponse> {
+        let dangling = if dangling_only { "true" } else { "false" };
+        let filters = HashMap::from([("dangling".to_string(), vec![dangling.to_string()])]);
+        let options = Some(PruneImagesOptions { filters: Some(filters) });
+        Ok(self.client.prune_images(options).await?)
+    }
+
+    /// Saves the images to a tar archive at `path` and returns the archive size in bytes.
+    pub async fn export_images(&self, names: &[String], path: &str, progress: impl Fn(u64)) -> Result<u64> {
+        let names = names.iter().map(String::as_str).collect::<Vec<&str>>();
+        write_stream_to_file(self.client.export_images(&names), path, progress).await?;
+        Ok(std::fs::metadata(path)?.len())
+    }
+
+    /// Exports the flattened filesystem of the container to a tar archive at `path` and returns the
+    /// archive size in bytes.
+    pub async fn export_container(&self, id: &str, path: &str, progress: impl Fn(u64)) -> Result<u64> {
+        write_stream_to_file(self.client.export_container(id), path, progress).await?;
+        Ok(std::fs::metadata(path)?.len())
+    }
+
+    /// Loads the images from the tar archive at `path` and returns the loaded image names.
+    pub async fn import_images(&self, path: &str, progress: impl Fn(u64) + Send + 'static) -> Result<Vec<String>> {
+        let archive = read_file_as_stream(path, progress).await?;
+        let options = ImportImageOptions { quiet: true, ..Default::default() };
+        let mut stream = self.client.import_image(options, body_try_stream(archive), None);
+        let mut loaded = vec![];
+
+        while let Some(info) = stream.next().await {
+            let info = info?;
+            if let Some(err) = info.error {
+                return Err(eyre!(err));
+            }
+
+            let images = info.stream.iter()
+                .flat_map(|s| s.lines())
+                .filter_map(|line| line.split_once(": ").map(|(_, image)| image.trim().to_string()));
+            loaded.extend(images);
+        }
+
+        Ok(loaded)
+    }
+
+    pub async fn system_df(&self) -> Result<SystemDataUsageResponse> {
+        Ok(self.client.df(None).await?)
+    }
+
+    pub async fn prune_containers(&self) -> Result<ContainerPruneResponse> {
+        Ok(self.client.prune_containers(None::<PruneContainersOptions>).await?)
+    }
+
+    /// Removes the unused volumes, named ones included, as the daemon only prunes anonymous
+    /// volumes by default.
+    pub async fn prune_volumes(&self) -> Result<VolumePruneResponse> {
+        let filters = HashMap::from([("all".to_string(), vec!["true".to_string()])]);
+        let options = Some(PruneVolumesOptions { filters: Some(filters) });
+        Ok(self.client.prune_volumes(options).await?)
+    }
+
+    pub async fn prune_networks(&self) -> Result<NetworkPruneResponse> {
+        Ok(self.client.prune_networks(None::<PruneNetworksOptions>).await?)
+    }
+
+    /// Removes all the build cache that is not in use, not only the dangling entries.
+    pub async fn prune_build_cache(&self) -> Result<BuildPruneResponse> {
+        let options = Some(PruneBuildOptions { all: Some(true), ..Default::default() });
+        Ok(self.client.prune_build(options).await?)
+    }
+
+    pub async fn list_services(&self) -> Result<Vec<Service>> {
+        let options = ListServicesOptions { status: Some(true), ..Default::default() };
+        Ok(self.client.list_services(Some(options)).await?)
+    }
+
+    /// Lists the tasks of the swarm, only those of `service_id` if given.
+    pub async fn list_tasks(&self, service_id: Option<&str>) -> Result<Vec<Task>> {
+        let filters = service_id.map(|id| HashMap::from([("service".to_string(), vec![id.to_string()])]));
+        Ok(self.client.list_tasks(Some(ListTasksOptions { filters })).await?)
+    }
+
+    pub async fn list_nodes(&self) -> Result<Vec<Node>> {
+        Ok(self.client.list_nodes(None::<ListNodesOptions>).await?)
+    }
+
+    /// Sets the replica count of a replicated service.
+    pub async fn scale_service(&self, id: &str, replicas: i64) -> Result<()> {
+        self.update_service_spec(id, None, |spec| {
+            let mode = spec.mode.get_or_insert_default();
+            if mode.replicated.is_none() {
+                return Err(eyre!("Only replicated services can be scaled"));
+            }
+            mode.replicated = Some(ServiceSpecModeReplicated { replicas: Some(replicas) });
+            Ok(())
+        })
+        .await
+    }
+
+    /// Rolls the tasks of the service over to `image`.
+    pub async fn update_service_image(&self, id: &str, image: &str) -> Result<()> {
+        self.update_service_spec(id, None, |spec| {
+            let task_template = spec.task_template.get_or_insert_default();
+            task_template.container_spec.get_or_insert_default().image = Some(image.to_string());
+            Ok(())
+        })
+        .await
+    }
+
+    /// Reverts the service to the spec it had before its last update.
+    pub async fn rollback_service(&self, id: &str) -> Result<()> {
+        self.update_service_spec(id, Some("previous".to_string()), |_| Ok(())).await
+    }
+
+    /// Applies `change` to the current spec of the service. The version it was read at is sent
+    /// along, so the daemon rejects the update if someone else changed the service meanwhile.
+    async fn update_service_spec(
+        &self,
+        id: &str,
+        rollback: Option<String>,
+        change: impl FnOnce(&mut ServiceSpec) -> Result<()>,
+    ) -> Result<()> {
+        let service = self.client.inspect_service(id, None::<InspectServiceOptions>).await?;
+        let version = service.version.and_then(|v| v.index).ok_or_else(|| eyre!("Service {id} has no version"))?;
+        let mut spec = service.spec.unwrap_or_default();
+        change(&mut spec)?;
+
+        let options = UpdateServiceOptions { version: i32::try_from(version)?, rollback, ..Default::default() };
+        self.client.update_service(id, spec, options, None).await?;
+        Ok(())
+    }
+
+    /// Sets a node to active, pause or drain. Draining moves its tasks to the other nodes.
+    pub async fn set_node_availability(&self, id: &str, availability: NodeSpecAvailabilityEnum) -> Result<()> {
+        let node = self.client.inspect_node(id).await?;
+        let version = node.version.and_then(|v| v.index).ok_or_else(|| eyre!("Node {id} has no version"))?;
+        let mut spec = node.spec.unwrap_or_default();
+        spec.availability = Some(availability);
+
+        self.client.update_node(id, spec, UpdateNodeOptions { version: i64::try_from(version)? }).await?;
+        Ok(())
+    }
+
+    pub async fn list_secrets(&self) -> Result<Vec<Secret>> {
+        Ok(self.client.list_secrets(None::<ListSecretsOptions>).await?)
+    }
+
+    /// Creates a secret holding `data`, which the daemon never hands back once stored.
+    pub async fn create_secret(&self, name: &str, data: &[u8], labels: HashMap<String, String>) -> Result<String> {
+        let spec = SecretSpec {
+            name: Some(name.to_string()),
+            labels: Some(labels),
+            data: Some(BASE64_STANDARD.encode(data)),
+            ..Default::default()
+        };
+        Ok(self.client.create_secret(spec).await?.id)
+    }
+
+    pub async fn remove_secret(&self, id: &str) -> Result<()> {
+        Ok(self.client.delete_secret(id).await?)
+    }
+
+    /// Configs are requested over the unix socket directly, so other connections can't reach them.
+    pub fn supports_configs(&self) -> bool {
+        swarm_config::is_supported()
+    }
+
+    pub async fn list_configs(&self) -> Result<Vec<Config>> {
+        swarm_config::list_configs(&self.api_version()).await
+    }
+
+    pub async fn create_config(&self, name: &str, data: &[u8], labels: HashMap<String, String>) -> Result<String> {
+        let spec = ConfigSpec {
+            name: Some(name.to_string()),
+            labels: Some(labels),
+            data: Some(BASE64_STANDARD.encode(data)),
+            ..Default::default()
+        };
+        swarm_config::create_config(&self.api_version(), &spec).await
+    }
+
+    pub async fn remove_config(&self, id: &str) -> Result<()> {
+        swarm_config::remove_config(&self.api_version(), id).await
+    }
+
+    /// Pulls `image` unless it is already present.
+    pub async fn ensure_image(&self, image: &str) -> Result<()> {
+        if self.client.inspect_image(image).await.is_ok() {
+            return Ok(());
+        }
+        self.pull_image(image).await
+    }
+
+    /// Pulls `image`, the `latest` tag if it has none.
+    pub async fn pull_image(&self, image: &str) -> Result<()> {
+        // An empty tag would pull every tag of the repository.
+        let (from_image, tag) = match image.rsplit_once(':') {
+            Some((repo, tag)) if !tag.contains('/') => (repo, tag),
+            _ => (image, "latest"),
+        };
+
+        let options = Some(CreateImageOptions {
+            from_image: Some(from_image.to_string()),
+            tag: Some(tag.to_string()),
+            ..Default::default()
+        });
+        self.client.create_image(options, None, None).try_collect::<Vec<_>>().await?;
+        Ok(())
+    }
+
+    /// Starts a short-lived container from the helper image with the given binds mounted.
+    pub async fn start_helper(&self, binds: Vec<String>) -> Result<String> {
+        let image = helper_image();
+        self.ensure_image(&image).await?;
+
+        let config = ContainerCreateBody {
+            image: Some(image),
+            cmd: Some(vec!["sleep".to_string(), "86400".to_string()]),
+            labels: Some(HashMap::from([(HELPER_LABEL.to_string(), "true".to_string())])),
+            host_config: Some(HostConfig {
+                binds: Some(binds),
+                auto_remove: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let response = self.client.create_container(None::<CreateContainerOptions>, config).await?;
+        self.client
+            .start_container(&response.id, None::<StartContainerOptions>)
+            .await?;
+        Ok(response.id)
+    }
+
+    /// Runs `cmd` in the container and returns its standard output, failing on a non-zero exit code.
+    pub async fn exec_output(&self, container_id: &str, cmd: Vec<String>) -> Result<String> {
+        let options = CreateExecOptions {
+            cmd: Some(cmd),
+            attach_stdout: Some(true),
+            attach_stderr: Some(true),
+            ..Default::default()
+        };
+        let exec = self.client.create_exec(container_id, options).await?;
+
+        let mut stdout = vec![];
+        let mut stderr = vec![];
+        if let StartExecResults::Attached { mut output, .. } = self.client.start_exec(&exec.id, None).await? {
+            while let Some(log) = output.next().await {
+                match log? {
+                    LogOutput::StdErr { message } => stderr.extend_from_slice(&message),
+                    log => stdout.extend_from_slice(&log.into_bytes()),
+                }
+            }
+        }
+
+        let exit_code = self.client.inspect_exec(&exec.id).await?.exit_code.unwrap_or(0);
+        if exit_code != 0 {
+            let err = String::from_utf8_lossy(&stderr).trim().to_string();
+            return Err(eyre!("Command exited with code {exit_code}: {err}"));
+        }
+
+        Ok(String::from_utf8_lossy(&stdout).to_string())
+    }
+
+    pub async fn list_directory(&self, container_id: &str, path: &str) -> Result<Vec<FileEntry>> {
+        let output = self.exec_output(container_id, list_directory_command(path)).await?;
+        Ok(parse_directory_listing(&output))
+    }
+
+    /// Lists the whole tree at `path` of a container through the archive API, so stopped containers
+    /// and images without a shell can be browsed too. Only the entry headers are kept, so the tree
+    /// can be browsed without reading the archive again.
+    pub async fn list_archive_tree(&self, container_id: &str, path: &str) -> Result<BTreeMap<String, Vec<FileEntry>>> {
+        let options = Some(DownloadFromContainerOptions { path: path.to_string() });
+        let entries = read_archive_entries(self.client.download_from_container(container_id, options)).await?;
+        Ok(parse_archive_tree(path, &entries))
+    }
+
+    /// The paths added, changed or deleted in the container compared to its image.
+    pub async fn container_changes(&self, container_id: &str) -> Result<Vec<(String, FileChange)>> {
+        let changes = self.client.container_changes(container_id).await?.unwrap_or_default();
+        Ok(changes.into_iter().map(|c| (c.path, FileChange::from(c.kind))).collect())
+    }
+
+    /// Returns the content of the file at `path` in the container.
+    pub async fn read_file(&self, container_id: &str, path: &str) -> Result<Vec<u8>> {
+        let options = Some(DownloadFromContainerOptions { path: path.to_string() });
+        let archive = self.client.download_from_container(container_id, options)
+            .try_fold(vec![], |mut acc, chunk| async move {
+                acc.extend_from_slice(&chunk);
+                Ok(acc)
+            })
+            .await?;
+        read_first_file(&archive)
+    }
+
+    /// Copies the file or directory at `path` in the container into the host directory `dest_dir`.
+    pub async fn download_path(&self, container_id: &str, path: &str, dest_dir: &str, progress: impl Fn(u64)) -> Result<u64> {
+        let options = Some(DownloadFromContainerOptions { path: path.to_string() });
+        unpack_stream(self.client.download_from_container(container_id, options), dest_dir, progress).await
+    }
+
+    /// Copies the file or directory at `host_path` into the directory `dest_dir` of the container
+    /// and returns the size of the archive sent. The archive is streamed while it is packed.
+    pub async fn upload_path(
+        &self,
+        container_id: &str,
+        host_path: &str,
+        dest_dir: &str,
+        progress: impl Fn(u64) + Send + 'static,
+    ) -> Result<u64> {
+        let (archive, packer) = pack_path(host_path, progress)?;
+        let options = Some(UploadToContainerOptions { path: dest_dir.to_string(), ..Default::default() });
+        let uploaded = self.client.upload_to_container(container_id, options, body_try_stream(archive)).await;
+
+        // A packing error is what made the upload fail, so it is the one worth reporting.
+        let size = packer.await??;
+        uploaded?;
+        Ok(size)
+    }
+
+    /// Archives the volume content to `path` through a helper container and writes its checksum
+    /// file next to it. Returns the archive size in bytes and its SHA-256.
+    pub async fn backup_volume(&self, name: &str, path: &str, progress: impl Fn(u64)) -> Result<(u64, String)> {
+        let helper_id = self.start_helper(vec![format!("{name}:{VOLUME_MOUNT_PATH}:ro")]).await?;
+
+        let result = async {
+            let options = Some(DownloadFromContainerOptions { path: VOLUME_MOUNT_PATH.to_string() });
+            let size = write_stream_to_file(self.client.download_from_container(&helper_id, options), path, progress).await?;
+            Ok((size, write_checksum_file(path)?))
+        }.await;
+
+        let _ = self.remove_container(&helper_id).await;
+        result
+    }
+
+    /// Extracts a backup made by [`Self::backup_volume`], or the content of any other tar archive,
+    /// into the volume, creating it if needed.
+    /// With `replace`, the archive is extracted into a staging directory of the volume and only
+    /// takes the place of the current content once it is complete, so a failed restore leaves the
+    /// volume as it was. Returns whether the archive was verified against a checksum file.
+    pub async fn restore_volume(
+        &self,
+        name: &str,
+        path: &str,
+        replace: bool,
+        progress: impl Fn(u64) + Send + 'static,
+    ) -> Result<bool> {
+        let is_verified = verify_checksum_file(path)?;
+
+        if self.inspect_volume(name).await.is_err() {
+            let options = VolumeCreateOptions {
+                name: Some(name.to_string()),
+                driver: Some("local".to_string()),
+                ..Default::default()
+            };
+            self.create_volume(options).await?;
+        }
+
+        let helper_id = self.start_helper(vec![format!("{name}:{VOLUME_MOUNT_PATH}")]).await?;
+
+        let result = async {
+            // Backups contain the mount directory itself, so their content is one level down. Any
+            // other archive, such as one made with `tar -C dir .`, holds the content at its root.
+            let mount_dir = VOLUME_MOUNT_PATH.trim_start_matches('/');
+            let is_backup = archive_top_level(path)?.is_some_and(|top_level| top_level == mount_dir);
+            let staging_dir = format!("{VOLUME_MOUNT_PATH}/{RESTORE_STAGING_DIR}");
+            let extract_dir = match (replace, is_backup) {
+                (false, false) => VOLUME_MOUNT_PATH.to_string(),
+                (false, true) => "/".to_string(),
+                (true, _) => {
+                    self.exec_output(&helper_id, ["rm", "-rf", &staging_dir].map(String::from).to_vec()).await?;
+                    self.exec_output(&helper_id, ["mkdir", &staging_dir].map(String::from).to_vec()).await?;
+                    staging_dir.clone()
+                }
+            };
+
+            let options = Some(UploadToContainerOptions { path: extract_dir, ..Default::default() });
+            let archive = read_file_as_stream(path, progress).await?;
+            if let Err(e) = self.client.upload_to_container(&helper_id, options, body_try_stream(archive)).await {
+                if replace {
+                    let _ = self.exec_output(&helper_id, ["rm", "-rf", &staging_dir].map(String::from).to_vec()).await;
+                }
+                return Err(e.into());
+            }
+
+            if replace {
+                let content_dir = if is_backup { format!("{staging_dir}/{mount_dir}") } else { staging_dir.clone() };
+                let cmds = [
+                    vec!["find", VOLUME_MOUNT_PATH, "-mindepth", "1", "-maxdepth", "1", "!", "-name", RESTORE_STAGING_DIR, "-exec", "rm", "-rf", "{}", ";"],
+                    vec!["find", &content_dir, "-mindepth", "1", "-maxdepth", "1", "-exec", "mv", "{}", VOLUME_MOUNT_PATH, ";"],
+                    vec!["rm", "-rf", &staging_dir],
+                ];
+                for cmd in cmds {
+                    self.exec_output(&helper_id, cmd.into_iter().map(String::from).collect()).await?;
+                }
+            }
+            Ok(is_verified)
+        }.await;
+
+        let _ = self.remove_container(&helper_id).await;
+        result
+    }
+
+    /// Creates `target` with the driver, options and labels of `source` and copies the data over
+    /// through a helper container. The new volume is removed again if the copy fails. Volumes
+    /// with a `device` option are refused.
+    pub async fn clone_volume(&self, source: &str, target: &str) -> Result<()> {
+        if self.inspect_volume(target).await.is_ok() {
+            return Err(eyre!("Volume {target} already exists"));
+        }
+
+        let volume = self.inspect_volume(source).await?;
+        // A `device` option points the volume at existing storage, such as a bind or NFS mount,
+        // so a clone with the same options would share the data instead of copying it.
+        if volume.options.contains_key("device") {
+            return Err(eyre!("Volume {source} is backed by a device, a clone would share its storage"));
+        }
+
+        let options = VolumeCreateOptions {
+            name: Some(target.to_string()),
+            driver: Some(volume.driver),
+            driver_opts: Some(volume.options),
+            labels: Some(volume.labels),
+            ..Default::default()
+        };
+        self.create_volume(options).await?;
+
+        let binds = vec![format!("{source}:{COPY_SOURCE_PATH}:ro"), format!("{target}:{COPY_TARGET_PATH}")];
+        let result = match self.start_helper(binds).await {
+            Ok(helper_id) => {
+                let source_dir = format!("{COPY_SOURCE_PATH}/.");
+                let cmd = ["cp", "-a", &source_dir, COPY_TARGET_PATH].map(String::from).to_vec();
+                let result = self.exec_output(&helper_id, cmd).await.map(|_| ());
+                let _ = self.remove_container(&helper_id).await;
+                result
+            }
+            Err(e) => Err(e),
+        };
+
+        if result.is_err() {
+            let _ = self.remove_volume(target, true).await;
+        }
+        result
+    }
+
+    /// Recreates the stopped containers that use the `source` volume so they use `target` instead.
+    /// Returns the names of the recreated containers and of the running ones that were left as is.
+    pub async fn rewire_volume(&self, source: &str, target: &str) -> Result<(Vec<String>, Vec<String>)> {
+        let options = ListContainersOptions {
+            all: true,
+            filters: Some(HashMap::from([("volume".to_string(), vec![source.to_string()])])),
+            ..Default::default()
+        };
+
+        let mut rewired = vec![];
+        let mut skipped = vec![];
+        for container in self.client.list_containers(Some(options)).await? {
+            let id = container.id.unwrap_or_default();
+            let name = container.names.unwrap_or_default().first()
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_else(|| id.clone());
+
+            if is_container_running(&container.state.map(|state| state.to_string()).unwrap_or_default()) {
+                skipped.push(name);
+                continue;
+            }
+
+            let mut config = recreate_config(self.inspect_container(&id).await?);
+            replace_volume(&mut config, source, target);
+            self.recreate_container(&id, &name, config).await?;
+            rewired.push(name);
+        }
+
+        Ok((rewired, skipped))
+    }
+
+    /// Replaces a stopped container with one created from `config` under the same name.
+    pub async fn recreate_container(&self, id: &str, name: &str, config: ContainerCreateBody) -> Result<String> {
+        let options = Some(CreateContainerOptions { name: Some(name.to_string()), ..Default::default() });
+        self.replace_container(id, name, async { Ok(self.client.create_container(options, config).await?.id) }).await
+    }
+
+    /// Replaces the container `id` named `name` with the one `create` makes and returns its ID.
+    /// The old container is kept under a temporary name until the new one exists, and gets its
+    /// name back if `create` fails. It is stopped gracefully before being removed.
+    async fn replace_container(&self, id: &str, name: &str, create: impl Future<Output = Result<String>>) -> Result<String> {
+        let old_name = format!("{name}-crabd-old");
+        self.client.rename_container(id, RenameContainerOptions { name: old_name }).await?;
+
+        match create.await {
+            Ok(new_id) => {
+                self.stop_container(id).await?;
+                self.remove_container(id).await?;
+                Ok(new_id)
+            }
+            Err(e) => {
+                let _ = self.client.rename_container(id, RenameContainerOptions { name: name.to_string() }).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Lists the containers of a compose project, leaving out the one-off `compose run` ones.
+    pub async fn list_project_containers(&self, project: &str) -> Result<Vec<ContainerSummary>> {
+        let filters = HashMap::from([("label".to_string(), vec![format!("{PROJECT_LABEL}={project}")])]);
+        let options = ListContainersOptions { all: true, filters: Some(filters), ..Default::default() };
+        let containers = self.client.list_containers(Some(options)).await?;
+
+        Ok(containers.into_iter()
+            .filter(|c| c.labels.as_ref().and_then(|l| l.get(ONEOFF_LABEL)).is_none_or(|oneoff| oneoff != "True"))
+            .collect())
+    }
+
+    /// Brings the project in line with its files like `docker compose up -d`: missing networks,
+    /// volumes and containers are created, containers that drifted from the file are recreated
+    /// and stopped ones are started. Services that have to be built are expected to be built.
+    pub async fn compose_up(&self, project: &ComposeProject) -> Result<String> {
+        for (key, name, driver) in project.networks_to_create() {
+            if self.inspect_network(&name).await.is_err() {
+                let labels = HashMap::from([(PROJECT_LABEL.to_string(), project.name.clone()), (NETWORK_LABEL.to_string(), key)]);
+                self.create_network(NetworkCreateRequest { name, driver, labels: Some(labels), ..Default::default() }).await?;
+            }
+        }
+
+        for (key, name, driver) in project.volumes_to_create() {
+            if self.inspect_volume(&name).await.is_err() {
+                let labels = HashMap::from([(PROJECT_LABEL.to_string(), project.name.clone()), (VOLUME_LABEL.to_string(), key)]);
+                let options = VolumeCreateOptions { name: Some(name), driver, labels: Some(labels), ..Default::default() };
+                self.create_volume(options).await?;
+            }
+        }
+
+        let containers = self.list_project_containers(&project.name).await?;
+        let mut summary = vec![];
+        for service in project.ordered_services() {
+            let service_containers = containers.iter()
+                .filter(|c| c.labels.as_ref().and_then(|l| l.get(SERVICE_LABEL)) == Some(&service.name))
+                .collect::<Vec<&ContainerSummary>>();
+
+            if service_containers.is_empty() {
+                self.create_service(project, service).await?;
+                summary.push(format!("{} created", service.name));
+                continue;
+            }
+
+            let mut has_drifted = false;
+            for container in &service_containers {
+                let inspect = self.inspect_container(container.id.as_deref().unwrap_or_default()).await?;
+                has_drifted |= !service.drift(project, &inspect).is_empty();
+            }
+
+            if has_drifted {
+                self.compose_recreate(project, &service.name).await?;
+                summary.push(format!("{} recreated", service.name));
+                continue;
+            }
+
+            let stopped = service_containers.iter()
+                .filter(|c| !is_container_running(&c.state.map(|state| state.to_string()).unwrap_or_default()))
+                .filter_map(|c| c.id.as_deref())
+                .collect::<Vec<&str>>();
+            for id in &stopped {
+                self.start_container(id).await?;
+            }
+            summary.push(format!("{} {}", service.name, if stopped.is_empty() { "running" } else { "started" }));
+        }
+
+        Ok(summary.join(", "))
+    }
+
+    /// Stops and removes the containers and networks of the project like `docker compose down`,
+    /// volumes are kept. Works without the files, as everything is found by its labels.
+    pub async fn compose_down(&self, project: &str) -> Result<String> {
+        let containers = self.list_project_containers(project).await?;
+        for id in containers.iter().filter_map(|c| c.id.as_deref()) {
+            self.stop_container(id).await?;
+            self.remove_container(id).await?;
+        }
+
+        let filters = HashMap::from([("label".to_string(), vec![format!("{PROJECT_LABEL}={project}")])]);
+        let networks = self.client.list_networks(Some(ListNetworksOptions { filters: Some(filters) })).await?;
+        for name in networks.iter().filter_map(|n| n.name.as_deref()) {
+            self.remove_network(name).await?;
+        }
+
+        Ok(format!("Removed {} container(s) and {} network(s) of {project}", containers.len(), networks.len()))
+    }
+
+    /// Pulls the images of the services, skipping the ones that are built.
+    pub async fn compose_pull(&self, project: &ComposeProject) -> Result<String> {
+        let images = project.services.values()
+            .filter_map(|service| service.image.clone())
+            .collect::<HashSet<String>>();
+
+        for image in &images {
+            self.pull_image(image).await?;
+        }
+        Ok(format!("Pulled {} image(s) of {}", images.len(), project.name))
+    }
+
+    /// Replaces the containers of `service` with new ones created from the file, keeping their
+    /// numbers so a scaled service stays scaled. Each old container is only stopped and removed
+    /// once its replacement is created and connected to its networks, and before it starts.
+    pub async fn compose_recreate(&self, project: &ComposeProject, service: &str) -> Result<String> {
+        let compose_service = project.services.get(service).ok_or_else(|| eyre!("No service {service} in the file"))?;
+        let containers = self.list_project_containers(&project.name).await?.into_iter()
+            .filter(|c| c.labels.as_ref().and_then(|l| l.get(SERVICE_LABEL)).is_some_and(|s| s == service))
+            .collect::<Vec<ContainerSummary>>();
+
+        if containers.is_empty() {
+            self.create_service(project, compose_service).await?;
+            return Ok(format!("Created {service}"));
+        }
+
+        self.ensure_service_image(project, compose_service).await?;
+        for container in &containers {
+            let id = container.id.as_deref().unwrap_or_default();
+            let name = container.names.as_ref().and_then(|names| names.first())
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_else(|| id.to_string());
+            let number = container.labels.as_ref()
+                .and_then(|l| l.get(CONTAINER_NUMBER_LABEL))
+                .and_then(|number| number.parse().ok())
+                .unwrap_or(1);
+
+            let create = self.create_service_container(project, compose_service, number);
+            let new_id = self.replace_container(id, &name, create).await?;
+            self.start_container(&new_id).await?;
+        }
+        Ok(format!("Recreated {service} ({} container(s))", containers.len()))
+    }
+
+    async fn create_service(&self, project: &ComposeProject, service: &ComposeService) -> Result<()> {
+        self.ensure_service_image(project, service).await?;
+        for number in 1..=service.replicas {
+            let id = self.create_service_container(project, service, number).await?;
+            self.start_container(&id).await?;
+        }
+        Ok(())
+    }
+
+    async fn ensure_service_image(&self, project: &ComposeProject, service: &ComposeService) -> Result<()> {
+        let image = service.image_name(&project.name);
+        if service.is_built() && self.client.inspect_image(&image).await.is_err() {
+            return Err(eyre!("{} has to be built first with docker compose build", service.name));
+        }
+        self.ensure_image(&image).await
+    }
+
+    /// Creates container `number` of the service connected to all its networks, without starting
+    /// it. The container is removed again if a network can't be connected.
+    async fn create_service_container(&self, project: &ComposeProject, service: &ComposeService, number: u32) -> Result<String> {
+        let name = service.container_name(&project.name, number);
+        let options = Some(CreateContainerOptions { name: Some(name), ..Default::default() });
+        let id = self.client.create_container(options, service.create_body(project, number)?).await?.id;
+
+        for (network, endpoint) in service.secondary_networks(project) {
+            let options = NetworkConnectRequest { container: Some(id.clone()), endpoint_config: Some(endpoint) };
+            if let Err(e) = self.client.connect_network(&network, options).await {
+                let _ = self.remove_container(&id).await;
+                return Err(e.into());
+            }
+        }
+
+        Ok(id)
+    }
 }
@@ -0,0 +1,942 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use bollard::secret::{ContainerCreateBody, ContainerInspectResponse, EndpointSettings, NetworkingConfig};
+use color_eyre::eyre::{Result, eyre};
+use yaml_rust2::yaml::Hash;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use super::run_config::{RunConfig, format_port, parse_bind, parse_port, split_command};
+
+pub const PROJECT_LABEL: &str = "com.docker.compose.project";
+pub const SERVICE_LABEL: &str = "com.docker.compose.service";
+pub const CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+pub const WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+pub const CONTAINER_NUMBER_LABEL: &str = "com.docker.compose.container-number";
+pub const ONEOFF_LABEL: &str = "com.docker.compose.oneoff";
+pub const NETWORK_LABEL: &str = "com.docker.compose.network";
+pub const VOLUME_LABEL: &str = "com.docker.compose.volume";
+
+const DEFAULT_NETWORK: &str = "default";
+/// Service sequences whose entries are added to the ones of earlier files instead of replacing them.
+const APPENDED_SEQUENCES: [&str; 6] = ["ports", "expose", "dns", "dns_search", "external_links", "tmpfs"];
+
+/// Where the compose files of a project live, as recorded by compose in the container labels.
+#[derive(Clone, Debug, Default)]
+pub struct ComposeSource {
+    pub working_dir: String,
+    pub config_files: Vec<String>,
+}
+
+impl ComposeSource {
+    pub fn from_labels(labels: &HashMap<String, String>) -> Option<Self> {
+        let config_files = labels.get(CONFIG_FILES_LABEL)?
+            .split(',')
+            .map(str::trim)
+            .filter(|file| !file.is_empty())
+            .map(String::from)
+            .collect::<Vec<String>>();
+
+        let working_dir = labels.get(WORKING_DIR_LABEL).cloned().unwrap_or_default();
+        (!config_files.is_empty()).then_some(Self { working_dir, config_files })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ComposeProject {
+    pub name: String,
+    pub source: ComposeSource,
+    pub services: BTreeMap<String, ComposeService>,
+    networks: BTreeMap<String, ComposeResource>,
+    volumes: BTreeMap<String, ComposeResource>,
+}
+
+/// A top-level network or volume of the file.
+#[derive(Clone, Debug, Default)]
+struct ComposeResource {
+    name: Option<String>,
+    external: bool,
+    driver: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ComposeService {
+    pub name: String,
+    pub image: Option<String>,
+    pub depends_on: Vec<String>,
+    pub environment: BTreeMap<String, String>,
+    pub ports: Vec<String>,
+    pub replicas: u32,
+    container_name: Option<String>,
+    command: Vec<String>,
+    entrypoint: Vec<String>,
+    volumes: Vec<String>,
+    networks: BTreeMap<String, Vec<String>>,
+    network_mode: Option<String>,
+    restart: Option<String>,
+    labels: BTreeMap<String, String>,
+    hostname: Option<String>,
+    user: Option<String>,
+    working_dir: Option<String>,
+    tty: bool,
+    stdin_open: bool,
+}
+
+impl ComposeProject {
+    /// Reads and merges the compose files of the project, later files overriding earlier ones.
+    /// Variables are substituted from the `.env` file of the working directory and the
+    /// environment crabd runs in, the latter taking precedence like it does for compose.
+    pub fn load(name: &str, source: &ComposeSource) -> Result<Self> {
+        let mut vars = read_env_file(&Path::new(&source.working_dir).join(".env"))
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<HashMap<String, String>>();
+        vars.extend(std::env::vars());
+
+        let mut document = Yaml::Hash(Hash::new());
+        for file in &source.config_files {
+            let path = resolve_path(&source.working_dir, file);
+            let content = fs::read_to_string(&path).map_err(|e| eyre!("Can't read {file}: {e}"))?;
+            let docs = YamlLoader::load_from_str(&content).map_err(|e| eyre!("Invalid YAML in {file}: {e}"))?;
+            if let Some(doc) = docs.into_iter().next() {
+                document = merge(document, resolve(doc, &vars));
+            }
+        }
+
+        let services = document["services"].as_hash()
+            .ok_or_else(|| eyre!("No services found in {}", source.config_files.join(", ")))?
+            .iter()
+            .filter_map(|(key, service)| Some((scalar(key)?, service)))
+            .map(|(key, service)| Ok((key.clone(), ComposeService::from(&key, service, &source.working_dir, &vars)?)))
+            .collect::<Result<BTreeMap<String, ComposeService>>>()?;
+
+        Ok(Self {
+            name: name.to_string(),
+            source: source.clone(),
+            services,
+            networks: parse_resources(&document["networks"]),
+            volumes: parse_resources(&document["volumes"]),
+        })
+    }
+
+    /// The services in the order they have to be started, dependencies first.
+    pub fn ordered_services(&self) -> Vec<&ComposeService> {
+        fn visit<'a>(project: &'a ComposeProject, name: &str, seen: &mut HashSet<String>, ordered: &mut Vec<&'a ComposeService>) {
+            let Some(service) = project.services.get(name) else { return };
+            if !seen.insert(name.to_string()) {
+                return;
+            }
+            for dependency in &service.depends_on {
+                visit(project, dependency, seen, ordered);
+            }
+            ordered.push(service);
+        }
+
+        let mut seen = HashSet::new();
+        let mut ordered = vec![];
+        for name in self.services.keys() {
+            visit(self, name, &mut seen, &mut ordered);
+        }
+        ordered
+    }
+
+    pub fn network_name(&self, key: &str) -> String {
+        resource_name(&self.name, key, self.networks.get(key))
+    }
+
+    pub fn volume_name(&self, key: &str) -> String {
+        resource_name(&self.name, key, self.volumes.get(key))
+    }
+
+    /// The networks used by the services that compose would create, as (key, name, driver).
+    pub fn networks_to_create(&self) -> Vec<(String, String, Option<String>)> {
+        let keys = self.services.values()
+            .filter(|service| service.network_mode.is_none())
+            .flat_map(|service| service.networks.keys())
+            .collect::<HashSet<&String>>();
+
+        let mut networks = keys.into_iter()
+            .filter(|key| !self.networks.get(*key).is_some_and(|n| n.external))
+            .map(|key| (key.clone(), self.network_name(key), self.networks.get(key).and_then(|n| n.driver.clone())))
+            .collect::<Vec<_>>();
+        networks.sort();
+        networks
+    }
+
+    /// The named volumes of the file that compose would create, as (key, name, driver).
+    pub fn volumes_to_create(&self) -> Vec<(String, String, Option<String>)> {
+        self.volumes.iter()
+            .filter(|(_, volume)| !volume.external)
+            .map(|(key, volume)| (key.clone(), self.volume_name(key), volume.driver.clone()))
+            .collect()
+    }
+}
+
+impl ComposeService {
+    fn from(name: &str, yaml: &Yaml, working_dir: &str, vars: &HashMap<String, String>) -> Result<Self> {
+        let optional = |key: &str| scalar(&yaml[key]).filter(|value| !value.is_empty());
+        let command = |key: &str| match &yaml[key] {
+            Yaml::Array(_) => Ok(string_list(&yaml[key])),
+            value => split_command(&scalar(value).unwrap_or_default()).map_err(|e| eyre!("{name}: {e}")),
+        };
+
+        // Files come first so `environment` overrides them, variables without a value are taken
+        // from the environment and left out if it doesn't have them either.
+        let mut environment = BTreeMap::new();
+        for file in env_files(&yaml["env_file"]) {
+            let path = resolve_path(working_dir, &file);
+            let pairs = read_env_file(Path::new(&path)).map_err(|e| eyre!("{name}: can't read env file {file}: {e}"))?;
+            environment.extend(pairs);
+        }
+        for (key, value) in key_values(&yaml["environment"]) {
+            match value.or_else(|| vars.get(&key).cloned()) {
+                Some(value) => environment.insert(key, value),
+                None => environment.remove(&key),
+            };
+        }
+
+        let ports = match &yaml["ports"] {
+            Yaml::Array(ports) => ports.iter().filter_map(port_entry).collect(),
+            _ => vec![],
+        };
+
+        let volumes = match &yaml["volumes"] {
+            Yaml::Array(volumes) => volumes.iter().filter_map(volume_entry).collect(),
+            _ => vec![],
+        };
+
+        let mut networks = match &yaml["networks"] {
+            Yaml::Hash(networks) => networks.iter()
+                .filter_map(|(key, network)| Some((scalar(key)?, string_list(&network["aliases"]))))
+                .collect::<BTreeMap<String, Vec<String>>>(),
+            value => string_list(value).into_iter().map(|key| (key, vec![])).collect(),
+        };
+        let network_mode = optional("network_mode");
+        if networks.is_empty() && network_mode.is_none() {
+            networks.insert(DEFAULT_NETWORK.to_string(), vec![]);
+        }
+
+        let depends_on = match &yaml["depends_on"] {
+            Yaml::Hash(services) => services.keys().filter_map(scalar).collect(),
+            value => string_list(value),
+        };
+
+        let labels = key_values(&yaml["labels"]).into_iter()
+            .map(|(key, value)| (key, value.unwrap_or_default()))
+            .collect();
+
+        // A fixed container name can only be taken by one container, so compose runs a single one.
+        let container_name = optional("container_name");
+        let replicas = scalar(&yaml["deploy"]["replicas"])
+            .or_else(|| scalar(&yaml["scale"]))
+            .and_then(|replicas| replicas.parse().ok())
+            .filter(|_| container_name.is_none())
+            .unwrap_or(1);
+
+        Ok(Self {
+            name: name.to_string(),
+            image: optional("image"),
+            depends_on,
+            environment,
+            ports,
+            replicas,
+            container_name,
+            command: command("command")?,
+            entrypoint: command("entrypoint")?,
+            volumes,
+            networks,
+            network_mode,
+            restart: optional("restart"),
+            labels,
+            hostname: optional("hostname"),
+            user: optional("user"),
+            working_dir: optional("working_dir"),
+            tty: yaml["tty"].as_bool().unwrap_or_default(),
+            stdin_open: yaml["stdin_open"].as_bool().unwrap_or_default(),
+        })
+    }
+
+    /// The name of container `number` of the service, numbers starting at 1 like in compose.
+    pub fn container_name(&self, project: &str, number: u32) -> String {
+        self.container_name.clone().unwrap_or_else(|| format!("{project}-{}-{number}", self.name))
+    }
+
+    /// The image of the service, or the one compose names after a build-only service.
+    pub fn image_name(&self, project: &str) -> String {
+        self.image.clone().unwrap_or_else(|| format!("{project}-{}", self.name))
+    }
+
+    pub fn is_built(&self) -> bool {
+        self.image.is_none()
+    }
+
+    /// The config of container `number` of the service, attached to its first network. Compose
+    /// labels are set, so compose itself and the container table treat the container as part of
+    /// the project.
+    pub fn create_body(&self, project: &ComposeProject, number: u32) -> Result<ContainerCreateBody> {
+        let to_err = |e: String| eyre!("{}: {e}", self.name);
+        let primary_network = self.networks.iter().next();
+
+        let run_config = RunConfig {
+            image: self.image_name(&project.name),
+            name: self.container_name(&project.name, number),
+            cmd: self.command.clone(),
+            env: self.environment.iter().map(|(key, value)| format!("{key}={value}")).collect(),
+            ports: self.ports.iter().map(|port| parse_port(port)).collect::<Result<_, String>>().map_err(to_err)?,
+            binds: self.binds(project).map_err(to_err)?,
+            network: match (&self.network_mode, primary_network) {
+                (Some(mode), _) => mode.clone(),
+                (None, Some((key, _))) => project.network_name(key),
+                (None, None) => String::new(),
+            },
+            restart_policy: self.restart.as_deref().map(|r| r.split(':').next().unwrap_or(r)).unwrap_or("no").to_string(),
+            interactive: false,
+        };
+
+        let mut labels = self.labels.clone().into_iter().collect::<HashMap<String, String>>();
+        labels.extend([
+            (PROJECT_LABEL.to_string(), project.name.clone()),
+            (SERVICE_LABEL.to_string(), self.name.clone()),
+            (CONFIG_FILES_LABEL.to_string(), project.source.config_files.join(",")),
+            (WORKING_DIR_LABEL.to_string(), project.source.working_dir.clone()),
+            (CONTAINER_NUMBER_LABEL.to_string(), number.to_string()),
+            (ONEOFF_LABEL.to_string(), "False".to_string()),
+        ]);
+
+        let anonymous_volumes = self.volumes.iter()
+            .filter(|volume| !volume.contains(':'))
+            .map(|volume| (volume.clone(), HashMap::new()))
+            .collect::<HashMap<String, HashMap<(), ()>>>();
+
+        let endpoints_config = match (&self.network_mode, primary_network) {
+            (None, Some((key, aliases))) => Some(HashMap::from([(project.network_name(key), self.endpoint(aliases))])),
+            _ => None,
+        };
+
+        let mut body = run_config.into_container_config();
+        body.entrypoint = Some(self.entrypoint.clone()).filter(|e| !e.is_empty());
+        body.labels = Some(labels);
+        body.hostname = self.hostname.clone();
+        body.user = self.user.clone();
+        body.working_dir = self.working_dir.clone();
+        body.tty = Some(self.tty);
+        body.open_stdin = Some(self.stdin_open);
+        body.attach_stdin = None;
+        body.volumes = Some(anonymous_volumes).filter(|v| !v.is_empty());
+        body.networking_config = endpoints_config.map(|endpoints| NetworkingConfig { endpoints_config: Some(endpoints) });
+        Ok(body)
+    }
+
+    /// The networks besides the first one, as (name, endpoint), which are connected after the
+    /// container is created since older engines take a single network on create.
+    pub fn secondary_networks(&self, project: &ComposeProject) -> Vec<(String, EndpointSettings)> {
+        if self.network_mode.is_some() {
+            return vec![];
+        }
+
+        self.networks.iter()
+            .skip(1)
+            .map(|(key, aliases)| (project.network_name(key), self.endpoint(aliases)))
+            .collect()
+    }
+
+    /// How the running container differs from the file, one line per difference in the image,
+    /// the environment variables of the file and the published ports.
+    pub fn drift(&self, project: &ComposeProject, container: &ContainerInspectResponse) -> Vec<String> {
+        let mut drift = vec![];
+        let config = container.config.clone().unwrap_or_default();
+
+        let running_image = config.image.unwrap_or_default();
+        let file_image = self.image_name(&project.name);
+        if normalize_image(&running_image) != normalize_image(&file_image) {
+            drift.push(format!("image: {running_image} → {file_image}"));
+        }
+
+        let running_env = config.env.unwrap_or_default().into_iter()
+            .filter_map(|pair| pair.split_once('=').map(|(key, value)| (key.to_string(), value.to_string())))
+            .collect::<HashMap<String, String>>();
+        for (key, value) in &self.environment {
+            match running_env.get(key) {
+                Some(running) if running == value => {}
+                Some(running) => drift.push(format!("env {key}: {running} → {value}")),
+                None => drift.push(format!("env {key}: unset → {value}")),
+            }
+        }
+
+        let running_ports = container.host_config.as_ref()
+            .and_then(|host_config| host_config.port_bindings.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(container_port, bindings)| {
+                bindings.unwrap_or_default().into_iter().map(move |binding| format_port(
+                    &binding.host_ip.unwrap_or_default(),
+                    &binding.host_port.unwrap_or_default(),
+                    &container_port,
+                ))
+            })
+            .collect::<Vec<String>>();
+        let file_ports = self.ports.iter()
+            .filter_map(|port| parse_port(port).ok())
+            .map(|port| port.to_string())
+            .collect::<Vec<String>>();
+        if sorted(&running_ports) != sorted(&file_ports) {
+            let or_none = |ports: Vec<String>| if ports.is_empty() { "none".to_string() } else { ports.join(", ") };
+            drift.push(format!("ports: {} → {}", or_none(sorted(&running_ports)), or_none(sorted(&file_ports))));
+        }
+
+        drift
+    }
+
+    fn endpoint(&self, aliases: &[String]) -> EndpointSettings {
+        let mut aliases = aliases.to_vec();
+        aliases.insert(0, self.name.clone());
+        EndpointSettings { aliases: Some(aliases), ..Default::default() }
+    }
+
+    /// The volume mounts as binds, with named volumes resolved to their project-scoped names and
+    /// relative host paths to the working directory. Anonymous volumes are not binds.
+    fn binds(&self, project: &ComposeProject) -> Result<Vec<String>, String> {
+        self.volumes.iter()
+            .filter(|volume| volume.contains(':'))
+            .map(|volume| {
+                let (source, rest) = volume.split_once(':').unwrap_or_default();
+                let source = match source {
+                    s if s.starts_with('.') => resolve_path(&project.source.working_dir, s),
+                    s if s.starts_with(['/', '~']) => s.to_string(),
+                    s => project.volume_name(s),
+                };
+                parse_bind(&format!("{source}:{rest}"))
+            })
+            .collect()
+    }
+}
+
+fn resource_name(project: &str, key: &str, resource: Option<&ComposeResource>) -> String {
+    match resource {
+        Some(ComposeResource { name: Some(name), .. }) => name.clone(),
+        Some(ComposeResource { external: true, .. }) => key.to_string(),
+        _ => format!("{project}_{key}"),
+    }
+}
+
+fn parse_resources(yaml: &Yaml) -> BTreeMap<String, ComposeResource> {
+    let Some(resources) = yaml.as_hash() else { return BTreeMap::new() };
+    resources.iter()
+        .filter_map(|(key, resource)| {
+            let external = &resource["external"];
+            let resource = ComposeResource {
+                name: scalar(&resource["name"]).or_else(|| scalar(&external["name"])),
+                external: external.as_bool().unwrap_or(external.as_hash().is_some()),
+                driver: scalar(&resource["driver"]),
+            };
+            Some((scalar(key)?, resource))
+        })
+        .collect()
+}
+
+/// Turns the long port syntax into the short one, ranges and other forms are kept as written.
+fn port_entry(yaml: &Yaml) -> Option<String> {
+    if yaml.as_hash().is_none() {
+        return scalar(yaml);
+    }
+
+    let target = scalar(&yaml["target"])?;
+    let protocol = scalar(&yaml["protocol"]).unwrap_or_else(|| "tcp".to_string());
+    let published = scalar(&yaml["published"]).unwrap_or_default();
+    let host_ip = scalar(&yaml["host_ip"]).unwrap_or_default();
+    Some(format_port(&host_ip, &published, &format!("{target}/{protocol}")))
+}
+
+/// Turns the long volume syntax into the short one, leaving out tmpfs mounts.
+fn volume_entry(yaml: &Yaml) -> Option<String> {
+    if yaml.as_hash().is_none() {
+        return scalar(yaml);
+    }
+
+    let target = scalar(&yaml["target"])?;
+    if scalar(&yaml["type"]).as_deref() == Some("tmpfs") {
+        return None;
+    }
+
+    let read_only = yaml["read_only"].as_bool().unwrap_or_default();
+    Some(match (scalar(&yaml["source"]), read_only) {
+        (Some(source), true) => format!("{source}:{target}:ro"),
+        (Some(source), false) => format!("{source}:{target}"),
+        (None, _) => target,
+    })
+}
+
+fn env_files(yaml: &Yaml) -> Vec<String> {
+    match yaml {
+        Yaml::Array(files) => files.iter()
+            .filter_map(|file| match file.as_hash() {
+                Some(_) => scalar(&file["path"]),
+                None => scalar(file),
+            })
+            .collect(),
+        value => scalar(value).into_iter().collect(),
+    }
+}
+
+/// Reads `KEY=VALUE` lines, skipping comments and blank lines and unquoting the values.
+fn read_env_file(path: &Path) -> std::io::Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(path)?;
+    let pairs = content.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.strip_prefix("export ").unwrap_or(line).split_once('='))
+        .map(|(key, value)| {
+            let value = value.trim();
+            let unquoted = ['"', '\''].iter()
+                .find_map(|q| value.strip_prefix(*q).and_then(|v| v.strip_suffix(*q)))
+                .unwrap_or(value);
+            (key.trim().to_string(), unquoted.to_string())
+        })
+        .collect();
+    Ok(pairs)
+}
+
+fn resolve_path(working_dir: &str, path: &str) -> String {
+    Path::new(working_dir).join(path).to_string_lossy().to_string()
+}
+
+/// Substitutes variables in every string of the document and expands merge keys
+/// (`<<: *anchor`), where the keys of the mapping win over the merged ones.
+fn resolve(yaml: Yaml, vars: &HashMap<String, String>) -> Yaml {
+    match yaml {
+        Yaml::String(value) => Yaml::String(interpolate(&value, vars)),
+        Yaml::Array(items) => Yaml::Array(items.into_iter().map(|item| resolve(item, vars)).collect()),
+        Yaml::Hash(hash) => {
+            let merge_key = Yaml::String("<<".to_string());
+            let mut resolved = Hash::new();
+            for (key, value) in hash {
+                if key != merge_key {
+                    resolved.insert(key, resolve(value, vars));
+                    continue;
+                }
+
+                let merged = match value {
+                    Yaml::Array(items) => items,
+                    value => vec![value],
+                };
+                for (key, value) in merged.into_iter().filter_map(|m| resolve(m, vars).into_hash()).flatten() {
+                    if !resolved.contains_key(&key) {
+                        resolved.insert(key, value);
+                    }
+                }
+            }
+            Yaml::Hash(resolved)
+        }
+        yaml => yaml,
+    }
+}
+
+/// Merges `other` into `base` the way compose merges override files: mappings key by key,
+/// the sequences of [`APPENDED_SEQUENCES`] by appending the new entries, `volumes` by mount
+/// target, `environment` and `labels` lists by variable and anything else by replacing it.
+fn merge(base: Yaml, other: Yaml) -> Yaml {
+    match (base, other) {
+        (Yaml::Hash(mut base), Yaml::Hash(other)) => {
+            for (key, value) in other {
+                let merged = match base.remove(&key) {
+                    Some(existing) => merge_entry(&key, existing, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Yaml::Hash(base)
+        }
+        (base, Yaml::Null | Yaml::BadValue) => base,
+        (_, other) => other,
+    }
+}
+
+fn merge_entry(key: &Yaml, base: Yaml, other: Yaml) -> Yaml {
+    match (key.as_str(), base, other) {
+        (Some(key), Yaml::Array(mut items), Yaml::Array(others)) if APPENDED_SEQUENCES.contains(&key) => {
+            for item in others {
+                if !items.contains(&item) {
+                    items.push(item);
+                }
+            }
+            Yaml::Array(items)
+        }
+        (Some("volumes"), Yaml::Array(items), Yaml::Array(others)) => merge_by(items, others, mount_target),
+        (Some("environment" | "labels"), Yaml::Array(items), Yaml::Array(others)) => {
+            merge_by(items, others, |item| scalar(item).map(|item| item.split('=').next().unwrap_or_default().to_string()))
+        }
+        (_, base, other) => merge(base, other),
+    }
+}
+
+/// Appends `others` to `items`, dropping the items that have the same key as one of `others`.
+fn merge_by(items: Vec<Yaml>, others: Vec<Yaml>, key: fn(&Yaml) -> Option<String>) -> Yaml {
+    let keys = others.iter().filter_map(key).collect::<HashSet<String>>();
+    let mut merged = items.into_iter()
+        .filter(|item| key(item).is_none_or(|key| !keys.contains(&key)))
+        .collect::<Vec<Yaml>>();
+    merged.extend(others);
+    Yaml::Array(merged)
+}
+
+/// The path a volume entry is mounted at in the container.
+fn mount_target(yaml: &Yaml) -> Option<String> {
+    match yaml.as_hash() {
+        Some(_) => scalar(&yaml["target"]),
+        None => {
+            let volume = scalar(yaml)?;
+            let mut parts = volume.split(':');
+            let first = parts.next().unwrap_or_default();
+            Some(parts.next().unwrap_or(first).to_string())
+        }
+    }
+}
+
+/// Substitutes `$VAR` and `${VAR}` along with the `:-`, `-`, `:+` and `+` modifiers, `$$`
+/// being a literal dollar sign. Unset variables are substituted with an empty string.
+fn interpolate(text: &str, vars: &HashMap<String, String>) -> String {
+    let is_name_char = |c: &char| c.is_ascii_alphanumeric() || *c == '_';
+    let mut result = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.peek() {
+            Some('$') => {
+                chars.next();
+                result.push('$');
+            }
+            Some('{') => {
+                chars.next();
+                let mut expression = String::new();
+                let mut depth = 1;
+                for c in chars.by_ref() {
+                    depth += match c {
+                        '{' => 1,
+                        '}' => -1,
+                        _ => 0,
+                    };
+                    if depth == 0 {
+                        break;
+                    }
+                    expression.push(c);
+                }
+                result.push_str(&substitute(&expression, vars));
+            }
+            Some(c) if c.is_ascii_alphabetic() || *c == '_' => {
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(is_name_char) {
+                    name.push(c);
+                }
+                result.push_str(vars.get(&name).map_or("", String::as_str));
+            }
+            _ => result.push('$'),
+        }
+    }
+
+    result
+}
+
+fn substitute(expression: &str, vars: &HashMap<String, String>) -> String {
+    let end = expression.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(expression.len());
+    let (name, modifier) = expression.split_at(end);
+    let value = vars.get(name);
+    let is_set_and_not_empty = value.is_some_and(|v| !v.is_empty());
+
+    if let Some(default) = modifier.strip_prefix(":-") {
+        return if is_set_and_not_empty { value.cloned().unwrap_or_default() } else { interpolate(default, vars) };
+    }
+    if let Some(default) = modifier.strip_prefix('-') {
+        return value.cloned().unwrap_or_else(|| interpolate(default, vars));
+    }
+    if let Some(alternative) = modifier.strip_prefix(":+") {
+        return if is_set_and_not_empty { interpolate(alternative, vars) } else { String::new() };
+    }
+    if let Some(alternative) = modifier.strip_prefix('+') {
+        return if value.is_some() { interpolate(alternative, vars) } else { String::new() };
+    }
+
+    value.cloned().unwrap_or_default()
+}
+
+fn scalar(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(value) | Yaml::Real(value) => Some(value.clone()),
+        Yaml::Integer(value) => Some(value.to_string()),
+        Yaml::Boolean(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn string_list(yaml: &Yaml) -> Vec<String> {
+    match yaml {
+        Yaml::Array(items) => items.iter().filter_map(scalar).collect(),
+        value => scalar(value).into_iter().collect(),
+    }
+}
+
+/// Reads a mapping or a list of `KEY=VALUE` entries, a missing value being `None`.
+fn key_values(yaml: &Yaml) -> Vec<(String, Option<String>)> {
+    match yaml {
+        Yaml::Hash(pairs) => pairs.iter()
+            .filter_map(|(key, value)| Some((scalar(key)?, scalar(value))))
+            .collect(),
+        Yaml::Array(items) => items.iter()
+            .filter_map(scalar)
+            .map(|item| match item.split_once('=') {
+                Some((key, value)) => (key.to_string(), Some(value.to_string())),
+                None => (item, None),
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Adds the implicit `latest` tag, so `nginx` and `nginx:latest` compare equal.
+fn normalize_image(image: &str) -> String {
+    let name = image.rsplit('/').next().unwrap_or(image);
+    if name.contains([':', '@']) { image.to_string() } else { format!("{image}:latest") }
+}
+
+fn sorted(values: &[String]) -> Vec<String> {
+    let mut values = values.to_vec();
+    values.sort();
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(text: &str) -> Yaml {
+        YamlLoader::load_from_str(text).unwrap().remove(0)
+    }
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    fn project(services: &[(&str, &[&str])]) -> ComposeProject {
+        let services = services.iter()
+            .map(|(name, depends_on)| {
+                let service = ComposeService {
+                    name: name.to_string(),
+                    depends_on: depends_on.iter().map(|d| d.to_string()).collect(),
+                    ..Default::default()
+                };
+                (name.to_string(), service)
+            })
+            .collect();
+
+        ComposeProject {
+            name: "app".to_string(),
+            source: ComposeSource::default(),
+            services,
+            networks: BTreeMap::new(),
+            volumes: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn interpolate_substitutes_plain_and_braced_variables() {
+        let vars = vars(&[("TAG", "1.2"), ("NAME", "web")]);
+        assert_eq!(interpolate("nginx:$TAG", &vars), "nginx:1.2");
+        assert_eq!(interpolate("${NAME}-${TAG}", &vars), "web-1.2");
+        assert_eq!(interpolate("$NAME.conf", &vars), "web.conf");
+        assert_eq!(interpolate("$MISSING|${MISSING}", &vars), "|");
+    }
+
+    #[test]
+    fn interpolate_keeps_escaped_and_lone_dollars() {
+        let vars = vars(&[("HOME", "/root")]);
+        assert_eq!(interpolate("$$HOME", &vars), "$HOME");
+        assert_eq!(interpolate("cost: 5$", &vars), "cost: 5$");
+        assert_eq!(interpolate("$1", &vars), "$1");
+    }
+
+    #[test]
+    fn interpolate_expands_nested_defaults() {
+        let vars = vars(&[("PORT", "8080")]);
+        assert_eq!(interpolate("${HOST:-${FALLBACK:-localhost}}:${PORT}", &vars), "localhost:8080");
+    }
+
+    #[test]
+    fn substitute_applies_modifiers() {
+        let vars = vars(&[("SET", "value"), ("EMPTY", "")]);
+
+        assert_eq!(substitute("SET:-default", &vars), "value");
+        assert_eq!(substitute("EMPTY:-default", &vars), "default");
+        assert_eq!(substitute("UNSET:-default", &vars), "default");
+
+        assert_eq!(substitute("EMPTY-default", &vars), "");
+        assert_eq!(substitute("UNSET-default", &vars), "default");
+
+        assert_eq!(substitute("SET:+alternative", &vars), "alternative");
+        assert_eq!(substitute("EMPTY:+alternative", &vars), "");
+        assert_eq!(substitute("EMPTY+alternative", &vars), "alternative");
+        assert_eq!(substitute("UNSET+alternative", &vars), "");
+
+        assert_eq!(substitute("SET", &vars), "value");
+        assert_eq!(substitute("UNSET", &vars), "");
+    }
+
+    #[test]
+    fn resolve_expands_merge_keys_with_own_keys_winning() {
+        let document = yaml("
+base: &base
+  image: nginx
+  restart: always
+web:
+  <<: *base
+  restart: 'no'
+");
+        let resolved = resolve(document, &HashMap::new());
+        assert_eq!(resolved["web"]["image"].as_str(), Some("nginx"));
+        assert_eq!(resolved["web"]["restart"].as_str(), Some("no"));
+        assert!(resolved["web"]["<<"].is_badvalue());
+    }
+
+    #[test]
+    fn resolve_merges_a_list_of_mappings_in_order() {
+        let document = yaml("
+a: &a {image: a, user: a}
+b: &b {image: b, hostname: b}
+web:
+  <<: [*a, *b]
+");
+        let resolved = resolve(document, &HashMap::new());
+        assert_eq!(resolved["web"]["image"].as_str(), Some("a"));
+        assert_eq!(resolved["web"]["user"].as_str(), Some("a"));
+        assert_eq!(resolved["web"]["hostname"].as_str(), Some("b"));
+    }
+
+    #[test]
+    fn resolve_interpolates_nested_strings() {
+        let document = yaml("services: {web: {image: 'nginx:${TAG}', ports: ['${PORT:-80}:80']}}");
+        let resolved = resolve(document, &vars(&[("TAG", "alpine")]));
+        assert_eq!(resolved["services"]["web"]["image"].as_str(), Some("nginx:alpine"));
+        assert_eq!(resolved["services"]["web"]["ports"][0].as_str(), Some("80:80"));
+    }
+
+    #[test]
+    fn merge_overrides_mappings_key_by_key() {
+        let merged = merge(
+            yaml("services: {web: {image: nginx, environment: {A: '1', B: '2'}}}"),
+            yaml("services: {web: {image: 'nginx:alpine', environment: {B: '3'}}, db: {image: postgres}}"),
+        );
+        let web = &merged["services"]["web"];
+        assert_eq!(web["image"].as_str(), Some("nginx:alpine"));
+        assert_eq!(web["environment"]["A"].as_str(), Some("1"));
+        assert_eq!(web["environment"]["B"].as_str(), Some("3"));
+        assert_eq!(merged["services"]["db"]["image"].as_str(), Some("postgres"));
+    }
+
+    #[test]
+    fn merge_keeps_base_for_empty_values() {
+        let merged = merge(yaml("web: {image: nginx}"), yaml("web:"));
+        assert_eq!(merged["web"]["image"].as_str(), Some("nginx"));
+    }
+
+    #[test]
+    fn merge_appends_ports_without_duplicates() {
+        let merged = merge(yaml("ports: ['80:80', '443:443']"), yaml("ports: ['443:443', '8080:8080']"));
+        assert_eq!(string_list(&merged["ports"]), ["80:80", "443:443", "8080:8080"]);
+    }
+
+    #[test]
+    fn merge_replaces_volumes_by_target() {
+        let merged = merge(
+            yaml("volumes: ['data:/data', './conf:/etc/app:ro', /cache]"),
+            yaml("volumes: ['./dev-data:/data', {type: bind, source: ./logs, target: /logs}]"),
+        );
+        let volumes = merged["volumes"].as_vec().unwrap();
+        assert_eq!(volumes.len(), 4);
+        assert_eq!(volumes.iter().filter_map(mount_target).collect::<Vec<String>>(), ["/etc/app", "/cache", "/data", "/logs"]);
+        assert_eq!(volumes[2].as_str(), Some("./dev-data:/data"));
+    }
+
+    #[test]
+    fn merge_replaces_environment_lists_by_variable() {
+        let merged = merge(yaml("environment: [A=1, B=2]"), yaml("environment: [B=3, C]"));
+        assert_eq!(string_list(&merged["environment"]), ["A=1", "B=3", "C"]);
+    }
+
+    #[test]
+    fn merge_replaces_other_sequences() {
+        let merged = merge(yaml("command: [npm, start]"), yaml("command: [npm, run, dev]"));
+        assert_eq!(string_list(&merged["command"]), ["npm", "run", "dev"]);
+    }
+
+    #[test]
+    fn port_entry_converts_the_long_syntax() {
+        assert_eq!(port_entry(&yaml("'8080:80'")).as_deref(), Some("8080:80"));
+        assert_eq!(port_entry(&yaml("{target: 80, published: 8080}")).as_deref(), Some("8080:80/tcp"));
+        assert_eq!(port_entry(&yaml("{target: 53, published: 53, protocol: udp, host_ip: 127.0.0.1}")).as_deref(), Some("127.0.0.1:53:53/udp"));
+        assert_eq!(port_entry(&yaml("{target: 80}")).as_deref(), Some("80/tcp"));
+        assert_eq!(port_entry(&yaml("{published: 80}")), None);
+    }
+
+    #[test]
+    fn volume_entry_converts_the_long_syntax() {
+        assert_eq!(volume_entry(&yaml("'data:/data'")).as_deref(), Some("data:/data"));
+        assert_eq!(volume_entry(&yaml("{type: volume, source: data, target: /data}")).as_deref(), Some("data:/data"));
+        assert_eq!(volume_entry(&yaml("{type: bind, source: ./conf, target: /conf, read_only: true}")).as_deref(), Some("./conf:/conf:ro"));
+        assert_eq!(volume_entry(&yaml("{type: volume, target: /cache}")).as_deref(), Some("/cache"));
+        assert_eq!(volume_entry(&yaml("{type: tmpfs, target: /tmp}")), None);
+    }
+
+    #[test]
+    fn normalize_image_adds_the_latest_tag() {
+        assert_eq!(normalize_image("nginx"), "nginx:latest");
+        assert_eq!(normalize_image("nginx:alpine"), "nginx:alpine");
+        assert_eq!(normalize_image("localhost:5000/app"), "localhost:5000/app:latest");
+        assert_eq!(normalize_image("app@sha256:abc"), "app@sha256:abc");
+    }
+
+    #[test]
+    fn ordered_services_starts_dependencies_first() {
+        let project = project(&[("web", &["api", "cache"]), ("api", &["db"]), ("db", &[]), ("cache", &[])]);
+        let ordered = project.ordered_services().iter().map(|s| s.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(ordered, ["db", "api", "cache", "web"]);
+    }
+
+    #[test]
+    fn ordered_services_ignores_cycles_and_unknown_services() {
+        let project = project(&[("a", &["b", "missing"]), ("b", &["a"])]);
+        let ordered = project.ordered_services().iter().map(|s| s.name.as_str()).collect::<Vec<&str>>();
+        assert_eq!(ordered, ["b", "a"]);
+    }
+
+    #[test]
+    fn key_values_reads_mappings_and_lists() {
+        assert_eq!(key_values(&yaml("{A: '1', B: }")), [("A".to_string(), Some("1".to_string())), ("B".to_string(), None)]);
+        assert_eq!(key_values(&yaml("[A=1=2, B]")), [("A".to_string(), Some("1=2".to_string())), ("B".to_string(), None)]);
+    }
+
+    #[test]
+    fn read_env_file_skips_comments_and_unquotes_values() {
+        let path = std::env::temp_dir().join(format!("crabd-test-{}.env", std::process::id()));
+        fs::write(&path, "# comment\n\nA=1\nexport B=\"two words\"\nC='x=y'\nINVALID\n").unwrap();
+        let pairs = read_env_file(&path);
+        let _ = fs::remove_file(&path);
+
+        let expected = [("A", "1"), ("B", "two words"), ("C", "x=y")]
+            .map(|(key, value)| (key.to_string(), value.to_string()));
+        assert_eq!(pairs.unwrap(), expected);
+    }
+
+    #[test]
+    fn service_reads_replicas_unless_the_container_name_is_fixed() {
+        let service = |text: &str| ComposeService::from("web", &yaml(text), "/srv", &HashMap::new()).unwrap();
+        assert_eq!(service("image: nginx").replicas, 1);
+        assert_eq!(service("{image: nginx, scale: 2}").replicas, 2);
+        assert_eq!(service("{image: nginx, deploy: {replicas: 3}}").replicas, 3);
+        assert_eq!(service("{image: nginx, deploy: {replicas: 3}, container_name: web}").replicas, 1);
+        assert_eq!(service("{image: nginx, scale: 2}").container_name("app", 2), "app-web-2");
+    }
+}
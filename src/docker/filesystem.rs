@@ -0,0 +1,233 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use bollard::secret::ChangeType;
+
+/// Image used for helper containers that mount volumes, overridable with `CRABD_HELPER_IMAGE`.
+pub const DEFAULT_HELPER_IMAGE: &str = "busybox";
+pub const HELPER_IMAGE_ENV: &str = "CRABD_HELPER_IMAGE";
+pub const HELPER_LABEL: &str = "crabd.helper";
+pub const VOLUME_MOUNT_PATH: &str = "/volume";
+pub const COPY_SOURCE_PATH: &str = "/from";
+pub const COPY_TARGET_PATH: &str = "/to";
+/// Directory in the volume a replacing restore extracts to, before it takes the place of the old content.
+pub const RESTORE_STAGING_DIR: &str = ".crabd-restore";
+
+#[derive(Clone, Debug)]
+pub struct FileEntry {
+    pub name: String,
+    pub kind: FileKind,
+    pub size: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileKind {
+    Directory,
+    File,
+    Link,
+    Other,
+}
+
+/// How a path in a container differs from its image, as reported by `docker diff`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileChange {
+    Added,
+    Changed,
+    Deleted,
+}
+
+impl FileChange {
+    pub fn from(kind: ChangeType) -> Self {
+        match kind {
+            ChangeType::_1 => Self::Added,
+            ChangeType::_2 => Self::Deleted,
+            ChangeType::_0 => Self::Changed,
+        }
+    }
+}
+
+impl fmt::Display for FileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added => write!(f, "added"),
+            Self::Changed => write!(f, "changed"),
+            Self::Deleted => write!(f, "deleted"),
+        }
+    }
+}
+
+pub fn helper_image() -> String {
+    std::env::var(HELPER_IMAGE_ENV).unwrap_or_else(|_| DEFAULT_HELPER_IMAGE.to_string())
+}
+
+/// Command printing `type|size|path` for every direct child of `path`.
+pub fn list_directory_command(path: &str) -> Vec<String> {
+    ["find", path, "-mindepth", "1", "-maxdepth", "1", "-exec", "stat", "-c", "%F|%s|%n", "{}", ";"]
+        .map(String::from)
+        .to_vec()
+}
+
+/// Parses the output of [`list_directory_command`], directories first.
+pub fn parse_directory_listing(output: &str) -> Vec<FileEntry> {
+    let mut entries = output.lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '|');
+            let kind = match parts.next()? {
+                "directory" => FileKind::Directory,
+                "regular file" | "regular empty file" => FileKind::File,
+                "symbolic link" => FileKind::Link,
+                _ => FileKind::Other,
+            };
+            let size = parts.next()?.parse().unwrap_or(-1);
+            let name = parts.next()?.rsplit('/').next()?.to_string();
+            Some(FileEntry { name, kind, size })
+        })
+        .collect::<Vec<FileEntry>>();
+
+    sort_entries(&mut entries);
+    entries
+}
+
+/// Lists every directory of the tree at `dir`, keyed by its path, from the entries of an archive
+/// of it as returned by [`super::archive::read_archive_entries`]. The archive holds `dir` under
+/// its own name, except for the root. Directories that only show in the paths below them are
+/// listed too.
+pub fn parse_archive_tree(dir: &str, archive_entries: &[(String, tar::EntryType, u64)]) -> BTreeMap<String, Vec<FileEntry>> {
+    let base = dir.trim_end_matches('/').rsplit('/').next().unwrap_or_default();
+    let mut tree = BTreeMap::from([(dir.to_string(), vec![])]);
+
+    for (path, entry_type, size) in archive_entries {
+        let path = path.trim_start_matches("./").trim_matches('/');
+        let relative = match path.split_once('/') {
+            _ if base.is_empty() => path,
+            Some((first, rest)) if first == base => rest,
+            None if path == base => continue,
+            _ => path,
+        };
+        let (parents, name) = relative.rsplit_once('/').unwrap_or(("", relative));
+        if name.is_empty() {
+            continue;
+        }
+
+        let mut parent = dir.to_string();
+        for directory in parents.split('/').filter(|d| !d.is_empty()) {
+            let path = join_path(&parent, directory);
+            if !tree.contains_key(&path) {
+                let entry = FileEntry { name: directory.to_string(), kind: FileKind::Directory, size: -1 };
+                tree.entry(parent).or_default().push(entry);
+                tree.insert(path.clone(), vec![]);
+            }
+            parent = path;
+        }
+
+        let kind = match entry_type {
+            t if t.is_dir() => FileKind::Directory,
+            t if t.is_file() => FileKind::File,
+            t if t.is_symlink() || t.is_hard_link() => FileKind::Link,
+            _ => FileKind::Other,
+        };
+        if kind == FileKind::Directory {
+            let path = join_path(&parent, name);
+            if tree.contains_key(&path) {
+                continue;
+            }
+            tree.insert(path, vec![]);
+        }
+
+        let size = if kind == FileKind::File { *size as i64 } else { -1 };
+        tree.entry(parent).or_default().push(FileEntry { name: name.to_string(), kind, size });
+    }
+
+    for entries in tree.values_mut() {
+        sort_entries(entries);
+    }
+    tree
+}
+
+/// Sorts directories first, then by name.
+pub fn sort_entries(entries: &mut [FileEntry]) {
+    entries.sort_by(|p, n| {
+        let p_is_dir = p.kind == FileKind::Directory;
+        let n_is_dir = n.kind == FileKind::Directory;
+        n_is_dir.cmp(&p_is_dir).then(p.name.cmp(&n.name))
+    });
+}
+
+pub fn join_path(parent: &str, name: &str) -> String {
+    format!("{}/{name}", parent.trim_end_matches('/'))
+}
+
+pub fn parent_path(path: &str) -> String {
+    match path.trim_end_matches('/').rsplit_once('/') {
+        Some(("", _)) | None => "/".to_string(),
+        Some((parent, _)) => parent.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, entry_type: tar::EntryType, size: u64) -> (String, tar::EntryType, u64) {
+        (path.to_string(), entry_type, size)
+    }
+
+    fn names(entries: Option<&Vec<FileEntry>>) -> Vec<(String, FileKind, i64)> {
+        entries.unwrap().iter().map(|e| (e.name.clone(), e.kind, e.size)).collect()
+    }
+
+    #[test]
+    fn parse_archive_tree_lists_every_directory() {
+        let entries = [
+            entry("etc/", tar::EntryType::Directory, 0),
+            entry("etc/hosts", tar::EntryType::Regular, 12),
+            entry("etc/ssl/", tar::EntryType::Directory, 0),
+            entry("etc/ssl/cert.pem", tar::EntryType::Regular, 100),
+            entry("bin", tar::EntryType::Symlink, 0),
+        ];
+        let tree = parse_archive_tree("/", &entries);
+
+        assert_eq!(names(tree.get("/")), vec![
+            ("etc".to_string(), FileKind::Directory, -1),
+            ("bin".to_string(), FileKind::Link, -1),
+        ]);
+        assert_eq!(names(tree.get("/etc")), vec![
+            ("ssl".to_string(), FileKind::Directory, -1),
+            ("hosts".to_string(), FileKind::File, 12),
+        ]);
+        assert_eq!(names(tree.get("/etc/ssl")), vec![("cert.pem".to_string(), FileKind::File, 100)]);
+    }
+
+    #[test]
+    fn parse_archive_tree_strips_the_archived_directory() {
+        let entries = [
+            entry("app/", tar::EntryType::Directory, 0),
+            entry("app/data/", tar::EntryType::Directory, 0),
+            entry("app/main.py", tar::EntryType::Regular, 5),
+        ];
+        let tree = parse_archive_tree("/srv/app", &entries);
+
+        assert_eq!(names(tree.get("/srv/app")), vec![
+            ("data".to_string(), FileKind::Directory, -1),
+            ("main.py".to_string(), FileKind::File, 5),
+        ]);
+        assert_eq!(names(tree.get("/srv/app/data")), vec![]);
+    }
+
+    #[test]
+    fn parse_archive_tree_adds_implied_directories() {
+        let tree = parse_archive_tree("/", &[entry("var/log/app.log", tar::EntryType::Regular, 3)]);
+
+        assert_eq!(names(tree.get("/")), vec![("var".to_string(), FileKind::Directory, -1)]);
+        assert_eq!(names(tree.get("/var")), vec![("log".to_string(), FileKind::Directory, -1)]);
+        assert_eq!(names(tree.get("/var/log")), vec![("app.log".to_string(), FileKind::File, 3)]);
+    }
+
+    #[test]
+    fn parent_path_stops_at_root() {
+        assert_eq!(parent_path("/etc/ssl"), "/etc");
+        assert_eq!(parent_path("/etc"), "/");
+        assert_eq!(parent_path("/"), "/");
+        assert_eq!(join_path("/", "etc"), "/etc");
+    }
+}
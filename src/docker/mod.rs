@@ -1 +1,7 @@
-pub mod client;
\ No newline at end of file
+pub mod archive;
+pub mod client;
+pub mod compose;
+pub mod filesystem;
+pub mod recreate;
+pub mod run_config;
+pub mod swarm_config;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use bollard::secret::{
+    ContainerConfig, ContainerCreateBody, ContainerInspectResponse, EndpointSettings, MountPointTypeEnum, MountTypeEnum,
+    NetworkingConfig,
+};
+
+/// Builds the configuration for an identical replacement of an inspected container. Anonymous
+/// volumes are mounted by name so their data survives the recreation, and every connected
+/// network is kept along with its aliases and static addresses.
+pub fn recreate_config(inspect: ContainerInspectResponse) -> ContainerCreateBody {
+    let id = inspect.id.unwrap_or_default();
+    let mut config = create_body(inspect.config.unwrap_or_default());
+    let mut host_config = inspect.host_config.unwrap_or_default();
+
+    // Docker sets the hostname to the short container id unless one was given.
+    if config.hostname.as_deref().is_some_and(|hostname| id.starts_with(hostname)) {
+        config.hostname = None;
+    }
+
+    let mut binds = host_config.binds.take().unwrap_or_default();
+    let mounted = binds.iter()
+        .filter_map(|bind| bind.split(':').nth(1).map(String::from))
+        .chain(host_config.mounts.iter().flatten().filter_map(|mount| mount.target.clone()))
+        .collect::<Vec<String>>();
+
+    for mount in inspect.mounts.unwrap_or_default() {
+        let (Some(name), Some(destination)) = (mount.name, mount.destination) else { continue };
+        if mount.typ != Some(MountPointTypeEnum::VOLUME) || mounted.contains(&destination) {
+            continue;
+        }
+        let mode = if mount.rw == Some(false) { ":ro" } else { "" };
+        binds.push(format!("{name}:{destination}{mode}"));
+    }
+
+    host_config.binds = Some(binds).filter(|b| !b.is_empty());
+    config.host_config = Some(host_config);
+
+    let networks = inspect.network_settings.and_then(|settings| settings.networks).unwrap_or_default();
+    let endpoints_config = networks.into_iter()
+        .map(|(name, endpoint)| {
+            let settings = EndpointSettings {
+                ipam_config: endpoint.ipam_config,
+                links: endpoint.links,
+                aliases: endpoint.aliases,
+                driver_opts: endpoint.driver_opts,
+                ..Default::default()
+            };
+            (name, settings)
+        })
+        .collect();
+    config.networking_config = Some(NetworkingConfig { endpoints_config: Some(endpoints_config) });
+
+    config
+}
+
+/// Points every bind and mount of the `source` volume at the `target` volume.
+pub fn replace_volume(config: &mut ContainerCreateBody, source: &str, target: &str) {
+    let Some(host_config) = config.host_config.as_mut() else { return };
+
+    for bind in host_config.binds.iter_mut().flatten() {
+        if let Some(rest) = bind.strip_prefix(source).and_then(|rest| rest.strip_prefix(':')) {
+            *bind = format!("{target}:{rest}");
+        }
+    }
+
+    for mount in host_config.mounts.iter_mut().flatten() {
+        if mount.typ == Some(MountTypeEnum::VOLUME) && mount.source.as_deref() == Some(source) {
+            mount.source = Some(target.to_string());
+        }
+    }
+}
+
+/// Copies the inspected container configuration into a create request.
+pub fn create_body(config: ContainerConfig) -> ContainerCreateBody {
+    ContainerCreateBody {
+        hostname: config.hostname,
+        domainname: config.domainname,
+        user: config.user,
+        attach_stdin: config.attach_stdin,
+        attach_stdout: config.attach_stdout,
+        attach_stderr: config.attach_stderr,
+        exposed_ports: config.exposed_ports,
+        tty: config.tty,
+        open_stdin: config.open_stdin,
+        stdin_once: config.stdin_once,
+        env: config.env,
+        cmd: config.cmd,
+        healthcheck: config.healthcheck,
+        args_escaped: config.args_escaped,
+        image: config.image,
+        volumes: config.volumes,
+        working_dir: config.working_dir,
+        entrypoint: config.entrypoint,
+        network_disabled: config.network_disabled,
+        mac_address: config.mac_address,
+        on_build: config.on_build,
+        labels: config.labels,
+        stop_signal: config.stop_signal,
+        stop_timeout: config.stop_timeout,
+        shell: config.shell,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use bollard::secret::{ContainerCreateBody, HostConfig, PortBinding, RestartPolicy, RestartPolicyNameEnum};
+
+use crate::utils::expand_home;
+
+/// Settings collected from the run form, using the same syntax as `docker run`.
+#[derive(Clone, Debug, Default)]
+pub struct RunConfig {
+    pub image: String,
+    pub name: String,
+    pub cmd: Vec<String>,
+    pub env: Vec<String>,
+    pub ports: Vec<PortMapping>,
+    pub binds: Vec<String>,
+    pub network: String,
+    pub restart_policy: String,
+    pub interactive: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct PortMapping {
+    host_ip: String,
+    host_port: String,
+    container_port: String,
+}
+
+impl fmt::Display for PortMapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", format_port(&self.host_ip, &self.host_port, &self.container_port))
+    }
+}
+
+impl RunConfig {
+    pub fn into_container_config(self) -> ContainerCreateBody {
+        let exposed_ports = self.ports.iter()
+            .map(|p| (p.container_port.clone(), HashMap::new()))
+            .collect::<HashMap<String, HashMap<(), ()>>>();
+
+        let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
+        for port in &self.ports {
+            let binding = PortBinding {
+                host_ip: Some(port.host_ip.clone()).filter(|ip| !ip.is_empty()),
+                host_port: Some(port.host_port.clone()),
+            };
+            port_bindings.entry(port.container_port.clone()).or_default().get_or_insert_default().push(binding);
+        }
+
+        let restart_policy = RestartPolicy {
+            name: RestartPolicyNameEnum::from_str(&self.restart_policy).ok(),
+            maximum_retry_count: None,
+        };
+
+        let host_config = HostConfig {
+            binds: Some(self.binds).filter(|b| !b.is_empty()),
+            port_bindings: Some(port_bindings).filter(|p| !p.is_empty()),
+            network_mode: Some(self.network).filter(|n| !n.is_empty()),
+            restart_policy: Some(restart_policy),
+            ..Default::default()
+        };
+
+        ContainerCreateBody {
+            image: Some(self.image),
+            cmd: Some(self.cmd).filter(|c| !c.is_empty()),
+            env: Some(self.env).filter(|e| !e.is_empty()),
+            exposed_ports: Some(exposed_ports).filter(|p| !p.is_empty()),
+            tty: Some(self.interactive),
+            open_stdin: Some(self.interactive),
+            attach_stdin: Some(self.interactive),
+            host_config: Some(host_config),
+            ..Default::default()
+        }
+    }
+}
+
+/// Parses `[ip:][host_port:]container_port[/protocol]`.
+pub fn parse_port(value: &str) -> Result<PortMapping, String> {
+    let (ports, protocol) = value.split_once('/').unwrap_or((value, "tcp"));
+    if !["tcp", "udp", "sctp"].contains(&protocol) {
+        return Err(format!("Invalid protocol in port mapping: {value}"));
+    }
+
+    let mut parts = ports.rsplitn(3, ':');
+    let container_port = parts.next().unwrap_or_default();
+    let host_port = parts.next().unwrap_or_default();
+    let host_ip = parts.next().unwrap_or_default();
+
+    let is_valid_port = |port: &str| port.parse::<u16>().is_ok_and(|p| p != 0);
+    if !is_valid_port(container_port) || !(host_port.is_empty() || is_valid_port(host_port)) {
+        return Err(format!("Invalid port mapping: {value}"));
+    }
+
+    Ok(PortMapping {
+        host_ip: host_ip.to_string(),
+        host_port: host_port.to_string(),
+        container_port: format!("{container_port}/{protocol}"),
+    })
+}
+
+/// Formats a port binding the way [`parse_port`] reads it. The wildcard host IP is left out, as
+/// the daemon reports it for bindings that were created without one.
+pub fn format_port(host_ip: &str, host_port: &str, container_port: &str) -> String {
+    match (host_ip, host_port) {
+        (_, "") => container_port.to_string(),
+        ("" | "0.0.0.0" | "::", port) => format!("{port}:{container_port}"),
+        (ip, port) => format!("{ip}:{port}:{container_port}"),
+    }
+}
+
+/// Parses `source:target[:mode]`, where source is a volume name or a host path.
+pub fn parse_bind(value: &str) -> Result<String, String> {
+    let parts = value.split(':').collect::<Vec<&str>>();
+    let (source, target, mode) = match parts.as_slice() {
+        [source, target] => (*source, *target, None),
+        [source, target, mode] => (*source, *target, Some(*mode)),
+        _ => return Err(format!("Invalid mount: {value}")),
+    };
+
+    if source.is_empty() || !target.starts_with('/') {
+        return Err(format!("Invalid mount: {value}"));
+    }
+
+    // Host paths must be absolute for the daemon, volume names are passed as they are.
+    let source = if source.starts_with(['.', '~', '/']) {
+        std::path::absolute(expand_home(source))
+            .map_err(|e| format!("Invalid mount source {source}: {e}"))?
+            .to_string_lossy()
+            .to_string()
+    } else {
+        source.to_string()
+    };
+
+    Ok(match mode {
+        Some(mode) => format!("{source}:{target}:{mode}"),
+        None => format!("{source}:{target}"),
+    })
+}
+
+/// Parses `KEY=VALUE`, or `KEY` to pass the variable without a value.
+pub fn parse_env(value: &str) -> Result<String, String> {
+    let key = value.split('=').next().unwrap_or_default();
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return Err(format!("Invalid env variable: {value}"));
+    }
+    Ok(value.to_string())
+}
+
+/// Splits a command line into arguments, honoring single and double quotes.
+pub fn split_command(value: &str) -> Result<Vec<String>, String> {
+    let mut args = vec![];
+    let mut current = String::new();
+    let mut quote = None;
+    let mut has_arg = false;
+
+    for c in value.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                has_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if has_arg {
+                    args.push(std::mem::take(&mut current));
+                    has_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                has_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+
+    if has_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn port(host_ip: &str, host_port: &str, container_port: &str) -> (String, String, String) {
+        (host_ip.to_string(), host_port.to_string(), container_port.to_string())
+    }
+
+    fn parsed_port(value: &str) -> (String, String, String) {
+        let mapping = parse_port(value).unwrap();
+        (mapping.host_ip, mapping.host_port, mapping.container_port)
+    }
+
+    #[test]
+    fn parse_port_reads_every_form() {
+        assert_eq!(parsed_port("80"), port("", "", "80/tcp"));
+        assert_eq!(parsed_port("8080:80"), port("", "8080", "80/tcp"));
+        assert_eq!(parsed_port("127.0.0.1:8080:80"), port("127.0.0.1", "8080", "80/tcp"));
+        assert_eq!(parsed_port("127.0.0.1::80"), port("127.0.0.1", "", "80/tcp"));
+        assert_eq!(parsed_port("53:53/udp"), port("", "53", "53/udp"));
+    }
+
+    #[test]
+    fn parse_port_rejects_invalid_mappings() {
+        assert!(parse_port("").is_err());
+        assert!(parse_port("0").is_err());
+        assert!(parse_port("70000").is_err());
+        assert!(parse_port("8080:http").is_err());
+        assert!(parse_port("x:80").is_err());
+        assert!(parse_port("80/icmp").is_err());
+    }
+
+    #[test]
+    fn parse_bind_keeps_volume_names() {
+        assert_eq!(parse_bind("data:/var/lib/data"), Ok("data:/var/lib/data".to_string()));
+        assert_eq!(parse_bind("data:/var/lib/data:ro"), Ok("data:/var/lib/data:ro".to_string()));
+    }
+
+    #[test]
+    fn parse_bind_makes_host_paths_absolute() {
+        let bind = parse_bind("./conf:/etc/app:ro").unwrap();
+        let cwd = std::env::current_dir().unwrap();
+        assert!(bind.starts_with(&cwd.to_string_lossy().to_string()));
+        assert!(bind.ends_with("conf:/etc/app:ro"));
+        assert_eq!(parse_bind("/srv/conf:/etc/app"), Ok("/srv/conf:/etc/app".to_string()));
+    }
+
+    #[test]
+    fn parse_bind_rejects_invalid_mounts() {
+        assert!(parse_bind("data").is_err());
+        assert!(parse_bind(":/data").is_err());
+        assert!(parse_bind("data:relative").is_err());
+        assert!(parse_bind("data:/data:ro:extra").is_err());
+    }
+
+    #[test]
+    fn parse_env_accepts_keys_with_or_without_values() {
+        assert_eq!(parse_env("KEY=a=b"), Ok("KEY=a=b".to_string()));
+        assert_eq!(parse_env("KEY"), Ok("KEY".to_string()));
+        assert!(parse_env("=value").is_err());
+        assert!(parse_env("MY KEY=value").is_err());
+    }
+
+    #[test]
+    fn split_command_honors_quotes() {
+        assert_eq!(split_command("sh -c 'echo hi'"), Ok(vec!["sh".to_string(), "-c".to_string(), "echo hi".to_string()]));
+        assert_eq!(split_command(r#"KEY="a b" OTHER=c"#), Ok(vec!["KEY=a b".to_string(), "OTHER=c".to_string()]));
+        assert_eq!(split_command(r#"echo "it's""#), Ok(vec!["echo".to_string(), "it's".to_string()]));
+    }
+
+    #[test]
+    fn split_command_keeps_empty_quoted_args() {
+        assert_eq!(split_command("echo ''  x"), Ok(vec!["echo".to_string(), String::new(), "x".to_string()]));
+        assert_eq!(split_command("   "), Ok(vec![]));
+    }
+
+    #[test]
+    fn split_command_rejects_unterminated_quotes() {
+        assert!(split_command("echo 'hi").is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! Swarm configs, whose endpoints bollard doesn't cover. The requests are made over the daemon's
+//! unix socket, the same one bollard connects to by default.
+
+use bollard::secret::{Config, ConfigSpec};
+use color_eyre::eyre::{Result, eyre};
+
+const DEFAULT_SOCKET: &str = "/var/run/docker.sock";
+
+pub async fn list_configs(api_version: &str) -> Result<Vec<Config>> {
+    let response = request(api_version, "GET", "/configs", None).await?;
+    serde_json::from_str(&response).map_err(unexpected_response)
+}
+
+/// Creates the config and returns its ID.
+pub async fn create_config(api_version: &str, spec: &ConfigSpec) -> Result<String> {
+    let body = serde_json::to_string(spec)?;
+    let response = request(api_version, "POST", "/configs/create", Some(body)).await?;
+    let created = serde_json::from_str::<serde_json::Value>(&response).map_err(unexpected_response)?;
+    Ok(created["ID"].as_str().unwrap_or_default().to_string())
+}
+
+pub async fn remove_config(api_version: &str, id: &str) -> Result<()> {
+    request(api_version, "DELETE", &format!("/configs/{id}"), None).await?;
+    Ok(())
+}
+
+/// Whether configs can be reached, which takes the daemon's unix socket.
+pub fn is_supported() -> bool {
+    cfg!(unix) && socket_path().is_ok()
+}
+
+fn unexpected_response(e: serde_json::Error) -> color_eyre::Report {
+    eyre!("Unexpected response from the daemon: {e}")
+}
+
+/// The socket from `DOCKER_HOST` if it points to one, the default socket otherwise.
+fn socket_path() -> Result<String> {
+    match std::env::var("DOCKER_HOST") {
+        Ok(host) if host.starts_with("unix://") => Ok(host.trim_start_matches("unix://").to_string()),
+        Ok(host) if !host.is_empty() => Err(eyre!("Configs are only supported over a unix socket, not {host}")),
+        _ => Ok(DEFAULT_SOCKET.to_string()),
+    }
+}
+
+/// Sends an HTTP/1.0 request, so the daemon answers with a plain body and closes the connection
+/// instead of chunking the response. Error responses are turned into their message.
+#[cfg(unix)]
+async fn request(api_version: &str, method: &str, path: &str, body: Option<String>) -> Result<String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::UnixStream::connect(socket_path()?).await?;
+    let body = body.unwrap_or_default();
+    let request = format!(
+        "{method} /v{api_version}{path} HTTP/1.0\r\nHost: docker\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response).await?;
+    let response = String::from_utf8_lossy(&response);
+
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(|| eyre!("Malformed response from the daemon"))?;
+    let status = head.split_whitespace().nth(1).and_then(|s| s.parse::<u16>().ok()).unwrap_or_default();
+
+    if !(200..300).contains(&status) {
+        let message = serde_json::from_str::<serde_json::Value>(body).ok()
+            .and_then(|v| v["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| format!("Request failed with status {status}"));
+        return Err(eyre!(message));
+    }
+
+    Ok(body.to_string())
+}
+
+#[cfg(not(unix))]
+async fn request(_api_version: &str, _method: &str, _path: &str, _body: Option<String>) -> Result<String> {
+    socket_path()?;
+    Err(eyre!("Configs are only supported over a unix socket"))
+}
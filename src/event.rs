@@ -1,3 +1,12 @@
+use crate::docker::run_config::RunConfig;
+use bollard::query_parameters::CommitContainerOptions;
+use bollard::secret::{ContainerUpdateBody, NetworkCreateRequest, NodeSpecAvailabilityEnum, VolumeCreateOptions};
+use crate::ui::compose_view::ComposeAction;
+use crate::ui::container_table::GroupAction;
+use crate::ui::dependents_panel::DependencyTarget;
+use crate::ui::network_form::NetworkConnection;
+use crate::ui::swarm_object_table::{NewSwarmObject, SwarmObjectKind};
+use crate::ui::system_view::PruneTarget;
 use color_eyre::eyre::{OptionExt, Result};
 use crossterm::event::KeyEventKind;
 use futures::{FutureExt, StreamExt};
@@ -23,12 +32,65 @@ pub enum AppEvent {
     StopContainer(String),
     KillContainer(String),
     RemoveContainer(String),
+    ContainerGroupAction(GroupAction, String, Vec<String>),
     GoToContainerDetails(String),
+    CommitContainer(Box<CommitContainerOptions>),
+    ContainerCommitted(String, String),
+    ExportContainer(String, String),
+    RenameContainer(String, String),
+    UpdateContainer(String, Box<ContainerUpdateBody>),
+    ShowComposeProject(String),
+    UpdateComposeProject,
+    ComposeAction(ComposeAction),
     UpdateVolumes,
+    UpdateVolumeInfo(String),
+    GoToVolumeDetails(String),
+    CreateVolume(Box<VolumeCreateOptions>),
+    BrowseVolume(String),
+    ListDirectory(String, String),
+    ReadFile(String, String),
+    DownloadPath(String, String, String),
+    UploadPath(String, String, String),
+    BrowseContainer(String),
+    CloseFileBrowser,
+    BackupVolume(String, String),
+    RestoreVolume(String, String, bool),
+    CloneVolume(String, String, bool, bool),
     RemoveVolume(String, bool),
     UpdateNetworks,
+    CreateNetwork(Box<NetworkCreateRequest>),
+    ConnectNetwork(NetworkConnection),
+    DisconnectNetwork(String, String, bool),
+    UpdateNetworkInfo(String),
+    ShowNetworkTopology,
+    UpdateNetworkTopology,
+    GoToNetworkDetails(String),
     RemoveNetwork(String),
     UpdateImages,
+    UpdateImageUsage,
+    PreviewImagePrune,
+    PruneImages(bool),
+    UpdateServices,
+    ShowServiceTasks(String),
+    ScaleService(String, i64),
+    UpdateServiceImage(String, String),
+    RollbackService(String),
+    UpdateTasks,
+    UpdateNodes,
+    SetNodeAvailability(String, NodeSpecAvailabilityEnum),
+    UpdateSwarmObjects(SwarmObjectKind),
+    CreateSwarmObject(SwarmObjectKind, Box<NewSwarmObject>),
+    RemoveSwarmObject(SwarmObjectKind, String),
+    UpdateSystem,
+    ShowDaemonInfo,
+    UpdateDaemonInfo,
+    SystemPrune(PruneTarget),
+    SaveImages(Vec<String>, String),
+    LoadImages(String),
+    RunContainer(RunConfig),
+    ShowDependents(DependencyTarget),
+    TransferProgress(u64),
+    TransferFinished(Result<String, String>),
     RemoveImage(String, bool),
     Back,
 }
@@ -55,6 +117,20 @@ impl EventHandler {
     pub fn send(&mut self, app_event: AppEvent) {
         let _ = self.sender.send(Event::App(app_event));
     }
+
+    pub fn sender(&self) -> AppEventSender {
+        AppEventSender(self.sender.clone())
+    }
+}
+
+/// Cloneable handle for sending app events from background tasks.
+#[derive(Clone, Debug)]
+pub struct AppEventSender(mpsc::UnboundedSender<Event>);
+
+impl AppEventSender {
+    pub fn send(&self, app_event: AppEvent) {
+        let _ = self.0.send(Event::App(app_event));
+    }
 }
 
 struct EventTask {
@@ -78,10 +154,9 @@ impl EventTask {
                 _ = self.sender.closed() => break,
                 _ = tick_delay => self.send(Event::Tick),
                 Some(Ok(event)) = crossterm_event => {
-                    if let Key(key) = event {
-                        if key.kind == KeyEventKind::Press {
-                            self.send(Event::Crossterm(key))
-                        }
+                    if let Key(key) = event
+                        && key.kind == KeyEventKind::Press {
+                        self.send(Event::Crossterm(key))
                     }
                 }
             };
@@ -11,7 +11,7 @@ use color_eyre::eyre::Result;
 async fn main() -> Result<()> {
     color_eyre::install()?;
     let terminal = ratatui::init();
-    let app = App::new()?;
+    let app = App::new().await?;
     let result = app.run(terminal).await;
     ratatui::restore();
     result
@@ -1,6 +1,6 @@
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Flex, Layout, Rect},
     style::{Modifier, Style, palette::tailwind},
     widgets::{Block, BorderType, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
 };
@@ -50,6 +50,20 @@ pub fn render_scrollbar(frame: &mut Frame, area: Rect, state: &mut ScrollbarStat
     frame.render_stateful_widget(scrollbar, area, state);
 }
 
+pub enum PopupAction {
+    None,
+    Close,
+    Submit,
+}
+
+pub fn popup_area(area: Rect, width: Constraint, height: Constraint) -> Rect {
+    let vertical = Layout::vertical([height]).flex(Flex::Center);
+    let horizontal = Layout::horizontal([width]).flex(Flex::Center);
+    let [area] = vertical.areas(area);
+    let [area] = horizontal.areas(area);
+    area
+}
+
 pub fn render_footer(frame: &mut Frame, area: Rect, text: String, border_style: Option<Style>) {
     let paragraph_style = Style::default().fg(tailwind::SLATE.c200);
 
@@ -96,3 +110,33 @@ pub fn time_ago_string(epoch_secs: i64) -> String {
         format!("in {value} {unit}{plural}")
     }
 }
+
+pub fn size_string(bytes: i64) -> String {
+    if bytes < 0 {
+        return "-".to_string();
+    }
+
+    let units = ["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit_index = 0;
+
+    while size >= 1000.0 && unit_index < units.len() - 1 {
+        size /= 1000.0;
+        unit_index += 1;
+    }
+
+    if unit_index == 0 {
+        format!("{bytes} {}", units[unit_index])
+    } else {
+        format!("{size:.2} {}", units[unit_index])
+    }
+}
+
+/// Shortens an RFC 3339 timestamp from the API to `YYYY-MM-DD HH:MM:SS`.
+pub fn date_string(timestamp: &str) -> String {
+    match timestamp.get(..19) {
+        Some(date) => date.replacen('T', " ", 1),
+        None if timestamp.is_empty() => "-".to_string(),
+        None => timestamp.to_string(),
+    }
+}
@@ -0,0 +1,370 @@
+use bollard::secret::ContainerInspectResponse;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph},
+};
+
+use crate::docker::compose::{ComposeProject, ComposeSource, SERVICE_LABEL};
+use crate::event::AppEvent;
+use crate::utils::is_container_running;
+
+use super::common::{PopupAction, render_footer};
+use super::confirm_popup::ConfirmPopup;
+
+const REFRESH_AFTER_TICK: u8 = 30;
+const DEFAULT_FOOTER: &str = " <Esc/Q> back | <U> up | <P> pull | <R> recreate service | <D> down";
+
+#[derive(Clone, Debug)]
+pub enum ComposeAction {
+    Up,
+    Down,
+    Pull,
+    Recreate(String),
+}
+
+/// A compose project next to its files: the services with their containers and how the running
+/// containers drifted from the file.
+pub struct ComposeView {
+    project_name: String,
+    source: Option<ComposeSource>,
+    project: Option<ComposeProject>,
+    load_err: Option<String>,
+    services: Vec<ServiceStatus>,
+    selected: usize,
+    confirm: Option<(ComposeAction, ConfirmPopup)>,
+    err: Option<String>,
+    skipped_tick_count_for_refresh: u8,
+}
+
+pub struct ServiceStatus {
+    name: String,
+    image: String,
+    containers: Vec<(String, String)>,
+    drift: Vec<String>,
+    is_in_file: bool,
+}
+
+impl ServiceStatus {
+    /// Matches the containers to the services of the file by their service label. Containers of
+    /// services that are no longer in the file are listed last.
+    pub fn from_list(project: Option<&ComposeProject>, containers: &[ContainerInspectResponse]) -> Vec<Self> {
+        let service_of = |c: &ContainerInspectResponse| {
+            c.config.as_ref()?.labels.as_ref()?.get(SERVICE_LABEL).cloned()
+        };
+        let status_of = |c: &ContainerInspectResponse| {
+            let name = c.name.clone().unwrap_or_default().trim_start_matches('/').to_string();
+            let state = c.state.as_ref().and_then(|s| s.status).map_or("-".to_string(), |s| s.to_string());
+            (name, state)
+        };
+
+        let mut services = project.map_or(vec![], |project| {
+            project.services.values()
+                .map(|service| {
+                    let own = containers.iter()
+                        .filter(|c| service_of(c).as_ref() == Some(&service.name))
+                        .collect::<Vec<&ContainerInspectResponse>>();
+
+                    let mut drift = vec![];
+                    for line in own.iter().flat_map(|c| service.drift(project, c)) {
+                        if !drift.contains(&line) {
+                            drift.push(line);
+                        }
+                    }
+
+                    Self {
+                        name: service.name.clone(),
+                        image: service.image_name(&project.name),
+                        containers: own.into_iter().map(status_of).collect(),
+                        drift,
+                        is_in_file: true,
+                    }
+                })
+                .collect::<Vec<Self>>()
+        });
+
+        for container in containers {
+            let name = service_of(container).unwrap_or_default();
+            if services.iter().any(|s| s.name == name && s.is_in_file) {
+                continue;
+            }
+
+            match services.iter_mut().find(|s| s.name == name) {
+                Some(orphan) => orphan.containers.push(status_of(container)),
+                None => services.push(Self {
+                    name,
+                    image: container.config.as_ref().and_then(|c| c.image.clone()).unwrap_or_default(),
+                    containers: vec![status_of(container)],
+                    drift: vec![],
+                    is_in_file: false,
+                }),
+            }
+        }
+
+        services
+    }
+}
+
+impl ComposeView {
+    pub fn new(project_name: String) -> Self {
+        Self {
+            project_name,
+            source: None,
+            project: None,
+            load_err: None,
+            services: vec![],
+            selected: 0,
+            confirm: None,
+            err: None,
+            skipped_tick_count_for_refresh: 0,
+        }
+    }
+
+    pub fn project_name(&self) -> &str {
+        &self.project_name
+    }
+
+    pub fn project(&self) -> Option<&ComposeProject> {
+        self.project.as_ref()
+    }
+
+    /// Reloads the files and matches them with `containers`. The source found in the container
+    /// labels is remembered, so the project can still be brought up after it was taken down.
+    pub fn update(&mut self, source: Option<ComposeSource>, containers: Vec<ContainerInspectResponse>) {
+        if source.is_some() {
+            self.source = source;
+        }
+
+        match self.source.as_ref().map(|source| ComposeProject::load(&self.project_name, source)) {
+            Some(Ok(project)) => {
+                self.project = Some(project);
+                self.load_err = None;
+            }
+            Some(Err(e)) => {
+                self.project = None;
+                self.load_err = Some(e.to_string());
+            }
+            None => {
+                self.project = None;
+                self.load_err = Some("The containers don't tell where the compose files are".to_string());
+            }
+        }
+
+        self.services = ServiceStatus::from_list(self.project.as_ref(), &containers);
+        self.selected = self.selected.min(self.services.len().saturating_sub(1));
+    }
+
+    pub fn show_err(&mut self, msg: &str) {
+        self.err = Some(format!("[ERR] {msg}"));
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        if let Some((_, popup)) = self.confirm.as_mut() {
+            return match popup.handle_key_event(key_event) {
+                PopupAction::None => None,
+                PopupAction::Close => {
+                    self.confirm = None;
+                    None
+                }
+                PopupAction::Submit => self.confirm.take().map(|(action, _)| AppEvent::ComposeAction(action)),
+            };
+        }
+
+        if self.err.take().is_some() {
+            return None;
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(AppEvent::Back),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.services.len().saturating_sub(1));
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                None
+            }
+            KeyCode::Char('u') => {
+                let drifted = self.services.iter().filter(|s| s.is_in_file && !s.drift.is_empty()).collect::<Vec<_>>();
+                let items = container_items(&drifted);
+                let title = format!("Recreate the drifted containers of {}", self.project_name);
+                self.confirm_project_action(ComposeAction::Up, title, items, "up")
+            }
+            KeyCode::Char('p') => self.project_action(ComposeAction::Pull),
+            KeyCode::Char('r') => match self.services.get(self.selected) {
+                Some(service) if service.is_in_file => {
+                    let items = container_items(&[service]);
+                    let title = format!("Recreate the containers of {}", service.name);
+                    let action = ComposeAction::Recreate(service.name.clone());
+                    self.confirm_project_action(action, title, items, "recreate")
+                }
+                Some(service) => {
+                    let msg = format!("{} is not in the compose file anymore", service.name);
+                    self.show_err(&msg);
+                    None
+                }
+                None => None,
+            },
+            KeyCode::Delete | KeyCode::Char('d') => {
+                self.open_down_preview();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Actions that create containers need the files, so they are refused while those can't be read.
+    fn project_action(&mut self, action: ComposeAction) -> Option<AppEvent> {
+        if let Some(err) = self.load_err.clone() {
+            self.show_err(&err);
+            return None;
+        }
+        Some(AppEvent::ComposeAction(action))
+    }
+
+    /// Asks to confirm `action` when it replaces the containers in `items`. Actions that only
+    /// create or start containers run right away.
+    fn confirm_project_action(&mut self, action: ComposeAction, title: String, items: Vec<String>, hint: &str) -> Option<AppEvent> {
+        if items.is_empty() {
+            return self.project_action(action);
+        }
+
+        if let Some(err) = self.load_err.clone() {
+            self.show_err(&err);
+            return None;
+        }
+        self.confirm = Some((action, ConfirmPopup::new(title, items, hint)));
+        None
+    }
+
+    fn open_down_preview(&mut self) {
+        let items = container_items(&self.services.iter().collect::<Vec<_>>());
+        let title = format!("Stop and remove the containers and networks of {}", self.project_name);
+        self.confirm = Some((ComposeAction::Down, ConfirmPopup::new(title, items, "down")));
+    }
+
+    pub fn tick(&mut self) -> Option<AppEvent> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return None;
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Some(AppEvent::UpdateComposeProject)
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        use Constraint::{Length, Min};
+        let [summary_area, services_area, footer_area] = Layout::vertical([Length(5), Min(0), Length(3)]).areas(area);
+
+        self.render_summary(frame, summary_area);
+        self.render_services(frame, services_area);
+
+        let (footer_text, border_style) = match &self.err {
+            Some(err) => (err.clone(), Some(Style::new().red())),
+            None => (DEFAULT_FOOTER.to_string(), None),
+        };
+        render_footer(frame, footer_area, footer_text, border_style);
+
+        if let Some((_, popup)) = &self.confirm {
+            popup.draw(frame, area);
+        }
+    }
+
+    fn render_summary(&self, frame: &mut Frame, area: Rect) {
+        let key = |text: &str| text.to_string().fg(tailwind::BLUE.c400);
+        let (working_dir, files) = self.source.as_ref()
+            .map_or(("-".to_string(), "-".to_string()), |s| (s.working_dir.clone(), s.config_files.join(", ")));
+
+        let drifted = self.services.iter().filter(|s| !s.drift.is_empty()).count();
+        let status = match &self.load_err {
+            Some(err) => Line::from_iter([key(" Status: "), err.clone().fg(tailwind::RED.c400)]),
+            None if drifted > 0 => Line::from_iter([
+                key(" Status: "),
+                format!("{drifted} of {} service(s) drifted from the file", self.services.len()).fg(tailwind::AMBER.c400),
+            ]),
+            None => Line::from_iter([
+                key(" Status: "),
+                format!("{} service(s) in sync with the file", self.services.len()).fg(tailwind::GREEN.c400),
+            ]),
+        };
+
+        let lines = vec![
+            Line::from_iter([key(" Working dir: "), working_dir.into()]),
+            Line::from_iter([key(" Files: "), files.into()]),
+            status,
+        ];
+
+        frame.render_widget(Paragraph::new(lines).block(bordered_block(format!("Compose: {}", self.project_name))), area);
+    }
+
+    fn render_services(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = vec![];
+        let mut selected_line = 0;
+
+        for (index, service) in self.services.iter().enumerate() {
+            let is_selected = index == self.selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+
+            let running = service.containers.iter().filter(|(_, state)| is_container_running(state)).count();
+            let marker = if is_selected { " ● " } else { "   " };
+            let mut header = Line::from_iter([
+                marker.to_string().fg(tailwind::BLUE.c400),
+                service.name.clone().bold(),
+                format!("  {}  ", service.image).fg(tailwind::SLATE.c400),
+                format!("{running}/{} running", service.containers.len()).into(),
+            ]);
+            if is_selected {
+                header = header.style(Style::new().add_modifier(Modifier::REVERSED));
+            }
+            lines.push(header);
+
+            for (name, state) in &service.containers {
+                lines.push(Line::from(format!("     {name} ({state})")).fg(tailwind::SLATE.c400));
+            }
+
+            let details = match (service.is_in_file, service.containers.is_empty(), service.drift.is_empty()) {
+                (false, _, _) => vec![Line::from("     not in the compose file anymore").fg(tailwind::RED.c400)],
+                (true, true, _) => vec![Line::from("     not created").fg(tailwind::SLATE.c400)],
+                (true, false, true) => vec![Line::from("     in sync").fg(tailwind::GREEN.c400)],
+                (true, false, false) => service.drift.iter()
+                    .map(|drift| Line::from(format!("     {drift}")).fg(tailwind::AMBER.c400))
+                    .collect(),
+            };
+            lines.extend(details);
+            lines.push(Line::default());
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::from(" No services").fg(tailwind::SLATE.c400));
+        }
+
+        // Keeps the selected service in view, with its details if they fit.
+        let height = area.height.saturating_sub(2) as usize;
+        let scroll = (selected_line + 3).saturating_sub(height).min(selected_line);
+
+        let paragraph = Paragraph::new(lines)
+            .style(Style::new().fg(tailwind::SLATE.c200))
+            .scroll((scroll as u16, 0))
+            .block(bordered_block("Services".to_string()));
+        frame.render_widget(paragraph, area);
+    }
+}
+
+fn container_items(services: &[&ServiceStatus]) -> Vec<String> {
+    services.iter()
+        .flat_map(|service| service.containers.iter().map(|(name, state)| format!("{name} ({state})")))
+        .collect()
+}
+
+fn bordered_block(title: String) -> Block<'static> {
+    Block::bordered()
+        .border_type(BorderType::Plain)
+        .border_style(Style::new().fg(tailwind::BLUE.c400))
+        .title(Line::from(title).fg(tailwind::SLATE.c200))
+}
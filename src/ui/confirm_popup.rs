@@ -0,0 +1,74 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph},
+};
+
+use super::common::{PopupAction, popup_area};
+
+/// Asks to confirm an action, listing what it applies to.
+pub struct ConfirmPopup {
+    title: String,
+    items: Vec<String>,
+    hint: String,
+    scroll: usize,
+}
+
+impl ConfirmPopup {
+    /// `action` names the action in the hint, e.g. `remove` for " <Ent/Y> remove | <Esc/Q> cancel".
+    pub fn new(title: String, items: Vec<String>, action: &str) -> Self {
+        Self {
+            title,
+            items,
+            hint: format!(" <Ent/Y> {action} | <Esc/Q> cancel"),
+            scroll: 0,
+        }
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> PopupAction {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Char('y') => PopupAction::Submit,
+            KeyCode::Esc | KeyCode::Char('q' | 'n') => PopupAction::Close,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = (self.scroll + 1).min(self.items.len().saturating_sub(1));
+                PopupAction::None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = self.scroll.saturating_sub(1);
+                PopupAction::None
+            }
+            _ => PopupAction::None,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        use Constraint::{Length, Min};
+
+        // Two lines for the borders, one for the blank line and one for the hint.
+        let height = (self.items.len() + 4) as u16;
+        let area = popup_area(area, Constraint::Percentage(70), Length(height.min(area.height)));
+        frame.render_widget(Clear, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(tailwind::AMBER.c400))
+            .title(Line::from(format!(" {} ", self.title)).fg(tailwind::SLATE.c200));
+
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [list_area, hint_area] = Layout::vertical([Min(0), Length(2)]).areas(inner_area);
+
+        let lines = self.items.iter().map(|item| Line::from(format!(" - {item}"))).collect::<Vec<_>>();
+        let list = Paragraph::new(lines)
+            .style(Style::new().fg(tailwind::SLATE.c200))
+            .scroll((self.scroll as u16, 0));
+        frame.render_widget(list, list_area);
+
+        let hint = vec![Line::default(), Line::from(self.hint.clone()).fg(tailwind::SLATE.c400)];
+        frame.render_widget(Paragraph::new(hint), hint_area);
+    }
+}
@@ -0,0 +1,410 @@
+use std::str::FromStr;
+
+use bollard::query_parameters::CommitContainerOptions;
+use bollard::secret::{ContainerUpdateBody, HostConfig, RestartPolicy, RestartPolicyNameEnum};
+
+use crate::{
+    docker::archive::ARCHIVE_EXTENSIONS,
+    utils::{expand_home, is_valid_name, memory_string, parse_key_values, parse_memory},
+};
+
+use super::input_form::InputForm;
+use super::run_form::RESTART_POLICIES;
+
+const PAUSE_MODES: [&str; 2] = ["pause", "keep running"];
+const PROTOCOLS: [&str; 3] = ["tcp", "udp", "sctp"];
+
+const NANO_CPUS: f64 = 1e9;
+const CFS_PERIOD: i64 = 100_000;
+const DEFAULT_CPU_SHARES: i64 = 1024;
+const MIN_MEMORY: i64 = 6 << 20;
+
+/// The settings of a container that the update endpoint can change while it runs. Limits of 0
+/// mean unlimited, or the default weight for CPU shares.
+#[derive(Default, Clone, PartialEq)]
+pub struct ContainerSettings {
+    restart_policy: String,
+    max_retries: i64,
+    cpu_shares: i64,
+    /// The CPU limit in billionths of a CPU, whether set as `NanoCpus` or as a CFS quota.
+    nano_cpus: i64,
+    uses_cfs_quota: bool,
+    memory: i64,
+    memory_swap: i64,
+    pids_limit: i64,
+}
+
+impl ContainerSettings {
+    pub fn from(config: &HostConfig) -> Self {
+        let restart_policy = config.restart_policy.as_ref()
+            .and_then(|p| p.name)
+            .map(|name| name.to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| RESTART_POLICIES[0].to_string());
+
+        let cpu_quota = config.cpu_quota.unwrap_or_default();
+        let cpu_period = config.cpu_period.filter(|p| *p > 0).unwrap_or(CFS_PERIOD);
+        let nano_cpus = match cpu_quota > 0 {
+            true => cpu_quota * NANO_CPUS as i64 / cpu_period,
+            false => config.nano_cpus.unwrap_or_default(),
+        };
+
+        Self {
+            restart_policy,
+            max_retries: config.restart_policy.as_ref().and_then(|p| p.maximum_retry_count).unwrap_or_default(),
+            cpu_shares: config.cpu_shares.unwrap_or_default(),
+            nano_cpus,
+            uses_cfs_quota: cpu_quota > 0,
+            memory: config.memory.unwrap_or_default(),
+            memory_swap: config.memory_swap.unwrap_or_default(),
+            pids_limit: config.pids_limit.filter(|l| *l > 0).unwrap_or_default(),
+        }
+    }
+
+    /// The limits that are set, or `-` when there are none.
+    pub fn limits_text(&self) -> String {
+        let limits = [
+            (self.cpu_shares > 0).then(|| format!("CPU shares {}", self.cpu_shares)),
+            (self.nano_cpus > 0).then(|| format!("{} CPUs", cpus_string(self.nano_cpus))),
+            (self.memory > 0).then(|| format!("memory {}", memory_string(self.memory))),
+            (self.pids_limit > 0).then(|| format!("{} pids", self.pids_limit)),
+        ];
+
+        let limits = limits.into_iter().flatten().collect::<Vec<String>>();
+        if limits.is_empty() { "-".to_string() } else { limits.join(", ") }
+    }
+
+    /// One `label: before -> after` line per setting that differs from `before`.
+    pub fn changes_from(&self, before: &Self) -> Vec<String> {
+        before.summary().into_iter()
+            .zip(self.summary())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((label, old), (_, new))| format!("{label}: {old} -> {new}"))
+            .collect()
+    }
+
+    /// The update request for the settings that differ from `before`. The daemon ignores zero
+    /// values, so a removed PIDs limit is sent as -1. A memory limit can only be changed, as the
+    /// daemon takes -1 as a limit below its minimum, which [`parse_settings_form`] enforces.
+    pub fn update_body(&self, before: &Self) -> ContainerUpdateBody {
+        let changed = |new: i64, old: i64| (new != old).then_some(if new > 0 { new } else { -1 });
+        let mut update = ContainerUpdateBody {
+            memory: changed(self.memory, before.memory),
+            pids_limit: changed(self.pids_limit, before.pids_limit),
+            ..Default::default()
+        };
+
+        if self.restart_policy != before.restart_policy || self.max_retries != before.max_retries {
+            update.restart_policy = Some(RestartPolicy {
+                name: RestartPolicyNameEnum::from_str(&self.restart_policy).ok(),
+                maximum_retry_count: Some(self.max_retries).filter(|_| self.restart_policy == "on-failure"),
+            });
+        }
+
+        if self.cpu_shares != before.cpu_shares {
+            update.cpu_shares = Some(if self.cpu_shares > 0 { self.cpu_shares } else { DEFAULT_CPU_SHARES });
+        }
+
+        // A limit set with `--cpus` can only be changed as NanoCpus, the daemon rejects a quota.
+        if self.nano_cpus != before.nano_cpus {
+            if before.nano_cpus > 0 && !before.uses_cfs_quota {
+                update.nano_cpus = Some(self.nano_cpus);
+            } else {
+                update.cpu_period = Some(CFS_PERIOD);
+                update.cpu_quota = Some(match self.nano_cpus > 0 {
+                    true => self.nano_cpus * CFS_PERIOD / NANO_CPUS as i64,
+                    false => -1,
+                });
+            }
+        }
+
+        // A swap limit below the new memory limit would make the daemon refuse the update.
+        if update.memory.is_some() && before.memory_swap > 0 && self.memory > before.memory_swap {
+            update.memory_swap = Some(-1);
+        }
+
+        update
+    }
+
+    fn summary(&self) -> [(&'static str, String); 5] {
+        let or_unlimited = |value: i64, text: String| if value > 0 { text } else { "unlimited".to_string() };
+        let restart_policy = match self.restart_policy == "on-failure" && self.max_retries > 0 {
+            true => format!("{}:{}", self.restart_policy, self.max_retries),
+            false => self.restart_policy.clone(),
+        };
+
+        [
+            ("Restart policy", restart_policy),
+            ("CPU shares", if self.cpu_shares > 0 { self.cpu_shares.to_string() } else { "default".to_string() }),
+            ("CPUs", or_unlimited(self.nano_cpus, cpus_string(self.nano_cpus))),
+            ("Memory", or_unlimited(self.memory, memory_string(self.memory))),
+            ("PIDs limit", or_unlimited(self.pids_limit, self.pids_limit.to_string())),
+        ]
+    }
+}
+
+fn cpus_string(nano_cpus: i64) -> String {
+    let cpus = format!("{:.3}", nano_cpus as f64 / NANO_CPUS);
+    cpus.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+pub fn new_commit_form(container: &str) -> InputForm {
+    InputForm::new(&format!("Commit {container} to an image"))
+        .with_field("reference", "Repository[:tag]", "")
+        .with_field("author", "Author (optional)", "")
+        .with_field("message", "Message (optional)", "")
+        .with_field("cmd", "CMD (optional)", "")
+        .with_field("env", "ENV (KEY=value ...)", "")
+        .with_field("expose", "EXPOSE (80 53/udp ...)", "")
+        .with_choice("pause", "While committing", &PAUSE_MODES)
+}
+
+pub fn new_export_form(container: &str) -> InputForm {
+    InputForm::new(&format!("Export {container} filesystem"))
+        .with_field("path", "Archive path (.tar, .tar.gz)", &format!("./{container}.tar"))
+}
+
+pub fn parse_export_form(form: &InputForm) -> Result<String, String> {
+    let path = expand_home(&form.value("path"));
+    if !ARCHIVE_EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+        return Err("Path must end with .tar, .tar.gz or .tgz".to_string());
+    }
+    Ok(path)
+}
+
+pub fn new_rename_form(container: &str) -> InputForm {
+    InputForm::new(&format!("Rename {container}")).with_field("name", "Name", container)
+}
+
+/// Reads the new name, which has to be a valid container name other than the current one.
+pub fn parse_rename_form(form: &InputForm, container: &str) -> Result<String, String> {
+    let name = form.value("name");
+    if !is_valid_name(&name) {
+        return Err("Name must match [a-zA-Z0-9][a-zA-Z0-9_.-]+".to_string());
+    }
+    if name == container {
+        return Err("Name is unchanged".to_string());
+    }
+    Ok(name)
+}
+
+pub fn new_settings_form(container: &str, settings: &ContainerSettings) -> InputForm {
+    let or_empty = |value: i64, text: String| if value > 0 { text } else { String::new() };
+    let retries = or_empty(settings.max_retries, settings.max_retries.to_string());
+    let shares = or_empty(settings.cpu_shares, settings.cpu_shares.to_string());
+    let cpus = or_empty(settings.nano_cpus, cpus_string(settings.nano_cpus));
+    let memory = or_empty(settings.memory, memory_string(settings.memory));
+    let pids = or_empty(settings.pids_limit, settings.pids_limit.to_string());
+
+    InputForm::new(&format!("Update {container}"))
+        .with_choice_value("restart", "Restart policy", &RESTART_POLICIES, &settings.restart_policy)
+        .with_field("retries", "Max retries (on-failure)", &retries)
+        .with_field("shares", "CPU shares (empty for default)", &shares)
+        .with_field("cpus", "CPUs (e.g. 1.5, empty for no limit)", &cpus)
+        .with_field("memory", "Memory (e.g. 512m, 2g, empty for no limit)", &memory)
+        .with_field("pids", "PIDs limit (empty for no limit)", &pids)
+}
+
+/// Reads the settings from the form, keeping the parts of `before` that the form doesn't show.
+pub fn parse_settings_form(form: &InputForm, before: &ContainerSettings) -> Result<ContainerSettings, String> {
+    let restart_policy = form.value("restart");
+    let max_retries = parse_optional(&form.value("retries"), "max retries", 0..=i64::MAX)?;
+    if max_retries > 0 && restart_policy != "on-failure" {
+        return Err("Max retries only apply to the on-failure policy".to_string());
+    }
+
+    let cpus = form.value("cpus");
+    let nano_cpus = match cpus.parse::<f64>() {
+        _ if cpus.is_empty() => 0,
+        Ok(cpus) if cpus >= 0.01 && cpus.is_finite() => (cpus * NANO_CPUS).round() as i64,
+        _ => return Err(format!("Invalid CPUs: {cpus}, expected at least 0.01")),
+    };
+    if nano_cpus == 0 && before.nano_cpus > 0 && !before.uses_cfs_quota {
+        return Err("A CPU limit set with --cpus can be changed but not removed".to_string());
+    }
+
+    let memory = form.value("memory");
+    let memory = if memory.is_empty() { 0 } else { parse_memory(&memory)? };
+    if memory > 0 && memory < MIN_MEMORY {
+        return Err("Memory limit must be at least 6m".to_string());
+    }
+    if memory == 0 && before.memory > 0 {
+        return Err("A memory limit can be changed but not removed".to_string());
+    }
+
+    Ok(ContainerSettings {
+        restart_policy,
+        max_retries,
+        cpu_shares: parse_optional(&form.value("shares"), "CPU shares", 2..=262_144)?,
+        nano_cpus,
+        memory,
+        pids_limit: parse_optional(&form.value("pids"), "PIDs limit", 1..=i64::MAX)?,
+        ..before.clone()
+    })
+}
+
+/// Parses a number within `range`, an empty value being 0.
+fn parse_optional(value: &str, name: &str, range: std::ops::RangeInclusive<i64>) -> Result<i64, String> {
+    match value.parse::<i64>() {
+        _ if value.is_empty() => Ok(0),
+        Ok(number) if range.contains(&number) => Ok(number),
+        _ => Err(format!("Invalid {name}: {value}, expected {} to {}", range.start(), range.end())),
+    }
+}
+
+/// Reads the commit options from the form. CMD, ENV and EXPOSE are passed as Dockerfile
+/// instructions, one per line, the way `docker commit --change` does.
+pub fn parse_commit_form(form: &InputForm, container: &str) -> Result<CommitContainerOptions, String> {
+    let reference = form.value("reference");
+    if reference.is_empty() {
+        return Err("Repository is required".to_string());
+    }
+
+    // A colon followed by a path belongs to a registry port, not to a tag.
+    let (repo, tag) = match reference.rsplit_once(':') {
+        Some((repo, tag)) if !tag.contains('/') && !repo.is_empty() && !tag.is_empty() => (repo, tag),
+        _ => (reference.as_str(), "latest"),
+    };
+
+    let mut changes = vec![];
+
+    let cmd = form.value("cmd");
+    if !cmd.is_empty() {
+        changes.push(format!("CMD {cmd}"));
+    }
+
+    let mut env = parse_key_values(&form.value("env"))?.into_iter().collect::<Vec<(String, String)>>();
+    env.sort();
+    changes.extend(env.into_iter().map(|(key, value)| format!("ENV {key}={value}")));
+
+    for port in form.value("expose").split_whitespace() {
+        let (number, protocol) = port.split_once('/').unwrap_or((port, "tcp"));
+        if number.parse::<u16>().is_err() || number == "0" || !PROTOCOLS.contains(&protocol) {
+            return Err(format!("Invalid port to expose: {port}"));
+        }
+        changes.push(format!("EXPOSE {port}"));
+    }
+
+    let optional = |value: String| Some(value).filter(|v| !v.is_empty());
+
+    Ok(CommitContainerOptions {
+        container: Some(container.to_string()),
+        repo: Some(repo.to_string()),
+        tag: Some(tag.to_string()),
+        comment: optional(form.value("message")),
+        author: optional(form.value("author")),
+        pause: form.value("pause") == PAUSE_MODES[0],
+        changes: optional(changes.join("\n")),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(config: HostConfig) -> ContainerSettings {
+        ContainerSettings::from(&config)
+    }
+
+    #[test]
+    fn update_body_contains_only_the_changes() {
+        let before = settings(HostConfig { memory: Some(512 << 20), pids_limit: Some(100), ..Default::default() });
+        let after = ContainerSettings { memory: 1 << 30, ..before.clone() };
+
+        let update = after.update_body(&before);
+        assert_eq!(update.memory, Some(1 << 30));
+        assert_eq!(update.pids_limit, None);
+        assert_eq!(update.restart_policy, None);
+        assert_eq!(update.cpu_quota, None);
+    }
+
+    #[test]
+    fn update_body_sends_removed_pids_limit_as_unlimited() {
+        let before = settings(HostConfig { pids_limit: Some(100), ..Default::default() });
+        let after = ContainerSettings { pids_limit: 0, ..before.clone() };
+
+        assert_eq!(after.update_body(&before).pids_limit, Some(-1));
+    }
+
+    #[test]
+    fn update_body_keeps_nano_cpus_set_with_cpus() {
+        let before = settings(HostConfig { nano_cpus: Some(1_500_000_000), ..Default::default() });
+        let after = ContainerSettings { nano_cpus: 2_000_000_000, ..before.clone() };
+
+        let update = after.update_body(&before);
+        assert_eq!(update.nano_cpus, Some(2_000_000_000));
+        assert_eq!(update.cpu_quota, None);
+    }
+
+    #[test]
+    fn update_body_sets_cpus_as_quota() {
+        let before = settings(HostConfig::default());
+        let after = ContainerSettings { nano_cpus: 500_000_000, ..before.clone() };
+
+        let update = after.update_body(&before);
+        assert_eq!(update.cpu_period, Some(CFS_PERIOD));
+        assert_eq!(update.cpu_quota, Some(50_000));
+        assert_eq!(update.nano_cpus, None);
+    }
+
+    #[test]
+    fn update_body_lifts_swap_below_the_new_memory() {
+        let before = settings(HostConfig { memory: Some(256 << 20), memory_swap: Some(512 << 20), ..Default::default() });
+        let after = ContainerSettings { memory: 1 << 30, ..before.clone() };
+
+        assert_eq!(after.update_body(&before).memory_swap, Some(-1));
+    }
+
+    #[test]
+    fn update_body_sets_retries_only_for_on_failure() {
+        let before = settings(HostConfig::default());
+        let after = ContainerSettings { restart_policy: "on-failure".to_string(), max_retries: 3, ..before.clone() };
+
+        let policy = after.update_body(&before).restart_policy.unwrap();
+        assert_eq!(policy.name, Some(RestartPolicyNameEnum::ON_FAILURE));
+        assert_eq!(policy.maximum_retry_count, Some(3));
+    }
+
+    #[test]
+    fn parse_settings_form_reads_the_limits() {
+        let before = settings(HostConfig::default());
+        let wanted = ContainerSettings { nano_cpus: 1_500_000_000, memory: 512 << 20, pids_limit: 50, ..before.clone() };
+
+        let parsed = parse_settings_form(&new_settings_form("web", &wanted), &before).unwrap();
+        assert_eq!(parsed.nano_cpus, 1_500_000_000);
+        assert_eq!(parsed.memory, 512 << 20);
+        assert_eq!(parsed.pids_limit, 50);
+    }
+
+    #[test]
+    fn parse_settings_form_refuses_to_remove_memory_limit() {
+        let before = settings(HostConfig { memory: Some(512 << 20), ..Default::default() });
+        let cleared = ContainerSettings { memory: 0, ..before.clone() };
+
+        assert!(parse_settings_form(&new_settings_form("web", &cleared), &before).is_err());
+    }
+
+    #[test]
+    fn parse_settings_form_refuses_to_remove_cpus_limit() {
+        let before = settings(HostConfig { nano_cpus: Some(1_000_000_000), ..Default::default() });
+        let cleared = ContainerSettings { nano_cpus: 0, ..before.clone() };
+
+        assert!(parse_settings_form(&new_settings_form("web", &cleared), &before).is_err());
+    }
+
+    #[test]
+    fn parse_settings_form_refuses_memory_below_minimum() {
+        let before = settings(HostConfig::default());
+        let wanted = ContainerSettings { memory: 1 << 20, ..before.clone() };
+
+        assert!(parse_settings_form(&new_settings_form("web", &wanted), &before).is_err());
+    }
+
+    #[test]
+    fn parse_settings_form_refuses_retries_without_on_failure() {
+        let before = settings(HostConfig::default());
+        let wanted = ContainerSettings { max_retries: 3, ..before.clone() };
+
+        assert!(parse_settings_form(&new_settings_form("web", &wanted), &before).is_err());
+    }
+}
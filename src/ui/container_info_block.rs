@@ -2,27 +2,64 @@ use std::collections::HashMap;
 
 use crate::{event::AppEvent, utils::is_container_running};
 
-use super::common::{render_footer, render_scrollbar};
 use bollard::secret::{
-    ContainerInspectResponse, ContainerStateStatusEnum, MountPoint, MountPointTypeEnum, PortBinding,
+    ContainerInspectResponse, ContainerStateStatusEnum, ContainerUpdateBody, EndpointSettings, MountPoint,
+    MountPointTypeEnum, PortBinding,
 };
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     Frame,
-    layout::{Constraint, Layout, Rect},
-    style::{Color, Style, Styled, Stylize, palette::tailwind},
+    layout::Rect,
+    style::{Style, Stylize},
     text::Line,
-    widgets::{Block, BorderType, Paragraph},
 };
 
-use super::info_block::{ScrollInfo, ScrollableInfoBlock};
+use super::common::PopupAction;
+use super::container_form::{
+    ContainerSettings, new_commit_form, new_export_form, new_rename_form, new_settings_form, parse_commit_form,
+    parse_export_form, parse_rename_form, parse_settings_form,
+};
+use super::info_block::{ScrollInfo, ScrollableInfoBlock, get_filtered_list, get_key_value_lines};
+use super::input_form::InputForm;
+use super::network_form::{
+    ConnectionForm, new_connect_network_form, new_disconnect_form, parse_connect_form, parse_disconnect_form,
+};
+use super::confirm_popup::ConfirmPopup;
 
-#[derive(Default, Clone)]
+#[derive(Default)]
 pub struct ContainerInfoBlock {
     data: ContainerData,
     scroll_info: ScrollInfo,
     skipped_tick_count_for_refresh: u8,
+    form: Option<ContainerForm>,
+    update_preview: Option<(ContainerUpdateBody, ConfirmPopup)>,
+    err: Option<String>,
+}
+
+/// Popup shown over the details, the connection forms being shared with the network details.
+enum ContainerForm {
+    Connection(ConnectionForm),
+    Commit(InputForm),
+    Export(InputForm),
+    Rename(InputForm),
+    Settings(InputForm),
+}
+
+impl ContainerForm {
+    fn form(&self) -> &InputForm {
+        match self {
+            Self::Connection(connection) => connection.form(),
+            Self::Commit(form) | Self::Export(form) | Self::Rename(form) | Self::Settings(form) => form,
+        }
+    }
+
+    fn form_mut(&mut self) -> &mut InputForm {
+        match self {
+            Self::Connection(connection) => connection.form_mut(),
+            Self::Commit(form) | Self::Export(form) | Self::Rename(form) | Self::Settings(form) => form,
+        }
+    }
 }
 
 #[derive(Default, Clone)]
@@ -39,14 +76,38 @@ pub struct ContainerData {
     entrypoint: String,
     env: String,
     restart_policy: String,
+    settings: ContainerSettings,
     volumes: String,
     labels: String,
+    networks: Vec<String>,
+    network_addresses: String,
 }
 
 impl ScrollableInfoBlock for ContainerInfoBlock {
     type Data = ContainerData;
 
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if let Some((_, preview)) = self.update_preview.as_mut() {
+            return Ok(match preview.handle_key_event(key_event) {
+                PopupAction::None => None,
+                PopupAction::Close => {
+                    self.update_preview = None;
+                    None
+                }
+                PopupAction::Submit => self.update_preview.take()
+                    .map(|(update, _)| AppEvent::UpdateContainer(self.data.id.clone(), Box::new(update))),
+            });
+        }
+
+        if self.err.is_some() {
+            self.err = None;
+            return Ok(None);
+        }
+
         let event = match key_event.code {
             KeyCode::Delete | KeyCode::Char('d') => {
                 Some(AppEvent::RemoveContainer(self.data.id.clone()))
@@ -54,62 +115,52 @@ impl ScrollableInfoBlock for ContainerInfoBlock {
             KeyCode::Char('r') => Some(AppEvent::RestartContainer(self.data.id.clone())),
             KeyCode::Char('s') => Some(AppEvent::StopContainer(self.data.id.clone())),
             KeyCode::Char('x') => Some(AppEvent::KillContainer(self.data.id.clone())),
+            KeyCode::Char('c') => {
+                self.form = Some(ContainerForm::Connection(ConnectionForm::Connect(new_connect_network_form(&self.data.name))));
+                None
+            }
+            KeyCode::Char('u') => {
+                self.open_disconnect_form();
+                None
+            }
+            KeyCode::Char('f') => Some(AppEvent::BrowseContainer(self.data.id.clone())),
+            KeyCode::Char('i') => {
+                self.form = Some(ContainerForm::Commit(new_commit_form(&self.data.name)));
+                None
+            }
+            KeyCode::Char('e') => {
+                self.form = Some(ContainerForm::Export(new_export_form(&self.data.name)));
+                None
+            }
+            KeyCode::Char('n') => {
+                self.form = Some(ContainerForm::Rename(new_rename_form(&self.data.name)));
+                None
+            }
+            KeyCode::Char('l') => {
+                self.form = Some(ContainerForm::Settings(new_settings_form(&self.data.name, &self.data.settings)));
+                None
+            }
             _ => self.handle_nav_key_event(key_event)?,
         };
         Ok(event)
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
-        use Constraint::{Length, Min};
-
-        let vertical_layout = Layout::vertical([Min(0), Length(1), Length(3)]);
-        let [content_area, horizontal_scrollbar_area, footer_area] = vertical_layout.areas(area);
-
-        let horizontal_layout = Layout::horizontal([Min(0), Length(1)]);
-        let [info_area, vertical_scrollbar_area] = horizontal_layout.areas(content_area);
-
-        let content_lines = get_content_as_lines(&self.data);
-
-        let max_horizontal = content_lines.iter().fold(0, |max, line| {
-            let line_len = line.to_string().len();
-            if line_len > max { line_len } else { max }
-        });
-
-        self.scroll_info.max_horizontal = max_horizontal.saturating_sub(info_area.width as usize - 2);
-        self.scroll_info.max_vertical = content_lines.len().saturating_sub(info_area.height as usize - 2);
-
-        self.render_content(frame, info_area, content_lines);
-
-        self.scroll_info.vertical_state = self.scroll_info.vertical_state
-            .content_length(self.scroll_info.max_vertical)
-            .position(self.scroll_info.vertical);
-
-        render_scrollbar(
-            frame,
-            vertical_scrollbar_area,
-            &mut self.scroll_info.vertical_state,
-            true,
-        );
-
-        self.scroll_info.horizontal_state = self.scroll_info.horizontal_state
-            .content_length(self.scroll_info.max_horizontal)
-            .position(self.scroll_info.horizontal);
-
-        render_scrollbar(
-            frame,
-            horizontal_scrollbar_area,
-            &mut self.scroll_info.horizontal_state,
-            false,
-        );
+        let title = format!("Container: {}", self.data.name);
+        let (footer_text, footer_style) = match &self.err {
+            Some(err) => (err.clone(), Some(Style::new().red())),
+            None => (get_footer_text(is_container_running(&self.data.state)), None),
+        };
+        let result = self.draw_default(frame, area, title, get_content_as_lines(&self.data), footer_text, footer_style);
 
-        render_footer(
-            frame,
-            footer_area,
-            get_footer_text(is_container_running(&self.data.state)),
-            None,
-        );
+        if let Some(form) = &self.form {
+            form.form().draw(frame, area);
+        }
+        if let Some((_, preview)) = &self.update_preview {
+            preview.draw(frame, area);
+        }
 
-        Ok(())
+        result
     }
 
     fn tick(&mut self) -> Result<Option<AppEvent>> {
@@ -123,6 +174,10 @@ impl ScrollableInfoBlock for ContainerInfoBlock {
         Ok(event)
     }
 
+    fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
     fn update_data(&mut self, data: Self::Data) {
         self.data = data;
     }
@@ -133,26 +188,75 @@ impl ScrollableInfoBlock for ContainerInfoBlock {
 }
 
 impl ContainerInfoBlock {
-    fn render_content(&mut self, frame: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
-        let block_style = Style::new().fg(tailwind::BLUE.c400);
-
-        let title =
-            Line::from(format!("Container: {}", self.data.name.clone())).fg(tailwind::SLATE.c200);
-
-        let block = Block::bordered()
-            .border_type(BorderType::Plain)
-            .border_style(block_style)
-            .title(title);
-
-        let paragraph = Paragraph::new(lines)
-            .block(block)
-            .scroll((
-                self.scroll_info.vertical as u16,
-                self.scroll_info.horizontal as u16,
-            ))
-            .left_aligned();
-
-        frame.render_widget(paragraph, area);
+    fn open_disconnect_form(&mut self) {
+        if self.data.networks.is_empty() {
+            self.show_err("Not connected to any network");
+            return;
+        }
+
+        let title = format!("Disconnect {}", self.data.name);
+        let form = new_disconnect_form(&title, "Network", &self.data.networks);
+        self.form = Some(ContainerForm::Connection(ConnectionForm::Disconnect(form)));
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let container = self.data.id.clone();
+        let container_form = self.form.as_mut()?;
+
+        match container_form.form_mut().handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => {
+                let result = match container_form {
+                    ContainerForm::Connection(ConnectionForm::Connect(form)) => {
+                        parse_connect_form(form, None, Some(&container)).map(AppEvent::ConnectNetwork)
+                    }
+                    ContainerForm::Connection(ConnectionForm::Disconnect(form)) => {
+                        let (network, force) = parse_disconnect_form(form);
+                        Ok(AppEvent::DisconnectNetwork(network, container, force))
+                    }
+                    ContainerForm::Commit(form) => {
+                        parse_commit_form(form, &container).map(|options| AppEvent::CommitContainer(Box::new(options)))
+                    }
+                    ContainerForm::Export(form) => {
+                        parse_export_form(form).map(|path| AppEvent::ExportContainer(container, path))
+                    }
+                    ContainerForm::Rename(form) => {
+                        parse_rename_form(form, &self.data.name).map(|name| AppEvent::RenameContainer(container, name))
+                    }
+                    ContainerForm::Settings(form) => match parse_settings_form(form, &self.data.settings) {
+                        Ok(settings) => {
+                            let changes = settings.changes_from(&self.data.settings);
+                            if changes.is_empty() {
+                                form.set_err("Nothing to change");
+                                return None;
+                            }
+
+                            let title = format!("Update {}", self.data.name);
+                            let preview = ConfirmPopup::new(title, changes, "apply");
+                            self.update_preview = Some((settings.update_body(&self.data.settings), preview));
+                            self.form = None;
+                            return None;
+                        }
+                        Err(err) => Err(err),
+                    },
+                };
+
+                match result {
+                    Ok(event) => {
+                        self.form = None;
+                        Some(event)
+                    }
+                    Err(err) => {
+                        container_form.form_mut().set_err(&err);
+                        None
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -165,6 +269,7 @@ fn get_content_as_lines(data: &ContainerData) -> Vec<Line<'static>> {
         ("Created: ".to_string(), data.created.clone()),
         ("Start Time: ".to_string(), data.start_time.clone()),
         ("Restart Policy: ".to_string(), data.restart_policy.clone()),
+        ("Limits: ".to_string(), data.settings.limits_text()),
         ("State: ".to_string(), data.state.clone()),
         spacer.clone(),
         ("CMD: ".to_string(), data.cmd.clone()),
@@ -178,6 +283,11 @@ fn get_content_as_lines(data: &ContainerData) -> Vec<Line<'static>> {
         ]);
     }
 
+    if let Some(networks) = get_filtered_list(&data.network_addresses) {
+        lines.extend(vec![spacer.clone(), ("Networks:".to_string(), "".to_string())]);
+        lines.extend(networks);
+    }
+
     if let Some(ports) = get_filtered_list(&data.port_configs) {
         lines.extend(vec![
             spacer.clone(),
@@ -207,27 +317,7 @@ fn get_content_as_lines(data: &ContainerData) -> Vec<Line<'static>> {
         lines.extend(labels);
     }
 
-    let key_style = Style::new().fg(Color::Green);
-
-    lines
-        .into_iter()
-        .filter(|(_, content)| !content.eq("-"))
-        .map(|(key, content)| Line::from_iter([key.set_style(key_style), content.into()]))
-        .collect()
-}
-
-fn get_filtered_list(data: &str) -> Option<Vec<(String, String)>> {
-    let mut splitted_data: Vec<String> = data.split("\n")
-        .filter(|p| !p.is_empty())
-        .map(|s| s.to_string())
-        .collect();
-
-    if splitted_data.is_empty() {
-        return None;
-    }
-
-    splitted_data.sort_unstable();
-    Some(splitted_data.iter().map(|d| ("".to_string(), format!(" - {d}"))).collect())
+    get_key_value_lines(lines)
 }
 
 fn get_footer_text(is_running: bool) -> String {
@@ -236,7 +326,7 @@ fn get_footer_text(is_running: bool) -> String {
     } else {
         "| <R> start "
     };
-    format!(" <Esc/Q> back {op_text}| <Del/D> remove")
+    format!(" <Esc/Q> back {op_text}| <F> files | <I> commit | <E> export | <N> rename | <L> limits | <C> connect network | <U> disconnect network | <Del/D> remove")
 }
 
 impl ContainerData {
@@ -272,8 +362,15 @@ impl ContainerData {
 
         let mut ip_address = "-".to_string();
         let mut port_configs = "-".to_string();
+        let mut networks = vec![];
+        let mut network_addresses = "-".to_string();
         if let Some(network_settings) = container.network_settings {
             ip_address = network_settings.ip_address.unwrap_or(ip_address);
+            if let Some(endpoints) = network_settings.networks {
+                network_addresses = get_networks_text(&endpoints);
+                networks = endpoints.into_keys().collect();
+                networks.sort();
+            }
             port_configs = network_settings.ports
                 .map(|p| get_ports_text(&p))
                 .unwrap_or(port_configs);
@@ -288,6 +385,8 @@ impl ContainerData {
 
         let volumes = container.mounts.as_ref().map_or("-".to_string(), |mp| get_mounts_text(mp));
 
+        let settings = container.host_config.as_ref().map(ContainerSettings::from).unwrap_or_default();
+
         Self {
             id: container.id.as_deref().unwrap_or("-").to_string(),
             name,
@@ -301,8 +400,11 @@ impl ContainerData {
             entrypoint,
             env,
             restart_policy,
+            settings,
             volumes,
             labels,
+            networks,
+            network_addresses,
         }
     }
 }
@@ -337,6 +439,24 @@ fn get_ports_text(ports: &HashMap<String, Option<Vec<PortBinding>>>) -> String {
         .join("\n")
 }
 
+fn get_networks_text(networks: &HashMap<String, EndpointSettings>) -> String {
+    networks.iter()
+        .map(|(name, endpoint)| {
+            let details = [
+                ("IPv4", endpoint.ip_address.clone()),
+                ("IPv6", endpoint.global_ipv6_address.clone()),
+                ("aliases", endpoint.aliases.as_ref().map(|a| a.join(" "))),
+            ]
+            .into_iter()
+            .filter_map(|(key, value)| value.filter(|v| !v.is_empty()).map(|v| format!("{key} {v}")))
+            .collect::<Vec<String>>()
+            .join(", ");
+            format!("{name}: {details}")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 fn get_port_binding_text(port_binding: &PortBinding) -> String {
     format!(
         "{}:{}",
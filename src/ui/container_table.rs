@@ -1,7 +1,11 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::docker::compose::{PROJECT_LABEL, SERVICE_LABEL};
 use crate::ui::resource_table::ResourceTableInfo;
 use crate::{event::AppEvent, ui::resource_table::ResourceTable, utils::is_container_running};
 
-use super::common::{TableStyle, render_footer};
+use super::common::{PopupAction, TableStyle, render_footer};
+use super::confirm_popup::ConfirmPopup;
 use bollard::secret::{ContainerSummary, Port, PortTypeEnum};
 use color_eyre::Result;
 use ratatui::style::Stylize;
@@ -9,25 +13,65 @@ use ratatui::{
     Frame,
     crossterm::event::{KeyCode, KeyEvent},
     layout::{Constraint, Rect},
-    style::Style,
+    style::{Style, palette::tailwind},
     text::Text,
     widgets::{Cell, HighlightSpacing, Row, Table},
 };
+use strum_macros::Display;
 
 pub struct ContainerTable {
     style: TableStyle,
     show_all: bool,
     skipped_tick_count_for_update: u8,
-    info: ResourceTableInfo<ContainerTableRow>,
+    info: ResourceTableInfo<ContainerTreeRow>,
+    containers: Vec<ContainerTableRow>,
+    collapsed: HashSet<String>,
+    remove_preview: Option<(String, Vec<String>, ConfirmPopup)>,
     err: Option<String>,
+    notice: Option<String>,
 }
 
+#[derive(Clone)]
 pub struct ContainerTableRow {
     id: String,
     name: String,
     image: String,
     state: String,
     ports: String,
+    project: Option<String>,
+    service: Option<String>,
+}
+
+/// A row of the container tree: compose projects and their services are group rows holding
+/// the containers below them, other containers are listed after the projects.
+pub enum ContainerTreeRow {
+    Group(ComposeGroup),
+    Container(ContainerTableRow, usize),
+}
+
+pub struct ComposeGroup {
+    key: String,
+    name: String,
+    depth: usize,
+    container_ids: Vec<String>,
+    running: usize,
+    is_collapsed: bool,
+}
+
+/// An action run on every container of a compose project or service.
+#[derive(Display, Clone, Copy, Debug)]
+pub enum GroupAction {
+    #[strum(to_string = "Started")]
+    Start,
+
+    #[strum(to_string = "Stopped")]
+    Stop,
+
+    #[strum(to_string = "Restarted")]
+    Restart,
+
+    #[strum(to_string = "Removed")]
+    Remove,
 }
 
 impl Default for ContainerTable {
@@ -37,13 +81,17 @@ impl Default for ContainerTable {
             show_all: true,
             skipped_tick_count_for_update: 0,
             info: ResourceTableInfo::default(),
+            containers: vec![],
+            collapsed: HashSet::new(),
+            remove_preview: None,
             err: None,
+            notice: None,
         }
     }
 }
 
 impl ResourceTable for ContainerTable {
-    type RowType = ContainerTableRow;
+    type RowType = ContainerTreeRow;
 
     fn get_table_info(&mut self) -> &mut ResourceTableInfo<Self::RowType> {
         &mut self.info
@@ -59,19 +107,35 @@ impl ResourceTable for ContainerTable {
         self.info.row_heights.clear();
 
         let rows = self.info.items.iter().enumerate()
-            .filter(|(_, container)| self.show_all || String::eq(&container.state, "running"))
-            .map(|(index, container)| {
-                let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
-                let item = container.ref_array();
-                let ports: Vec<&str> = container.ports.split("\n").filter(|s| !s.is_empty()).collect();
+            .map(|(index, row)| {
+                let mut row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+
+                let (item, height, state_style) = match row {
+                    ContainerTreeRow::Group(group) => {
+                        row_style = row_style.bold();
+                        if group.depth == 0 {
+                            row_style = row_style.fg(tailwind::BLUE.c400);
+                        }
+                        (group.ref_array(), 3, group.state_style())
+                    }
+                    ContainerTreeRow::Container(container, depth) => {
+                        let ports = container.ports.split("\n").filter(|s| !s.is_empty()).count();
+                        let mut item = container.ref_array().map(String::clone);
+                        item[1] = format!("{}{}", "  ".repeat(*depth), container.name);
+                        (item, if ports == 0 { 3 } else { ports + 2 }, Style::new())
+                    }
+                };
 
-                let height = if ports.is_empty() { 3 } else { ports.len() + 2 };
                 if index < self.info.items.len() - 1 {
                     self.info.row_heights.push(height);
                 }
 
                 item.into_iter()
-                    .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                    .enumerate()
+                    .map(|(column, content)| {
+                        let cell = Cell::from(Text::from(format!("\n{content}\n")));
+                        if column == 3 { cell.style(state_style) } else { cell }
+                    })
                     .collect::<Row>()
                     .style(row_style)
                     .height(height as u16)
@@ -96,9 +160,13 @@ impl ResourceTable for ContainerTable {
 
     fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
         let mut border_style = None;
+        let show_all = self.show_all;
+        let mut footer_text = get_footer_text(show_all, self.get_selected_row());
 
-        let is_selected_container_running = self.get_selected_row().map(|c| is_container_running(&c.state));
-        let mut footer_text = get_footer_text(self.show_all, is_selected_container_running);
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
 
         if let Some(err) = &self.err {
             border_style = Some(Style::new().red());
@@ -111,19 +179,29 @@ impl ResourceTable for ContainerTable {
 
 impl ContainerTable {
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
-        if self.err.is_some() {
+        if self.remove_preview.is_some() {
+            return Ok(self.handle_remove_key_event(key_event));
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
             self.err = None;
+            self.notice = None;
             return Ok(None);
         }
 
+        if let Some(ContainerTreeRow::Group(group)) = self.get_selected_row() {
+            let (key, ids) = (group.key.clone(), group.container_ids.clone());
+            return self.handle_group_key_event(key_event, key, ids);
+        }
+
         let event = match key_event.code {
             KeyCode::Char('t') => {
-                self.show_all = !self.show_all;
+                self.toggle_show_all();
                 None
             }
-            KeyCode::Delete | KeyCode::Char('d') => self.get_selected_row().map(|c| AppEvent::RemoveContainer(c.id.clone())),
-            KeyCode::Enter => self.get_selected_row().map(|c| AppEvent::GoToContainerDetails(c.id.clone())),
-            KeyCode::Char(c) => match (c, self.get_selected_row().map(|c| c.id.clone())) {
+            KeyCode::Delete | KeyCode::Char('d') => self.selected_container_id().map(AppEvent::RemoveContainer),
+            KeyCode::Enter => self.selected_container_id().map(AppEvent::GoToContainerDetails),
+            KeyCode::Char(c) => match (c, self.selected_container_id()) {
                 ('r', Some(id)) => Some(AppEvent::RestartContainer(id)),
                 ('s', Some(id)) => Some(AppEvent::StopContainer(id)),
                 ('x', Some(id)) => Some(AppEvent::KillContainer(id)),
@@ -135,9 +213,161 @@ impl ContainerTable {
         Ok(event)
     }
 
+    fn handle_group_key_event(&mut self, key_event: KeyEvent, key: String, ids: Vec<String>) -> Result<Option<AppEvent>> {
+        let event = match key_event.code {
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if !self.collapsed.remove(&key) {
+                    self.collapsed.insert(key);
+                }
+                self.refresh_rows();
+                None
+            }
+            KeyCode::Char('t') => {
+                self.toggle_show_all();
+                None
+            }
+            // The daemon rejects starting a running container or stopping a stopped one.
+            KeyCode::Char('a') => {
+                let ids = self.filter_ids_by_state(&ids, false);
+                Some(AppEvent::ContainerGroupAction(GroupAction::Start, key, ids))
+            }
+            KeyCode::Char('s') => {
+                let ids = self.filter_ids_by_state(&ids, true);
+                Some(AppEvent::ContainerGroupAction(GroupAction::Stop, key, ids))
+            }
+            KeyCode::Char('r') => Some(AppEvent::ContainerGroupAction(GroupAction::Restart, key, ids)),
+            KeyCode::Char('c') => {
+                let project = key.split('/').next().unwrap_or(&key).to_string();
+                Some(AppEvent::ShowComposeProject(project))
+            }
+            KeyCode::Delete | KeyCode::Char('d') => {
+                self.open_remove_preview(key, ids);
+                None
+            }
+            _ => self.handle_nav_key_event(key_event)?,
+        };
+        Ok(event)
+    }
+
+    fn filter_ids_by_state(&self, ids: &[String], is_running: bool) -> Vec<String> {
+        self.containers.iter()
+            .filter(|c| ids.contains(&c.id) && is_container_running(&c.state) == is_running)
+            .map(|c| c.id.clone())
+            .collect()
+    }
+
+    fn handle_remove_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let (_, _, preview) = self.remove_preview.as_mut()?;
+        match preview.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.remove_preview = None;
+                None
+            }
+            PopupAction::Submit => {
+                let (key, ids, _) = self.remove_preview.take()?;
+                Some(AppEvent::ContainerGroupAction(GroupAction::Remove, key, ids))
+            }
+        }
+    }
+
+    fn open_remove_preview(&mut self, key: String, ids: Vec<String>) {
+        let items = self.containers.iter()
+            .filter(|c| ids.contains(&c.id))
+            .map(|c| format!("{} ({})", c.name, c.state))
+            .collect();
+        let title = format!("Remove all containers of {}", key.replace('/', " / "));
+        let preview = ConfirmPopup::new(title, items, "remove");
+        self.remove_preview = Some((key, ids, preview));
+    }
+
+    pub fn update_containers(&mut self, containers: Vec<ContainerTableRow>) {
+        self.containers = containers;
+        self.refresh_rows();
+    }
+
+    fn toggle_show_all(&mut self) {
+        self.show_all = !self.show_all;
+        self.refresh_rows();
+    }
+
+    fn selected_container_id(&mut self) -> Option<String> {
+        match self.get_selected_row()? {
+            ContainerTreeRow::Container(container, _) => Some(container.id.clone()),
+            ContainerTreeRow::Group(_) => None,
+        }
+    }
+
+    /// Rebuilds the visible tree from the containers, keeping group counts over every container
+    /// so a project with stopped containers still shows e.g. 3/4 running when only running ones are listed.
+    fn refresh_rows(&mut self) {
+        let mut projects: BTreeMap<&str, BTreeMap<&str, Vec<&ContainerTableRow>>> = BTreeMap::new();
+        let mut standalone = vec![];
+
+        for container in &self.containers {
+            match &container.project {
+                Some(project) => projects.entry(project.as_str()).or_default()
+                    .entry(container.service.as_deref().unwrap_or("-")).or_default()
+                    .push(container),
+                None => standalone.push(container),
+            }
+        }
+
+        let is_visible = |c: &ContainerTableRow| self.show_all || is_container_running(&c.state);
+        let mut rows = vec![];
+
+        for (project, services) in projects {
+            let containers = services.values().flatten().copied().collect::<Vec<&ContainerTableRow>>();
+            if !containers.iter().any(|c| is_visible(c)) {
+                continue;
+            }
+
+            let project_group = ComposeGroup::new(project.to_string(), project.to_string(), 0, &containers, &self.collapsed);
+            let is_project_collapsed = project_group.is_collapsed;
+            rows.push(ContainerTreeRow::Group(project_group));
+            if is_project_collapsed {
+                continue;
+            }
+
+            for (service, mut containers) in services {
+                if !containers.iter().any(|c| is_visible(c)) {
+                    continue;
+                }
+
+                let key = format!("{project}/{service}");
+                let service_group = ComposeGroup::new(key, service.to_string(), 1, &containers, &self.collapsed);
+                let is_service_collapsed = service_group.is_collapsed;
+                rows.push(ContainerTreeRow::Group(service_group));
+                if is_service_collapsed {
+                    continue;
+                }
+
+                containers.sort_by(|a, b| a.name.cmp(&b.name));
+                rows.extend(containers.into_iter()
+                    .filter(|c| is_visible(c))
+                    .map(|c| ContainerTreeRow::Container(c.clone(), 2)));
+            }
+        }
+
+        rows.extend(standalone.into_iter()
+            .filter(|c| is_visible(c))
+            .map(|c| ContainerTreeRow::Container(c.clone(), 0)));
+
+        let row_count = rows.len();
+        self.update_with_items(rows);
+
+        if self.info.state.selected().is_some_and(|index| index >= row_count) && row_count > 0 {
+            self.select_row(row_count - 1);
+        }
+    }
+
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
         let result = self.draw_default(frame, area);
 
+        if let Some((_, _, preview)) = &self.remove_preview {
+            preview.draw(frame, area);
+        }
+
         // If there is items but no row selected, select the first row.
         // This happens when changing the `self.show_all` parameter.
         if self.info.state.selected().is_none() && !self.info.items.is_empty() {
@@ -157,6 +387,14 @@ impl ContainerTable {
         Ok(Some(AppEvent::UpdateContainers))
     }
 
+    pub fn show_group_result(&mut self, action: GroupAction, key: &str, count: usize) {
+        self.notice = Some(format!("[OK] {action} {count} container(s) of {}", key.replace('/', " / ")));
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
     pub fn show_container_err(&mut self, err: String) {
         let err_msg = err.split(":") .collect::<Vec<&str>>().get(2)
             .map_or("Something went wrong...", |v| v);
@@ -164,6 +402,39 @@ impl ContainerTable {
     }
 }
 
+impl ComposeGroup {
+    fn new(key: String, name: String, depth: usize, containers: &[&ContainerTableRow], collapsed: &HashSet<String>) -> Self {
+        Self {
+            is_collapsed: collapsed.contains(&key),
+            key,
+            name,
+            depth,
+            container_ids: containers.iter().map(|c| c.id.clone()).collect(),
+            running: containers.iter().filter(|c| is_container_running(&c.state)).count(),
+        }
+    }
+
+    fn ref_array(&self) -> [String; 5] {
+        let arrow = if self.is_collapsed { "▸" } else { "▾" };
+        let kind = if self.depth == 0 { "compose project" } else { "compose service" };
+        [
+            "".to_string(),
+            format!("{}{arrow} {}", "  ".repeat(self.depth), self.name),
+            kind.to_string(),
+            format!("{}/{} running", self.running, self.container_ids.len()),
+            "".to_string(),
+        ]
+    }
+
+    fn state_style(&self) -> Style {
+        match self.running {
+            0 => Style::new().fg(tailwind::SLATE.c500),
+            running if running == self.container_ids.len() => Style::new().fg(tailwind::GREEN.c400),
+            _ => Style::new().fg(tailwind::AMBER.c400),
+        }
+    }
+}
+
 impl ContainerTableRow {
     const fn ref_array(&self) -> [&String; 5] {
         [&self.id, &self.name, &self.image, &self.state, &self.ports]
@@ -194,12 +465,16 @@ impl ContainerTableRow {
             .and_then(|name| name.strip_prefix("/"))
             .map_or("NaN".to_string(), |name| name.to_string());
 
+        let label = |key: &str| container.labels.as_ref().and_then(|labels| labels.get(key)).cloned();
+
         Self {
             id: container.id.as_deref().unwrap_or("-").to_string(),
             name,
             image: container.image.as_deref().unwrap_or("-").to_string(),
-            state: container.state.as_deref().unwrap_or("-").to_string(),
+            state: container.state.map_or("-".to_string(), |state| state.to_string()),
             ports: container.ports.as_ref().map_or("-".to_string(), |p| get_ports_text(p)),
+            project: label(PROJECT_LABEL),
+            service: label(SERVICE_LABEL),
         }
     }
 }
@@ -218,9 +493,21 @@ fn get_ports_text(ports: &[Port]) -> String {
         .join("\n")
 }
 
-fn get_footer_text(show_all: bool, is_running: Option<bool>) -> String {
+fn get_footer_text(show_all: bool, selected: Option<&ContainerTreeRow>) -> String {
     let toggle_text = if show_all { "All" } else { "Running" };
+
+    if let Some(ContainerTreeRow::Group(group)) = selected {
+        let collapse_text = if group.is_collapsed { "expand" } else { "collapse" };
+        return format!(
+            " <Ent/Space> {collapse_text} | <C> compose | <T> {toggle_text} | <A> start all | <R> restart all | <S> stop all | <Del/D> remove all"
+        );
+    }
+
     let mut op_text = "".to_string();
+    let is_running = match selected {
+        Some(ContainerTreeRow::Container(container, _)) => Some(is_container_running(&container.state)),
+        _ => None,
+    };
 
     if let Some(running) = is_running {
         let running_text = if running { "restart | <S> stop | <X> kill " } else { "start " };
@@ -0,0 +1,226 @@
+use bollard::secret::{SystemInfo, SystemVersion};
+use color_eyre::eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{Frame, layout::Rect, text::Line};
+
+use crate::event::AppEvent;
+
+use super::common::size_string;
+use super::info_block::{ScrollInfo, ScrollableInfoBlock, get_filtered_list, get_key_value_lines};
+
+const REFRESH_AFTER_TICK: u8 = 90;
+const FOOTER_TEXT: &str = " <Esc/Q> back";
+
+#[derive(Default, Clone)]
+pub struct DaemonInfoBlock {
+    data: DaemonData,
+    scroll_info: ScrollInfo,
+    skipped_tick_count_for_refresh: u8,
+}
+
+#[derive(Default, Clone)]
+pub struct DaemonData {
+    name: String,
+    server_version: String,
+    api_version: String,
+    client_api_version: String,
+    git_commit: String,
+    go_version: String,
+    build_time: String,
+    os: String,
+    kernel_version: String,
+    architecture: String,
+    root_dir: String,
+    storage_driver: String,
+    storage_status: String,
+    cgroup: String,
+    logging_driver: String,
+    default_runtime: String,
+    runtimes: String,
+    security_options: String,
+    cpus: String,
+    memory: String,
+    containers: String,
+    images: String,
+    swarm: String,
+    registry_mirrors: String,
+    insecure_registries: String,
+    warnings: String,
+}
+
+impl ScrollableInfoBlock for DaemonInfoBlock {
+    type Data = DaemonData;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        self.handle_nav_key_event(key_event)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let title = format!("Docker Engine: {}", self.data.name);
+        self.draw_default(frame, area, title, get_content_as_lines(&self.data), FOOTER_TEXT.to_string(), None)
+    }
+
+    fn tick(&mut self) -> Result<Option<AppEvent>> {
+        let event = if self.skipped_tick_count_for_refresh > REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh = 0;
+            Some(AppEvent::UpdateDaemonInfo)
+        } else {
+            self.skipped_tick_count_for_refresh += 1;
+            None
+        };
+        Ok(event)
+    }
+
+    fn update_data(&mut self, data: Self::Data) {
+        self.data = data;
+    }
+
+    fn get_scroll_info(&mut self) -> &mut ScrollInfo {
+        &mut self.scroll_info
+    }
+}
+
+fn get_content_as_lines(data: &DaemonData) -> Vec<Line<'static>> {
+    let spacer = ("".to_string(), "".to_string());
+
+    let mut lines = vec![
+        ("Server Version: ".to_string(), data.server_version.clone()),
+        ("API Version: ".to_string(), data.api_version.clone()),
+        ("Client API Version: ".to_string(), data.client_api_version.clone()),
+        ("Git Commit: ".to_string(), data.git_commit.clone()),
+        ("Go Version: ".to_string(), data.go_version.clone()),
+        ("Built: ".to_string(), data.build_time.clone()),
+        spacer.clone(),
+        ("Operating System: ".to_string(), data.os.clone()),
+        ("Kernel Version: ".to_string(), data.kernel_version.clone()),
+        ("Architecture: ".to_string(), data.architecture.clone()),
+        ("CPUs: ".to_string(), data.cpus.clone()),
+        ("Total Memory: ".to_string(), data.memory.clone()),
+        spacer.clone(),
+        ("Containers: ".to_string(), data.containers.clone()),
+        ("Images: ".to_string(), data.images.clone()),
+        ("Swarm: ".to_string(), data.swarm.clone()),
+        spacer.clone(),
+        ("Docker Root Dir: ".to_string(), data.root_dir.clone()),
+        ("Storage Driver: ".to_string(), data.storage_driver.clone()),
+    ];
+
+    lines.extend(get_filtered_list(&data.storage_status).unwrap_or_default());
+
+    lines.extend(vec![
+        ("Cgroup: ".to_string(), data.cgroup.clone()),
+        ("Logging Driver: ".to_string(), data.logging_driver.clone()),
+        ("Default Runtime: ".to_string(), data.default_runtime.clone()),
+    ]);
+
+    if let Some(runtimes) = get_filtered_list(&data.runtimes) {
+        lines.extend(vec![spacer.clone(), ("Runtimes:".to_string(), "".to_string())]);
+        lines.extend(runtimes);
+    }
+
+    if let Some(options) = get_filtered_list(&data.security_options) {
+        lines.extend(vec![spacer.clone(), ("Security Options:".to_string(), "".to_string())]);
+        lines.extend(options);
+    }
+
+    if let Some(mirrors) = get_filtered_list(&data.registry_mirrors) {
+        lines.extend(vec![spacer.clone(), ("Registry Mirrors:".to_string(), "".to_string())]);
+        lines.extend(mirrors);
+    }
+
+    if let Some(registries) = get_filtered_list(&data.insecure_registries) {
+        lines.extend(vec![spacer.clone(), ("Insecure Registries:".to_string(), "".to_string())]);
+        lines.extend(registries);
+    }
+
+    if let Some(warnings) = get_filtered_list(&data.warnings) {
+        lines.extend(vec![spacer.clone(), ("Warnings:".to_string(), "".to_string())]);
+        lines.extend(warnings);
+    }
+
+    get_key_value_lines(lines)
+}
+
+impl DaemonData {
+    /// Builds the data from `docker info` and `docker version`, `client_api_version` being the API
+    /// version negotiated with the daemon at startup.
+    pub fn from(info: SystemInfo, version: SystemVersion, client_api_version: String) -> Self {
+        let or_dash = |value: Option<String>| value.filter(|v| !v.is_empty()).unwrap_or_else(|| "-".to_string());
+
+        let api_version = match (version.api_version, version.min_api_version) {
+            (Some(api), Some(min)) => format!("{api} (minimum {min})"),
+            (api, _) => or_dash(api),
+        };
+
+        let os = match (info.operating_system, info.os_type) {
+            (Some(os), Some(os_type)) => format!("{os} ({os_type})"),
+            (os, _) => or_dash(os),
+        };
+
+        let cgroup = match (info.cgroup_driver, info.cgroup_version) {
+            (Some(driver), Some(version)) => format!("{driver} (v{version})"),
+            (driver, _) => driver.map_or("-".to_string(), |d| d.to_string()),
+        };
+
+        let containers = match info.containers {
+            Some(total) => format!(
+                "{total} ({} running, {} paused, {} stopped)",
+                info.containers_running.unwrap_or_default(),
+                info.containers_paused.unwrap_or_default(),
+                info.containers_stopped.unwrap_or_default(),
+            ),
+            None => "-".to_string(),
+        };
+
+        let storage_status = info.driver_status.unwrap_or_default().into_iter()
+            .filter_map(|pair| match pair.as_slice() {
+                [key, value] => Some(format!("{key}: {value}")),
+                _ => None,
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let runtimes = info.runtimes.unwrap_or_default().into_iter()
+            .map(|(name, runtime)| match runtime.path {
+                Some(path) => format!("{name}: {path}"),
+                None => name,
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let swarm = info.swarm
+            .and_then(|swarm| swarm.local_node_state)
+            .map_or("-".to_string(), |state| state.to_string());
+
+        let registry = info.registry_config.unwrap_or_default();
+
+        Self {
+            name: or_dash(info.name),
+            server_version: or_dash(version.version.or(info.server_version)),
+            api_version,
+            client_api_version,
+            git_commit: or_dash(version.git_commit),
+            go_version: or_dash(version.go_version),
+            build_time: or_dash(version.build_time),
+            os,
+            kernel_version: or_dash(info.kernel_version),
+            architecture: or_dash(info.architecture),
+            root_dir: or_dash(info.docker_root_dir),
+            storage_driver: or_dash(info.driver),
+            storage_status,
+            cgroup,
+            logging_driver: or_dash(info.logging_driver),
+            default_runtime: or_dash(info.default_runtime),
+            runtimes,
+            security_options: info.security_options.unwrap_or_default().join("\n"),
+            cpus: info.ncpu.map_or("-".to_string(), |cpus| cpus.to_string()),
+            memory: info.mem_total.map_or("-".to_string(), size_string),
+            containers,
+            images: info.images.map_or("-".to_string(), |images| images.to_string()),
+            swarm,
+            registry_mirrors: registry.mirrors.unwrap_or_default().join("\n"),
+            insecure_registries: registry.insecure_registry_cidrs.unwrap_or_default().join("\n"),
+            warnings: info.warnings.unwrap_or_default().join("\n"),
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use bollard::secret::{ContainerSummary, MountPointTypeEnum};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Cell, HighlightSpacing, Row, Table, TableState},
+};
+
+use crate::{event::AppEvent, utils::is_container_running};
+
+use super::common::{PopupAction, TableStyle};
+
+/// Resource whose dependent containers are listed in the panel.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DependencyTarget {
+    Image(String),
+    Volume(String),
+    Network(String),
+}
+
+/// Table that can open the dependents panel for its selected row. Implementors only give
+/// access to their panel and to the target of the selected row.
+pub trait DependentsTable {
+    fn get_dependents(&mut self) -> &mut Option<DependentsPanel>;
+
+    fn selected_dependency_target(&mut self) -> Option<DependencyTarget>;
+
+    fn dependents_target(&mut self) -> Option<DependencyTarget> {
+        self.get_dependents().as_ref().map(|d| d.target().clone())
+    }
+
+    fn update_dependents(&mut self, target: DependencyTarget, containers: Vec<ContainerSummary>) {
+        if let Some(dependents) = self.get_dependents().as_mut() {
+            dependents.update(target, containers);
+        }
+    }
+
+    fn open_dependents(&mut self) -> Option<AppEvent> {
+        let target = self.selected_dependency_target()?;
+        *self.get_dependents() = Some(DependentsPanel::new(target.clone()));
+        Some(AppEvent::ShowDependents(target))
+    }
+
+    /// Keeps the dependents panel in sync with the selected row.
+    fn retarget_dependents(&mut self) -> Option<AppEvent> {
+        let current = self.dependents_target()?;
+        self.selected_dependency_target()
+            .filter(|target| *target != current)
+            .map(AppEvent::ShowDependents)
+    }
+}
+
+pub struct DependentsPanel {
+    target: DependencyTarget,
+    containers: Vec<DependentContainer>,
+    state: TableState,
+    is_focused: bool,
+    style: TableStyle,
+}
+
+pub struct DependentContainer {
+    id: String,
+    name: String,
+    state: String,
+}
+
+impl DependentsPanel {
+    pub fn new(target: DependencyTarget) -> Self {
+        Self {
+            target,
+            containers: vec![],
+            state: TableState::default(),
+            is_focused: false,
+            style: TableStyle::default(),
+        }
+    }
+
+    pub fn target(&self) -> &DependencyTarget {
+        &self.target
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.is_focused
+    }
+
+    pub fn focus(&mut self) {
+        self.is_focused = !self.containers.is_empty();
+        if self.is_focused && self.state.selected().is_none() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn update(&mut self, target: DependencyTarget, containers: Vec<ContainerSummary>) {
+        if self.target != target {
+            self.state.select(None);
+            self.is_focused = false;
+        }
+
+        self.containers = DependentContainer::from_list(&containers, &target);
+        self.target = target;
+
+        if self.containers.is_empty() {
+            self.is_focused = false;
+        }
+    }
+
+    pub fn selected_container_id(&self) -> Option<String> {
+        self.state.selected()
+            .and_then(|i| self.containers.get(i))
+            .map(|c| c.id.clone())
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> PopupAction {
+        let last_index = self.containers.len().saturating_sub(1);
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'u') => return PopupAction::Close,
+            KeyCode::Enter => return PopupAction::Submit,
+            KeyCode::Tab => self.is_focused = false,
+            KeyCode::Down | KeyCode::Char('j') => {
+                let next = self.state.selected().map_or(0, |i| if i >= last_index { 0 } else { i + 1 });
+                self.state.select(Some(next));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let previous = self.state.selected().map_or(0, |i| if i == 0 { last_index } else { i - 1 });
+                self.state.select(Some(previous));
+            }
+            _ => {}
+        }
+
+        PopupAction::None
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        let border_color = if self.is_focused { tailwind::BLUE.c400 } else { tailwind::SLATE.c600 };
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(border_color))
+            .title(Line::from(format!(" Used by {} container(s) ", self.containers.len())).fg(tailwind::SLATE.c200))
+            .title_bottom(Line::from(" <Tab> focus | <Ent> details | <U> close ").fg(tailwind::SLATE.c400));
+
+        let header = ["Name", "State"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style);
+
+        let rows = self.containers.iter().map(|c| {
+            let state_style = if is_container_running(&c.state) { Style::new().green() } else { Style::new().red() };
+            Row::new([Cell::from(c.name.clone()), Cell::from(c.state.clone()).style(state_style)])
+                .style(self.style.row_style)
+        });
+
+        let highlight_style = if self.is_focused { self.style.selected_row_style } else { Style::default() };
+        let table = Table::new(rows, [Constraint::Min(10), Constraint::Length(10)])
+            .header(header)
+            .block(block)
+            .row_highlight_style(highlight_style)
+            .highlight_spacing(HighlightSpacing::Always)
+            .highlight_symbol(" ● ");
+
+        frame.render_stateful_widget(table, area, &mut self.state);
+    }
+}
+
+impl DependentContainer {
+    fn from_list(containers: &[ContainerSummary], target: &DependencyTarget) -> Vec<Self> {
+        let mut result = containers.iter()
+            .filter(|c| is_dependent(c, target))
+            .map(Self::from)
+            .collect::<Vec<Self>>();
+
+        result.sort_by(|p, n| n.state.cmp(&p.state).then(p.name.cmp(&n.name)));
+        result
+    }
+
+    fn from(container: &ContainerSummary) -> Self {
+        let name = container.names.as_deref()
+            .and_then(|names| names.first())
+            .and_then(|name| name.strip_prefix("/"))
+            .map_or("NaN".to_string(), |name| name.to_string());
+
+        Self {
+            id: container.id.as_deref().unwrap_or("-").to_string(),
+            name,
+            state: container.state.map_or("-".to_string(), |state| state.to_string()),
+        }
+    }
+}
+
+fn is_dependent(container: &ContainerSummary, target: &DependencyTarget) -> bool {
+    match target {
+        DependencyTarget::Image(id) => container.image_id.as_deref()
+            .is_some_and(|image_id| image_id.trim_start_matches("sha256:") == id),
+        DependencyTarget::Volume(name) => container.mounts.iter().flatten()
+            .any(|m| m.typ == Some(MountPointTypeEnum::VOLUME) && m.name.as_deref() == Some(name)),
+        DependencyTarget::Network(name) => container.network_settings.as_ref()
+            .and_then(|settings| settings.networks.as_ref())
+            .is_some_and(|networks| networks.contains_key(name)),
+    }
+}
+
+/// Splits `area` into a table area and, if the panel is shown, a side panel area.
+pub fn split_panel_area(area: Rect, has_panel: bool) -> (Rect, Rect) {
+    if !has_panel {
+        return (area, Rect::default());
+    }
+
+    let [table_area, panel_area] = Layout::horizontal([Constraint::Min(0), Constraint::Percentage(35)]).areas(area);
+    (table_area, panel_area)
+}
+
+/// Handles the keys meant for the panel, returning `None` if the key should go to the table.
+pub fn handle_panel_key_event(panel: &mut Option<DependentsPanel>, key_event: KeyEvent) -> Option<Option<AppEvent>> {
+    let dependents = panel.as_mut()?;
+
+    if !dependents.is_focused() {
+        return match key_event.code {
+            KeyCode::Tab => {
+                dependents.focus();
+                Some(None)
+            }
+            KeyCode::Char('u') | KeyCode::Esc => {
+                *panel = None;
+                Some(None)
+            }
+            _ => None,
+        };
+    }
+
+    let event = match dependents.handle_key_event(key_event) {
+        PopupAction::None => None,
+        PopupAction::Close => {
+            *panel = None;
+            None
+        }
+        PopupAction::Submit => dependents.selected_container_id().map(AppEvent::GoToContainerDetails),
+    };
+
+    Some(event)
+}
@@ -0,0 +1,452 @@
+use std::collections::BTreeMap;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize, palette::tailwind},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Cell, Clear, HighlightSpacing, Paragraph, Row, Table, TableState},
+};
+
+use crate::docker::filesystem::{FileChange, FileEntry, FileKind, join_path, parent_path, sort_entries};
+use crate::event::AppEvent;
+
+use super::common::{PopupAction, TableStyle, popup_area, render_footer, size_string};
+use super::input_form::InputForm;
+
+const MAX_PREVIEW_SIZE: i64 = 64 * 1024;
+const DEFAULT_FOOTER: &str = " <Esc/Q> close | <Ent> open | <Backspace/H> up | <W> download | <U> upload here | <C> changes | <R> refresh";
+
+/// Navigable listing of a directory tree inside a container.
+pub struct FileBrowser {
+    title: String,
+    container_id: String,
+    root: String,
+    path: String,
+    entries: Vec<FileEntry>,
+    /// The directories read from the archive of the container, so navigating doesn't read it again.
+    tree: Option<BTreeMap<String, Vec<FileEntry>>>,
+    state: TableState,
+    preview: Option<FilePreview>,
+    form: Option<(TransferForm, InputForm)>,
+    changes: BTreeMap<String, FileChange>,
+    change_list: Option<usize>,
+    select_after_load: Option<String>,
+    is_helper: bool,
+    style: TableStyle,
+    err: Option<String>,
+}
+
+/// The copy a form was opened for, a download carrying the container path to copy.
+enum TransferForm {
+    Download(String),
+    Upload,
+}
+
+struct FilePreview {
+    path: String,
+    lines: Vec<String>,
+    scroll: usize,
+}
+
+impl FileBrowser {
+    /// Creates a browser rooted at `root`, `is_helper` marking containers started only for browsing.
+    pub fn new(title: String, container_id: String, root: &str, is_helper: bool) -> Self {
+        Self {
+            title,
+            container_id,
+            root: root.to_string(),
+            path: root.to_string(),
+            entries: vec![],
+            tree: None,
+            state: TableState::default(),
+            preview: None,
+            form: None,
+            changes: BTreeMap::new(),
+            change_list: None,
+            select_after_load: None,
+            is_helper,
+            style: TableStyle::default(),
+            err: None,
+        }
+    }
+
+    pub fn container_id(&self) -> &str {
+        &self.container_id
+    }
+
+    pub fn is_helper(&self) -> bool {
+        self.is_helper
+    }
+
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Whether the archive of the container has to be read before listing. Helper containers are
+    /// listed with a command instead.
+    pub fn needs_tree(&self) -> bool {
+        !self.is_helper && self.tree.is_none()
+    }
+
+    /// Replaces what is known below `dir` with `tree`, read from an archive of `dir`.
+    pub fn update_tree(&mut self, dir: &str, tree: BTreeMap<String, Vec<FileEntry>>) {
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        let known = self.tree.get_or_insert_default();
+        known.retain(|path, _| path != dir && !path.starts_with(&prefix));
+        known.extend(tree);
+    }
+
+    pub fn tree_entries(&self, path: &str) -> Option<Vec<FileEntry>> {
+        self.tree.as_ref()?.get(path).cloned()
+    }
+
+    /// Lists the entries of `path`, along with the paths deleted from it compared to the image.
+    pub fn update_entries(&mut self, path: String, mut entries: Vec<FileEntry>) {
+        if path != self.path {
+            self.state.select(None);
+        }
+
+        let deleted = self.changes.iter()
+            .filter(|(changed, change)| **change == FileChange::Deleted && parent_path(changed) == path)
+            .filter_map(|(changed, _)| changed.rsplit('/').next())
+            .filter(|name| !entries.iter().any(|e| e.name == *name))
+            .map(|name| FileEntry { name: name.to_string(), kind: FileKind::Other, size: -1 })
+            .collect::<Vec<FileEntry>>();
+        entries.extend(deleted);
+        sort_entries(&mut entries);
+
+        self.path = path;
+        self.entries = entries;
+
+        if let Some(name) = self.select_after_load.take() {
+            self.state.select(self.entries.iter().position(|e| e.name == name));
+        }
+
+        if self.state.selected().is_none() && !self.entries.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn update_changes(&mut self, changes: Vec<(String, FileChange)>) {
+        self.changes = changes.into_iter().collect();
+    }
+
+    pub fn show_file(&mut self, path: String, content: Vec<u8>) {
+        match String::from_utf8(content) {
+            Ok(text) => {
+                let lines = text.lines().map(|l| l.replace('\t', "    ")).collect();
+                self.preview = Some(FilePreview { path, lines, scroll: 0 });
+            }
+            Err(_) => self.show_err("Binary file, use <W> to download it"),
+        }
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        if let Some(preview) = self.preview.as_mut() {
+            match key_event.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.preview = None,
+                KeyCode::Down | KeyCode::Char('j') => {
+                    preview.scroll = (preview.scroll + 1).min(preview.lines.len().saturating_sub(1))
+                }
+                KeyCode::Up | KeyCode::Char('k') => preview.scroll = preview.scroll.saturating_sub(1),
+                _ => {}
+            }
+            return None;
+        }
+
+        if self.form.is_some() {
+            return self.handle_form_key_event(key_event);
+        }
+
+        if self.change_list.is_some() {
+            return self.handle_change_list_key_event(key_event);
+        }
+
+        if self.err.take().is_some() {
+            return None;
+        }
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => Some(AppEvent::CloseFileBrowser),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.select_offset(1);
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.select_offset(-1);
+                None
+            }
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => self.open_selected(),
+            KeyCode::Backspace | KeyCode::Left | KeyCode::Char('h') if self.path != self.root => {
+                Some(AppEvent::ListDirectory(self.container_id.clone(), parent_path(&self.path)))
+            }
+            KeyCode::Char('r') => {
+                self.tree = None;
+                Some(AppEvent::ListDirectory(self.container_id.clone(), self.path.clone()))
+            }
+            KeyCode::Char('w') => {
+                if let Some(entry) = self.selected_entry() {
+                    let path = join_path(&self.path, &entry.name);
+                    let form = InputForm::new(&format!("Download {}", entry.name))
+                        .with_field("dest", "Destination directory", ".");
+                    self.form = Some((TransferForm::Download(path), form));
+                }
+                None
+            }
+            KeyCode::Char('u') => {
+                self.open_upload_form();
+                None
+            }
+            KeyCode::Char('c') => {
+                self.open_change_list();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        use Constraint::{Length, Min};
+
+        let [content_area, footer_area] = Layout::vertical([Min(0), Length(3)]).areas(area);
+
+        let relative_path = self.path.strip_prefix(&self.root).unwrap_or(&self.path);
+        let title = format!("{} | {}", self.title, if relative_path.is_empty() { "/" } else { relative_path });
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(tailwind::BLUE.c400))
+            .title(Line::from(title).fg(tailwind::SLATE.c200));
+
+        let header = ["Name", "Type", "Size", "Change"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style);
+
+        let rows = self.entries.iter().enumerate().map(|(index, entry)| {
+            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            let (name, kind) = match entry.kind {
+                FileKind::Directory => (format!("{}/", entry.name), "dir"),
+                FileKind::File => (entry.name.clone(), "file"),
+                FileKind::Link => (entry.name.clone(), "link"),
+                FileKind::Other => (entry.name.clone(), "other"),
+            };
+            let size = if entry.kind == FileKind::File { size_string(entry.size) } else { "-".to_string() };
+
+            match self.change_of(&entry.name) {
+                Some(FileChange::Deleted) => Row::new([name, "-".to_string(), "-".to_string(), "deleted".to_string()])
+                    .style(row_style.fg(change_color(FileChange::Deleted)).crossed_out()),
+                Some(change) => Row::new([name, kind.to_string(), size, change.to_string()])
+                    .style(row_style.fg(change_color(change))),
+                None => Row::new([name, kind.to_string(), size, String::new()]).style(row_style),
+            }
+        });
+
+        let table = Table::new(rows, [Min(20), Length(8), Length(12), Length(9)])
+            .header(header)
+            .block(block)
+            .row_highlight_style(self.style.selected_row_style)
+            .highlight_symbol(Text::from(" ● "))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, content_area, &mut self.state);
+
+        let (footer_text, border_style) = match &self.err {
+            Some(err) => (err.clone(), Some(Style::new().red())),
+            None => (DEFAULT_FOOTER.to_string(), None),
+        };
+        render_footer(frame, footer_area, footer_text, border_style);
+
+        if let Some(selected) = self.change_list {
+            draw_change_list(frame, area, &self.changes, selected);
+        }
+
+        if let Some(preview) = &self.preview {
+            draw_preview(frame, area, preview);
+        }
+
+        if let Some((_, form)) = &self.form {
+            form.draw(frame, area);
+        }
+    }
+
+    /// Volumes are mounted read-only for browsing, so only containers can be copied into.
+    fn open_upload_form(&mut self) {
+        if self.is_helper {
+            self.show_err("Volumes are browsed read-only, files can only be copied into containers");
+            return;
+        }
+
+        let form = InputForm::new(&format!("Upload to {}", self.path)).with_field("source", "Host file or directory", "");
+        self.form = Some((TransferForm::Upload, form));
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let (kind, form) = self.form.as_mut()?;
+
+        match form.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => {
+                let event = match kind {
+                    TransferForm::Download(path) => {
+                        AppEvent::DownloadPath(self.container_id.clone(), path.clone(), form.value("dest"))
+                    }
+                    TransferForm::Upload => match form.value("source") {
+                        source if source.is_empty() => {
+                            form.set_err("Host path is required");
+                            return None;
+                        }
+                        source => AppEvent::UploadPath(self.container_id.clone(), source, self.path.clone()),
+                    },
+                };
+                self.form = None;
+                Some(event)
+            }
+        }
+    }
+
+    /// Helper containers are created from a plain image, so only containers have changes to show.
+    fn open_change_list(&mut self) {
+        match (self.is_helper, self.changes.is_empty()) {
+            (true, _) => self.show_err("Volumes have no image to compare to"),
+            (false, true) => self.show_err("No changes compared to the image"),
+            (false, false) => self.change_list = Some(0),
+        }
+    }
+
+    /// Moves through the changes, Enter going to the directory of the selected one.
+    fn handle_change_list_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let selected = self.change_list?;
+        let last_index = self.changes.len().saturating_sub(1);
+
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'c') => self.change_list = None,
+            KeyCode::Down | KeyCode::Char('j') => self.change_list = Some((selected + 1).min(last_index)),
+            KeyCode::Up | KeyCode::Char('k') => self.change_list = Some(selected.saturating_sub(1)),
+            KeyCode::Enter => {
+                let path = self.changes.keys().nth(selected)?.clone();
+                self.change_list = None;
+                self.select_after_load = path.rsplit('/').next().map(String::from);
+                return Some(AppEvent::ListDirectory(self.container_id.clone(), parent_path(&path)));
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn change_of(&self, name: &str) -> Option<FileChange> {
+        self.changes.get(&join_path(&self.path, name)).copied()
+    }
+
+    fn open_selected(&mut self) -> Option<AppEvent> {
+        let entry = self.selected_entry()?.clone();
+        let path = join_path(&self.path, &entry.name);
+
+        if self.change_of(&entry.name) == Some(FileChange::Deleted) {
+            self.show_err(&format!("{path} was deleted from the container"));
+            return None;
+        }
+
+        match entry.kind {
+            FileKind::Directory => Some(AppEvent::ListDirectory(self.container_id.clone(), path)),
+            FileKind::File if entry.size <= MAX_PREVIEW_SIZE => Some(AppEvent::ReadFile(self.container_id.clone(), path)),
+            FileKind::File => {
+                self.show_err(&format!("File is larger than {}, use <W> to download it", size_string(MAX_PREVIEW_SIZE)));
+                None
+            }
+            FileKind::Link | FileKind::Other => {
+                self.show_err("Only directories and regular files can be opened");
+                None
+            }
+        }
+    }
+
+    fn selected_entry(&self) -> Option<&FileEntry> {
+        self.state.selected().and_then(|i| self.entries.get(i))
+    }
+
+    fn select_offset(&mut self, offset: isize) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let count = self.entries.len() as isize;
+        let index = self.state.selected().unwrap_or(0) as isize;
+        self.state.select(Some((index + offset).rem_euclid(count) as usize));
+    }
+}
+
+fn change_color(change: FileChange) -> Color {
+    match change {
+        FileChange::Added => tailwind::GREEN.c400,
+        FileChange::Changed => tailwind::AMBER.c400,
+        FileChange::Deleted => tailwind::RED.c400,
+    }
+}
+
+/// Every path that differs from the image, as `docker diff` prints them.
+fn draw_change_list(frame: &mut Frame, area: Rect, changes: &BTreeMap<String, FileChange>, selected: usize) {
+    let area = popup_area(area, Constraint::Percentage(85), Constraint::Percentage(85));
+    frame.render_widget(Clear, area);
+
+    let block = Block::bordered()
+        .border_type(BorderType::Plain)
+        .border_style(Style::new().fg(tailwind::BLUE.c400))
+        .title(Line::from(format!(" Changes compared to the image ({}) ", changes.len())).fg(tailwind::SLATE.c200))
+        .title_bottom(Line::from(" <J/K> select | <Ent> go to | <Esc/Q> close ").fg(tailwind::SLATE.c400));
+
+    let lines = changes.iter().enumerate()
+        .map(|(index, (path, change))| {
+            let symbol = match change {
+                FileChange::Added => "A",
+                FileChange::Changed => "C",
+                FileChange::Deleted => "D",
+            };
+            let line = Line::from(format!(" {symbol} {path}")).fg(change_color(*change));
+            if index == selected { line.reversed() } else { line }
+        })
+        .collect::<Vec<Line>>();
+
+    // Keeps the selected change in view.
+    let height = area.height.saturating_sub(2) as usize;
+    let scroll = (selected + 1).saturating_sub(height);
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((scroll as u16, 0));
+
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_preview(frame: &mut Frame, area: Rect, preview: &FilePreview) {
+    let area = popup_area(area, Constraint::Percentage(85), Constraint::Percentage(85));
+    frame.render_widget(Clear, area);
+
+    let block = Block::bordered()
+        .border_type(BorderType::Plain)
+        .border_style(Style::new().fg(tailwind::BLUE.c400))
+        .title(Line::from(format!(" {} ", preview.path)).fg(tailwind::SLATE.c200))
+        .title_bottom(Line::from(" <J/K> scroll | <Esc/Q> close ").fg(tailwind::SLATE.c400));
+
+    let lines = preview.lines.iter().map(|l| Line::from(l.clone())).collect::<Vec<Line>>();
+    let paragraph = Paragraph::new(lines)
+        .style(Style::new().fg(tailwind::SLATE.c200))
+        .block(block)
+        .scroll((preview.scroll as u16, 0));
+
+    frame.render_widget(paragraph, area);
+}
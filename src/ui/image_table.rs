@@ -1,34 +1,58 @@
+use std::collections::{HashMap, HashSet};
+
 use bollard::secret::ImageSummary;
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     Frame,
     layout::{Constraint, Rect},
-    style::{Style, Stylize},
+    style::{Style, Stylize, palette::tailwind},
     text::Text,
     widgets::{Cell, HighlightSpacing, Row, Table},
 };
 
 use crate::{
+    docker::archive::ARCHIVE_EXTENSIONS,
     event::AppEvent,
+    utils::expand_home,
     ui::{
-        common::{TableStyle, render_footer, time_ago_string},
+        common::{PopupAction, TableStyle, render_footer, size_string, time_ago_string},
+        dependents_panel::{DependencyTarget, DependentsPanel, DependentsTable, handle_panel_key_event, split_panel_area},
+        input_form::InputForm,
+        prune_preview::PrunePreview,
+        run_form::{new_run_form, parse_run_form},
         resource_table::{ResourceTable, ResourceTableInfo},
     },
 };
 
 use regex::Regex;
+use strum_macros::Display;
 
 const REFRESH_AFTER_TICK: u8 = 10;
+const REFRESH_USAGE_AFTER_REFRESH: u8 = 10;
 const REGEX_DELETE_IMG_ERR: &str = r"\((?:cannot|must) be forced\) - image is being used by (?:running|stopped) container \w+";
-const DEFAULT_FOOTER: &str = " <Del/D> remove | <F> force remove";
+const DANGLING_TAG: &str = "<none>:<none>";
+const PRUNE_HINT: &str = " <Ent/Y> prune | <A> dangling/all unused | <Esc/Q> cancel";
 
 #[derive(Default)]
 pub struct ImageTable {
     style: TableStyle,
     skipped_tick_count_for_refresh: u8,
+    skipped_refresh_count_for_usage: u8,
     info: ResourceTableInfo<ImageTableRow>,
+    images: Vec<ImageSummary>,
+    usage: HashMap<String, ImageUsage>,
+    is_usage_loaded: bool,
+    sort_by: ImageSortBy,
+    filter: ImageFilter,
+    prune_preview: Option<PrunePreview>,
+    prune_dangling_only: bool,
+    marked: HashSet<String>,
+    form: Option<ImageForm>,
+    dependents: Option<DependentsPanel>,
+    highlighted: Option<String>,
     err: Option<String>,
+    notice: Option<String>,
 }
 
 #[derive(Default)]
@@ -36,7 +60,50 @@ pub struct ImageTableRow {
     id: String,
     tags: String,
     size: String,
+    shared_size: String,
+    unique_size: String,
+    containers: String,
     created: String,
+    size_bytes: i64,
+    unique_size_bytes: i64,
+    created_epoch: i64,
+    is_dangling: bool,
+    is_unused: bool,
+}
+
+#[derive(Default, Clone, Copy)]
+pub struct ImageUsage {
+    shared_size: i64,
+    containers: i64,
+}
+
+enum ImageForm {
+    Save(Vec<String>, InputForm),
+    Load(InputForm),
+    Run(String, InputForm),
+}
+
+#[derive(Default, Display, Clone, Copy, PartialEq)]
+enum ImageSortBy {
+    #[default]
+    #[strum(to_string = "Created")]
+    Created,
+
+    #[strum(to_string = "Size")]
+    Size,
+}
+
+#[derive(Default, Display, Clone, Copy, PartialEq)]
+enum ImageFilter {
+    #[default]
+    #[strum(to_string = "All")]
+    All,
+
+    #[strum(to_string = "Dangling")]
+    Dangling,
+
+    #[strum(to_string = "Unused")]
+    Unused,
 }
 
 impl ResourceTable for ImageTable {
@@ -47,7 +114,7 @@ impl ResourceTable for ImageTable {
     }
 
     fn render_table(&mut self, frame: &mut Frame, area: Rect) {
-        let header = ["ID", "Tags", "Size", "Created"].into_iter()
+        let header = ["ID", "Tags", "Size", "Shared", "Unique", "Containers", "Created"].into_iter()
             .map(Cell::from)
             .collect::<Row>()
             .style(self.style.header_style)
@@ -56,8 +123,18 @@ impl ResourceTable for ImageTable {
         self.info.row_heights.clear();
 
         let rows = self.info.items.iter().enumerate().map(|(index, image)| {
-            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            let mut row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            if image.is_dangling {
+                row_style = row_style.fg(tailwind::AMBER.c400);
+            } else if image.is_unused {
+                row_style = row_style.fg(tailwind::SLATE.c500);
+            }
+            if self.highlighted.as_ref() == Some(&image.id) {
+                row_style = row_style.fg(tailwind::GREEN.c400).bold();
+            }
+
             let item = image.ref_array();
+            let is_marked = self.marked.contains(&image.id);
             let tags: Vec<&str> = image.tags.split("\n").filter(|s| !s.is_empty()).collect();
 
             let height = if tags.is_empty() { 3 } else { tags.len() + 2 };
@@ -66,7 +143,11 @@ impl ResourceTable for ImageTable {
             }
 
             item.into_iter()
-                .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                .enumerate()
+                .map(|(column, content)| {
+                    let mark = if column == 0 && is_marked { "✔ " } else { "" };
+                    Cell::from(Text::from(format!("\n{mark}{content}\n")))
+                })
                 .collect::<Row>()
                 .style(row_style)
                 .height(height as u16)
@@ -75,8 +156,11 @@ impl ResourceTable for ImageTable {
         let widths = vec![
             Constraint::Length(15),
             Constraint::Min(15),
-            Constraint::Min(0),
-            Constraint::Length(27),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(10),
+            Constraint::Length(16),
         ];
 
         let table = Table::new(rows, widths)
@@ -90,7 +174,12 @@ impl ResourceTable for ImageTable {
 
     fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
         let mut border_style = None;
-        let mut footer_text = DEFAULT_FOOTER.to_string();
+        let mut footer_text = get_footer_text(self.sort_by, self.filter);
+
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
 
         if let Some(err) = &self.err {
             border_style = Some(Style::new().red());
@@ -101,26 +190,96 @@ impl ResourceTable for ImageTable {
     }
 }
 
+impl DependentsTable for ImageTable {
+    fn get_dependents(&mut self) -> &mut Option<DependentsPanel> {
+        &mut self.dependents
+    }
+
+    fn selected_dependency_target(&mut self) -> Option<DependencyTarget> {
+        self.get_selected_row().map(|r| DependencyTarget::Image(r.id.clone()))
+    }
+}
+
 impl ImageTable {
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
-        if self.err.is_some() {
+        if self.prune_preview.is_some() {
+            return Ok(self.handle_prune_key_event(key_event));
+        }
+
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if let Some(event) = handle_panel_key_event(&mut self.dependents, key_event) {
+            return Ok(event);
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
             self.err = None;
+            self.notice = None;
+            self.highlighted = None;
             return Ok(None);
         }
 
         let event = match key_event.code {
+            KeyCode::Char('s') => {
+                self.toggle_sort();
+                None
+            }
+            KeyCode::Char('t') => {
+                self.toggle_filter();
+                None
+            }
+            KeyCode::Char('p') => {
+                self.prune_dangling_only = true;
+                Some(AppEvent::PreviewImagePrune)
+            }
+            KeyCode::Char(' ') => {
+                self.toggle_mark();
+                None
+            }
+            KeyCode::Char('e') => {
+                self.open_save_form();
+                None
+            }
+            KeyCode::Char('u') => self.open_dependents(),
+            KeyCode::Char('r') => {
+                self.form = self.get_selected_row()
+                    .map(|i| i.reference())
+                    .map(|image| ImageForm::Run(image.clone(), new_run_form(&image)));
+                None
+            }
+            KeyCode::Char('i') => {
+                self.form = Some(ImageForm::Load(InputForm::new("Load images").with_field("path", "Archive path", "")));
+                None
+            }
             KeyCode::Delete | KeyCode::Char('d') => self.get_selected_row()
                 .map(|i| AppEvent::RemoveImage(i.id.clone(), false)),
             KeyCode::Char('f') => self.get_selected_row()
                 .map(|i| AppEvent::RemoveImage(i.id.clone(), true)),
-            _ => self.handle_nav_key_event(key_event)?,
+            _ => self.handle_nav_key_event(key_event)?.or_else(|| self.retarget_dependents()),
         };
 
         Ok(event)
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
-        self.draw_default(frame, area)
+        let (table_area, panel_area) = split_panel_area(area, self.dependents.is_some());
+        let result = self.draw_default(frame, table_area);
+
+        if let Some(dependents) = self.dependents.as_mut() {
+            dependents.draw(frame, panel_area);
+        }
+
+        if let Some(preview) = &self.prune_preview {
+            preview.draw(frame, area);
+        }
+
+        if let Some(ImageForm::Save(_, form) | ImageForm::Load(form) | ImageForm::Run(_, form)) = &self.form {
+            form.draw(frame, area);
+        }
+
+        result
     }
 
     pub fn tick(&mut self) -> Result<Option<AppEvent>> {
@@ -130,7 +289,29 @@ impl ImageTable {
         }
 
         self.skipped_tick_count_for_refresh = 0;
-        Ok(Some(AppEvent::UpdateImages))
+
+        // `system df` is expensive on hosts with many images, so the usage data is
+        // refreshed less often than the image list itself.
+        let event = if self.skipped_refresh_count_for_usage == 0 {
+            AppEvent::UpdateImageUsage
+        } else {
+            AppEvent::UpdateImages
+        };
+
+        self.skipped_refresh_count_for_usage = (self.skipped_refresh_count_for_usage + 1) % REFRESH_USAGE_AFTER_REFRESH;
+        Ok(Some(event))
+    }
+
+    pub fn update_images(&mut self, images: Vec<ImageSummary>) {
+        self.images = images;
+        self.refresh_rows();
+    }
+
+    pub fn update_usage(&mut self, images: Vec<ImageSummary>) {
+        self.usage = images.iter()
+            .map(|i| (i.id.clone(), ImageUsage { shared_size: i.shared_size, containers: i.containers }))
+            .collect();
+        self.is_usage_loaded = true;
     }
 
     pub fn show_remove_image_err(&mut self, err: String) {
@@ -142,28 +323,276 @@ impl ImageTable {
 
         self.err = Some(format!("[ERR] {}", err_msg.trim()))
     }
+
+    pub fn show_prune_result(&mut self, deleted_count: usize, space_reclaimed: i64) {
+        self.notice = Some(format!(
+            "[OK] Pruned {deleted_count} image(s), reclaimed {}",
+            size_string(space_reclaimed)
+        ));
+    }
+
+    pub fn show_prune_err(&mut self, err: String) {
+        self.err = Some(format!("[ERR] Prune failed: {err}"));
+    }
+
+    pub fn is_form_open(&self) -> bool {
+        self.form.is_some()
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    /// Selects and highlights an image created from a container, showing all images so the
+    /// current filter can't hide it.
+    pub fn show_committed(&mut self, id: &str, reference: &str) {
+        let id = id.trim_start_matches("sha256:").to_string();
+        self.filter = ImageFilter::All;
+        self.refresh_rows();
+
+        if let Some(index) = self.info.items.iter().position(|r| r.id == id) {
+            self.select_row(index);
+        }
+        self.notice = Some(format!("[OK] Committed {reference}"));
+        self.highlighted = Some(id);
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let action = match self.form.as_mut()? {
+            ImageForm::Save(_, form) | ImageForm::Load(form) | ImageForm::Run(_, form) => form.handle_key_event(key_event),
+        };
+
+        match action {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => self.submit_form(),
+        }
+    }
+
+    fn submit_form(&mut self) -> Option<AppEvent> {
+        let event = match self.form.as_mut()? {
+            ImageForm::Save(names, form) => {
+                let path = expand_home(&form.value("path"));
+                if !ARCHIVE_EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+                    form.set_err("Path must end with .tar, .tar.gz or .tgz");
+                    return None;
+                }
+                AppEvent::SaveImages(names.clone(), path)
+            }
+            ImageForm::Load(form) => {
+                let path = expand_home(&form.value("path"));
+                if !std::path::Path::new(&path).is_file() {
+                    form.set_err("File not found");
+                    return None;
+                }
+                AppEvent::LoadImages(path)
+            }
+            ImageForm::Run(image, form) => match parse_run_form(image, form) {
+                Ok(config) => AppEvent::RunContainer(config),
+                Err(err) => {
+                    form.set_err(&err);
+                    return None;
+                }
+            },
+        };
+
+        self.form = None;
+        self.marked.clear();
+        Some(event)
+    }
+
+    fn toggle_mark(&mut self) {
+        if let Some(id) = self.get_selected_row().map(|i| i.id.clone())
+            && !self.marked.remove(&id) {
+            self.marked.insert(id);
+        }
+    }
+
+    fn open_save_form(&mut self) {
+        let rows = if self.marked.is_empty() {
+            self.get_selected_row().into_iter().collect::<Vec<&ImageTableRow>>()
+        } else {
+            self.info.items.iter().filter(|i| self.marked.contains(&i.id)).collect()
+        };
+
+        if rows.is_empty() {
+            return;
+        }
+
+        // Images are saved by tag when possible, otherwise the tags are lost on load.
+        let names = rows.iter()
+            .flat_map(|r| if r.has_tags() { r.tags.lines().map(String::from).collect() } else { vec![r.id.clone()] })
+            .collect::<Vec<String>>();
+
+        let file_name = match rows.as_slice() {
+            [row] if row.has_tags() => row.tags.lines().next().unwrap_or(&row.id).replace(['/', ':'], "_"),
+            [row] => row.id[..12].to_string(),
+            _ => "images".to_string(),
+        };
+
+        let title = format!("Save {} image(s)", rows.len());
+        let form = InputForm::new(&title).with_field("path", "Archive path (.tar, .tar.gz)", &format!("./{file_name}.tar"));
+        self.form = Some(ImageForm::Save(names, form));
+    }
+
+    fn handle_prune_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        if key_event.code == KeyCode::Char('a') {
+            self.prune_dangling_only = !self.prune_dangling_only;
+            self.open_prune_preview();
+            return None;
+        }
+
+        let action = self.prune_preview.as_mut().map_or(PopupAction::Close, |p| p.handle_key_event(key_event));
+        match action {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.prune_preview = None;
+                None
+            }
+            PopupAction::Submit => {
+                self.prune_preview = None;
+                Some(AppEvent::PruneImages(self.prune_dangling_only))
+            }
+        }
+    }
+
+    pub fn open_prune_preview(&mut self) {
+        let title = if self.prune_dangling_only { "Prune dangling images" } else { "Prune all unused images" };
+
+        // Without the usage data every image would look in use, so nothing can be confirmed.
+        if !self.is_usage_loaded {
+            let preview = PrunePreview::new(title.to_string(), vec![], 0)
+                .with_hint(PRUNE_HINT)
+                .with_empty_text(" Couldn't load the image usage, so the images to prune are unknown");
+            self.prune_preview = Some(preview);
+            return;
+        }
+
+        // Mirrors the daemon's prune rules: dangling-only prune removes untagged leaf
+        // images without containers, a full prune removes every image without containers.
+        let rows = ImageTableRow::from_list(&self.images, &self.usage);
+        let candidates = rows.iter()
+            .filter(|r| r.is_unused && (r.is_dangling || !self.prune_dangling_only))
+            .collect::<Vec<&ImageTableRow>>();
+
+        let items = candidates.iter()
+            .map(|r| format!("{} {} ({})", &r.id[..12], r.tags.replace("\n", ", "), r.unique_size))
+            .collect::<Vec<String>>();
+        let reclaimable = candidates.iter().map(|r| r.unique_size_bytes).sum();
+        self.prune_preview = Some(PrunePreview::new(title.to_string(), items, reclaimable).with_hint(PRUNE_HINT));
+    }
+
+    fn refresh_rows(&mut self) {
+        let mut rows = ImageTableRow::from_list(&self.images, &self.usage);
+        rows.retain(|r| match self.filter {
+            ImageFilter::All => true,
+            ImageFilter::Dangling => r.is_dangling,
+            ImageFilter::Unused => r.is_unused,
+        });
+        sort_rows(&mut rows, self.sort_by);
+        self.update_with_items(rows);
+    }
+
+    fn toggle_sort(&mut self) {
+        self.sort_by = match self.sort_by {
+            ImageSortBy::Created => ImageSortBy::Size,
+            ImageSortBy::Size => ImageSortBy::Created,
+        };
+        self.refresh_rows();
+    }
+
+    fn toggle_filter(&mut self) {
+        self.filter = match self.filter {
+            ImageFilter::All => ImageFilter::Dangling,
+            ImageFilter::Dangling => ImageFilter::Unused,
+            ImageFilter::Unused => ImageFilter::All,
+        };
+        self.refresh_rows();
+        self.select_row(0);
+    }
 }
 
 impl ImageTableRow {
-    const fn ref_array(&self) -> [&String; 4] {
-        [&self.id, &self.tags, &self.size, &self.created]
+    const fn ref_array(&self) -> [&String; 7] {
+        [
+            &self.id,
+            &self.tags,
+            &self.size,
+            &self.shared_size,
+            &self.unique_size,
+            &self.containers,
+            &self.created,
+        ]
+    }
+
+    fn has_tags(&self) -> bool {
+        self.tags != DANGLING_TAG
+    }
+
+    /// Returns the first tag of the image, or its ID if it has none.
+    fn reference(&self) -> String {
+        self.tags.lines().next().filter(|_| self.has_tags()).unwrap_or(&self.id).to_string()
     }
 
-    pub fn from_list(images: Vec<ImageSummary>) -> Vec<Self> {
-        let mut result = images.clone();
-        result.sort_by_key(|i| i.created);
-        result.reverse();
-        result.iter().map(Self::from).collect::<Vec<Self>>()
+    pub fn from_list(images: &[ImageSummary], usage: &HashMap<String, ImageUsage>) -> Vec<Self> {
+        let parent_ids = images.iter()
+            .map(|i| i.parent_id.as_str())
+            .filter(|id| !id.is_empty())
+            .collect::<HashSet<&str>>();
+
+        images.iter()
+            .map(|i| Self::from(i, usage.get(&i.id).copied(), parent_ids.contains(i.id.as_str())))
+            .collect::<Vec<Self>>()
     }
 
-    fn from(image: &ImageSummary) -> Self {
+    fn from(image: &ImageSummary, usage: Option<ImageUsage>, has_children: bool) -> Self {
         let id = image.id.split(":").collect::<Vec<&str>>()[1].to_string();
 
+        let tags = image.repo_tags.iter()
+            .filter(|t| t.as_str() != DANGLING_TAG)
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let (shared_size, unique_size, containers) = match usage {
+            Some(u) if u.shared_size >= 0 => (
+                size_string(u.shared_size),
+                size_string(image.size - u.shared_size),
+                if u.containers >= 0 { u.containers.to_string() } else { "-".to_string() },
+            ),
+            _ => ("-".to_string(), "-".to_string(), "-".to_string()),
+        };
+
+        let unique_size_bytes = usage
+            .filter(|u| u.shared_size >= 0)
+            .map_or(image.size, |u| image.size - u.shared_size);
+
         Self {
             id,
-            tags: image.repo_tags.join("\n"),
-            size: image.size.to_string(),
+            tags: if tags.is_empty() { DANGLING_TAG.to_string() } else { tags.join("\n") },
+            size: size_string(image.size),
+            shared_size,
+            unique_size,
+            containers,
             created: time_ago_string(image.created),
+            size_bytes: image.size,
+            unique_size_bytes,
+            created_epoch: image.created,
+            is_dangling: tags.is_empty() && !has_children,
+            is_unused: usage.is_some_and(|u| u.containers == 0),
         }
     }
 }
+
+fn sort_rows(rows: &mut [ImageTableRow], sort_by: ImageSortBy) {
+    match sort_by {
+        ImageSortBy::Created => rows.sort_by_key(|r| std::cmp::Reverse(r.created_epoch)),
+        ImageSortBy::Size => rows.sort_by_key(|r| std::cmp::Reverse(r.size_bytes)),
+    }
+}
+
+fn get_footer_text(sort_by: ImageSortBy, filter: ImageFilter) -> String {
+    format!(" <S> sort: {sort_by} | <T> {filter} | <P> prune | <U> used by | <R> run | <Space> mark | <E> save | <I> load | <Del/D> remove | <F> force remove")
+}
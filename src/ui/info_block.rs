@@ -1,7 +1,17 @@
+use std::collections::HashMap;
+
 use crate::event::AppEvent;
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
-use ratatui::{Frame, layout::Rect, widgets::ScrollbarState};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Styled, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Paragraph, ScrollbarState},
+};
+
+use super::common::{render_footer, render_scrollbar};
 
 #[derive(Default, Clone)]
 pub struct ScrollInfo {
@@ -39,8 +49,66 @@ pub trait ScrollableInfoBlock {
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()>;
 
+    fn draw_default(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        title: String,
+        content_lines: Vec<Line<'static>>,
+        footer_text: String,
+        footer_style: Option<Style>,
+    ) -> Result<()> {
+        use Constraint::{Length, Min};
+
+        let vertical_layout = Layout::vertical([Min(0), Length(1), Length(3)]);
+        let [content_area, horizontal_scrollbar_area, footer_area] = vertical_layout.areas(area);
+
+        let horizontal_layout = Layout::horizontal([Min(0), Length(1)]);
+        let [info_area, vertical_scrollbar_area] = horizontal_layout.areas(content_area);
+
+        let max_horizontal = content_lines.iter().fold(0, |max, line| {
+            let line_len = line.to_string().len();
+            if line_len > max { line_len } else { max }
+        });
+
+        let scroll_info = self.get_scroll_info();
+        scroll_info.max_horizontal = max_horizontal.saturating_sub(info_area.width as usize - 2);
+        scroll_info.max_vertical = content_lines.len().saturating_sub(info_area.height as usize - 2);
+
+        render_content(frame, info_area, title, content_lines, scroll_info);
+
+        scroll_info.vertical_state = scroll_info.vertical_state
+            .content_length(scroll_info.max_vertical)
+            .position(scroll_info.vertical);
+
+        render_scrollbar(
+            frame,
+            vertical_scrollbar_area,
+            &mut scroll_info.vertical_state,
+            true,
+        );
+
+        scroll_info.horizontal_state = scroll_info.horizontal_state
+            .content_length(scroll_info.max_horizontal)
+            .position(scroll_info.horizontal);
+
+        render_scrollbar(
+            frame,
+            horizontal_scrollbar_area,
+            &mut scroll_info.horizontal_state,
+            false,
+        );
+
+        render_footer(frame, footer_area, footer_text, footer_style);
+
+        Ok(())
+    }
+
     fn tick(&mut self) -> Result<Option<AppEvent>>;
 
+    /// Shows an error in the footer until the next key press. Blocks without actions ignore it.
+    fn show_err(&mut self, _err: &str) {}
+
     fn update_data(&mut self, data: Self::Data);
 
     fn get_scroll_info(&mut self) -> &mut ScrollInfo;
@@ -93,3 +161,52 @@ pub trait ScrollableInfoBlock {
         scroll_info.vertical = scroll_info.max_vertical;
     }
 }
+
+fn render_content(frame: &mut Frame, area: Rect, title: String, lines: Vec<Line<'static>>, scroll_info: &ScrollInfo) {
+    let block_style = Style::new().fg(tailwind::BLUE.c400);
+
+    let block = Block::bordered()
+        .border_type(BorderType::Plain)
+        .border_style(block_style)
+        .title(Line::from(title).fg(tailwind::SLATE.c200));
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((scroll_info.vertical as u16, scroll_info.horizontal as u16))
+        .left_aligned();
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Renders `(key, content)` pairs as lines, skipping the ones without a value.
+pub fn get_key_value_lines(lines: Vec<(String, String)>) -> Vec<Line<'static>> {
+    let key_style = Style::new().fg(Color::Green);
+
+    lines
+        .into_iter()
+        .filter(|(_, content)| !content.eq("-"))
+        .map(|(key, content)| Line::from_iter([key.set_style(key_style), content.into()]))
+        .collect()
+}
+
+pub fn get_filtered_list(data: &str) -> Option<Vec<(String, String)>> {
+    let mut splitted_data: Vec<String> = data.split("\n")
+        .filter(|p| !p.is_empty())
+        .map(|s| s.to_string())
+        .collect();
+
+    if splitted_data.is_empty() {
+        return None;
+    }
+
+    splitted_data.sort_unstable();
+    Some(splitted_data.iter().map(|d| ("".to_string(), format!(" - {d}"))).collect())
+}
+
+/// Joins map entries into one `key{separator}value` line each, as expected by [`get_filtered_list`].
+pub fn join_map(map: &HashMap<String, String>, separator: &str) -> String {
+    map.iter()
+        .map(|(k, v)| format!("{k}{separator}{v}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
@@ -0,0 +1,194 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Position, Rect},
+    style::{Color, Style, Styled, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph},
+};
+
+use super::common::{PopupAction, popup_area};
+
+const DEFAULT_HINT: &str = " <Tab/↓> next | <←/→> change option | <Ent> submit | <Esc> cancel";
+
+pub struct InputForm {
+    title: String,
+    fields: Vec<InputField>,
+    focused: usize,
+    err: Option<String>,
+}
+
+struct InputField {
+    key: &'static str,
+    label: String,
+    value: String,
+    options: Vec<String>,
+    is_secret: bool,
+}
+
+impl InputField {
+    fn is_choice(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    fn select_option(&mut self, offset: isize) {
+        let count = self.options.len() as isize;
+        let index = self.options.iter().position(|o| *o == self.value).unwrap_or(0) as isize;
+        self.value = self.options[(index + offset).rem_euclid(count) as usize].clone();
+    }
+}
+
+impl InputForm {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            fields: vec![],
+            focused: 0,
+            err: None,
+        }
+    }
+
+    pub fn with_field(mut self, key: &'static str, label: &str, value: &str) -> Self {
+        self.fields.push(InputField {
+            key,
+            label: label.to_string(),
+            value: value.to_string(),
+            options: vec![],
+            is_secret: false,
+        });
+        self
+    }
+
+    /// Adds an empty field whose value is masked while typing, for secrets.
+    pub fn with_secret_field(mut self, key: &'static str, label: &str) -> Self {
+        self.fields.push(InputField {
+            key,
+            label: label.to_string(),
+            value: String::new(),
+            options: vec![],
+            is_secret: true,
+        });
+        self
+    }
+
+    /// Adds a field whose value can only be one of `options`, the first one being the default.
+    pub fn with_choice(mut self, key: &'static str, label: &str, options: &[&str]) -> Self {
+        self.fields.push(InputField {
+            key,
+            label: label.to_string(),
+            value: options.first().map(|o| o.to_string()).unwrap_or_default(),
+            options: options.iter().map(|o| o.to_string()).collect(),
+            is_secret: false,
+        });
+        self
+    }
+
+    /// Like `with_choice`, starting on `value` when it's one of the options.
+    pub fn with_choice_value(self, key: &'static str, label: &str, options: &[&str], value: &str) -> Self {
+        let mut form = self.with_choice(key, label, options);
+        if let Some(field) = form.fields.last_mut()
+            && field.options.iter().any(|o| o == value) {
+            field.value = value.to_string();
+        }
+        form
+    }
+
+    pub fn value(&self, key: &str) -> String {
+        self.fields.iter()
+            .find(|f| f.key == key)
+            .map(|f| f.value.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    pub fn set_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> PopupAction {
+        self.err = None;
+
+        match key_event.code {
+            KeyCode::Esc => return PopupAction::Close,
+            KeyCode::Enter => return PopupAction::Submit,
+            KeyCode::Tab | KeyCode::Down => self.focused = (self.focused + 1) % self.fields.len().max(1),
+            KeyCode::BackTab | KeyCode::Up => {
+                self.focused = self.focused.checked_sub(1).unwrap_or(self.fields.len().saturating_sub(1))
+            }
+            code => {
+                if let Some(field) = self.fields.get_mut(self.focused) {
+                    match (code, field.is_choice()) {
+                        (KeyCode::Left, true) => field.select_option(-1),
+                        (KeyCode::Right | KeyCode::Char(' '), true) => field.select_option(1),
+                        (KeyCode::Backspace, false) => {
+                            field.value.pop();
+                        }
+                        (KeyCode::Char(c), false) => field.value.push(c),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        PopupAction::None
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let height = self.fields.len() as u16 + 5;
+        let area = popup_area(area, Constraint::Percentage(70), Constraint::Length(height));
+        frame.render_widget(Clear, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(tailwind::BLUE.c400))
+            .title(Line::from(format!(" {} ", self.title)).fg(tailwind::SLATE.c200));
+
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [fields_area, _, footer_area] = Layout::vertical([
+            Constraint::Length(self.fields.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]).areas(inner_area);
+
+        let label_width = self.fields.iter().map(|f| f.label.chars().count()).max().unwrap_or(0) + 3;
+        let value_width = (fields_area.width as usize).saturating_sub(label_width + 1);
+
+        let lines = self.fields.iter().enumerate().map(|(index, field)| {
+            let value_style = if index == self.focused {
+                Style::new().bg(tailwind::SLATE.c800).fg(tailwind::SLATE.c200)
+            } else {
+                Style::new().fg(tailwind::SLATE.c200)
+            };
+
+            let value = if field.is_choice() {
+                format!("< {} >", field.value)
+            } else if field.is_secret {
+                "•".repeat(field.value.chars().count().min(value_width))
+            } else {
+                // Keep the end of long values visible while typing.
+                let skipped = field.value.chars().count().saturating_sub(value_width);
+                field.value.chars().skip(skipped).collect()
+            };
+
+            Line::from_iter([
+                format!(" {:<width$}", format!("{}:", field.label), width = label_width - 1).fg(Color::Green),
+                format!("{value:<value_width$}").set_style(value_style),
+            ])
+        }).collect::<Vec<Line>>();
+
+        frame.render_widget(Paragraph::new(lines), fields_area);
+
+        if let Some(field) = self.fields.get(self.focused).filter(|f| !f.is_choice()) {
+            let value_len = field.value.chars().count().min(value_width) as u16;
+            let x = fields_area.x + label_width as u16 + value_len;
+            frame.set_cursor_position(Position::new(x, fields_area.y + self.focused as u16));
+        }
+
+        let footer = match &self.err {
+            Some(err) => Line::from(err.clone()).red(),
+            None => Line::from(DEFAULT_HINT).fg(tailwind::SLATE.c400),
+        };
+        frame.render_widget(footer, footer_area);
+    }
+}
@@ -1,8 +1,27 @@
-mod common;
+pub mod common;
+pub mod compose_view;
+pub mod confirm_popup;
+pub mod container_form;
 pub mod container_info_block;
 pub mod container_table;
+pub mod daemon_info_block;
+pub mod dependents_panel;
+pub mod file_browser;
 pub mod image_table;
 pub mod info_block;
+pub mod input_form;
+pub mod network_form;
+pub mod network_info_block;
 pub mod network_table;
+pub mod network_topology;
+pub mod node_table;
+pub mod prune_preview;
 pub mod resource_table;
+pub mod run_form;
+pub mod service_table;
+pub mod swarm_object_table;
+pub mod system_view;
+pub mod task_table;
+pub mod transfer_status;
+pub mod volume_info_block;
 pub mod volume_table;
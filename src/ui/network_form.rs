@@ -0,0 +1,176 @@
+use std::net::IpAddr;
+
+use bollard::secret::{Ipam, IpamConfig, NetworkCreateRequest};
+
+use crate::utils::{cidr_contains, is_valid_name, parse_cidr, parse_key_values};
+
+use super::input_form::InputForm;
+
+const DRIVERS: [&str; 4] = ["bridge", "macvlan", "ipvlan", "overlay"];
+const TOGGLE: [&str; 2] = ["no", "yes"];
+const DISCONNECT_MODES: [&str; 2] = ["normal", "force"];
+
+/// A container to attach to a network, with the optional alias and static IP to use there.
+#[derive(Clone, Debug)]
+pub struct NetworkConnection {
+    pub network: String,
+    pub container: String,
+    pub alias: String,
+    pub ip: String,
+}
+
+/// Connect or disconnect popup shown over a container or network detail view.
+pub enum ConnectionForm {
+    Connect(InputForm),
+    Disconnect(InputForm),
+}
+
+impl ConnectionForm {
+    pub fn form(&self) -> &InputForm {
+        match self {
+            Self::Connect(form) | Self::Disconnect(form) => form,
+        }
+    }
+
+    pub fn form_mut(&mut self) -> &mut InputForm {
+        match self {
+            Self::Connect(form) | Self::Disconnect(form) => form,
+        }
+    }
+}
+
+pub fn new_network_form() -> InputForm {
+    InputForm::new("Create network")
+        .with_field("name", "Name", "")
+        .with_choice("driver", "Driver", &DRIVERS)
+        .with_field("subnet", "Subnet (172.20.0.0/16)", "")
+        .with_field("gateway", "Gateway", "")
+        .with_field("range", "IP range (CIDR)", "")
+        .with_field("subnet6", "IPv6 subnet (fd00::/64)", "")
+        .with_choice("ipv6", "IPv6", &TOGGLE)
+        .with_choice("internal", "Internal", &TOGGLE)
+        .with_choice("attachable", "Attachable", &TOGGLE)
+        .with_field("options", "Driver options (key=value ...)", "")
+        .with_field("labels", "Labels (key=value ...)", "")
+}
+
+pub fn parse_network_form(form: &InputForm) -> Result<NetworkCreateRequest, String> {
+    let name = form.value("name");
+    if !is_valid_name(&name) {
+        return Err("Name must match [a-zA-Z0-9][a-zA-Z0-9_.-]+".to_string());
+    }
+
+    let mut ipam_configs = vec![];
+    if let Some(config) = parse_ipam_config(&form.value("subnet"), &form.value("gateway"), &form.value("range"))? {
+        ipam_configs.push(config);
+    }
+
+    let subnet6 = form.value("subnet6");
+    if !subnet6.is_empty() {
+        if !parse_cidr(&subnet6)?.0.is_ipv6() {
+            return Err("IPv6 subnet must be an IPv6 CIDR".to_string());
+        }
+        ipam_configs.push(IpamConfig { subnet: Some(subnet6.clone()), ..Default::default() });
+    }
+
+    let is_ipv6_subnet = ipam_configs.iter()
+        .filter_map(|config| config.subnet.as_deref())
+        .any(|subnet| subnet.contains(':'));
+
+    Ok(NetworkCreateRequest {
+        name,
+        driver: Some(form.value("driver")),
+        internal: Some(form.value("internal") == TOGGLE[1]),
+        attachable: Some(form.value("attachable") == TOGGLE[1]),
+        enable_ipv6: Some(form.value("ipv6") == TOGGLE[1] || is_ipv6_subnet),
+        ipam: Some(Ipam {
+            config: Some(ipam_configs).filter(|c| !c.is_empty()),
+            ..Default::default()
+        }),
+        options: Some(parse_key_values(&form.value("options"))?),
+        labels: Some(parse_key_values(&form.value("labels"))?),
+        ..Default::default()
+    })
+}
+
+/// Validates that the gateway and IP range, if given, fall within the subnet.
+fn parse_ipam_config(subnet: &str, gateway: &str, range: &str) -> Result<Option<IpamConfig>, String> {
+    if subnet.is_empty() {
+        if !gateway.is_empty() || !range.is_empty() {
+            return Err("Gateway and IP range require a subnet".to_string());
+        }
+        return Ok(None);
+    }
+
+    let block = parse_cidr(subnet)?;
+
+    if !gateway.is_empty() {
+        let address = gateway.parse::<IpAddr>().map_err(|_| format!("Invalid gateway: {gateway}"))?;
+        if !cidr_contains(block, address) {
+            return Err(format!("Gateway {gateway} is not in {subnet}"));
+        }
+    }
+
+    if !range.is_empty() {
+        let (address, prefix) = parse_cidr(range)?;
+        if prefix < block.1 || !cidr_contains(block, address) {
+            return Err(format!("IP range {range} is not in {subnet}"));
+        }
+    }
+
+    Ok(Some(IpamConfig {
+        subnet: Some(subnet.to_string()),
+        gateway: Some(gateway.to_string()).filter(|g| !g.is_empty()),
+        ip_range: Some(range.to_string()).filter(|r| !r.is_empty()),
+        ..Default::default()
+    }))
+}
+
+/// Form attaching `container` to a network to be typed in.
+pub fn new_connect_network_form(container: &str) -> InputForm {
+    InputForm::new(&format!("Connect {container} to a network"))
+        .with_field("network", "Network", "")
+        .with_field("alias", "Alias (optional)", "")
+        .with_field("ip", "Static IP (optional)", "")
+}
+
+/// Form attaching a container to be typed in to `network`.
+pub fn new_connect_container_form(network: &str) -> InputForm {
+    InputForm::new(&format!("Connect a container to {network}"))
+        .with_field("container", "Container (name or id)", "")
+        .with_field("alias", "Alias (optional)", "")
+        .with_field("ip", "Static IP (optional)", "")
+}
+
+/// Reads the connection from the form, `network` and `container` being the side already known.
+pub fn parse_connect_form(
+    form: &InputForm,
+    network: Option<&str>,
+    container: Option<&str>,
+) -> Result<NetworkConnection, String> {
+    let network = network.map(String::from).unwrap_or_else(|| form.value("network"));
+    let container = container.map(String::from).unwrap_or_else(|| form.value("container"));
+    if network.is_empty() || container.is_empty() {
+        return Err("Network and container are required".to_string());
+    }
+
+    let ip = form.value("ip");
+    if !ip.is_empty() && ip.parse::<IpAddr>().is_err() {
+        return Err(format!("Invalid IP address: {ip}"));
+    }
+
+    Ok(NetworkConnection { network, container, alias: form.value("alias"), ip })
+}
+
+/// Form picking which of the `options` (networks or containers) to detach.
+pub fn new_disconnect_form(title: &str, label: &str, options: &[String]) -> InputForm {
+    let options = options.iter().map(String::as_str).collect::<Vec<&str>>();
+    InputForm::new(title)
+        .with_choice("target", label, &options)
+        .with_choice("mode", "Mode", &DISCONNECT_MODES)
+}
+
+/// Returns the chosen option and whether the disconnect should be forced.
+pub fn parse_disconnect_form(form: &InputForm) -> (String, bool) {
+    (form.value("target"), form.value("mode") == DISCONNECT_MODES[1])
+}
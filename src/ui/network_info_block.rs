@@ -0,0 +1,256 @@
+use bollard::secret::Network;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Style, Stylize},
+    text::Line,
+};
+
+use crate::event::AppEvent;
+
+use super::common::PopupAction;
+use super::info_block::{ScrollInfo, ScrollableInfoBlock, get_filtered_list, get_key_value_lines, join_map};
+use super::network_form::{
+    ConnectionForm, new_connect_container_form, new_disconnect_form, parse_connect_form, parse_disconnect_form,
+};
+
+const REFRESH_AFTER_TICK: u8 = 30;
+const FOOTER_TEXT: &str = " <Esc/Q> back | <C> connect container | <U> disconnect container";
+
+#[derive(Default)]
+pub struct NetworkInfoBlock {
+    data: NetworkData,
+    scroll_info: ScrollInfo,
+    skipped_tick_count_for_refresh: u8,
+    form: Option<ConnectionForm>,
+    err: Option<String>,
+}
+
+#[derive(Default, Clone)]
+pub struct NetworkData {
+    id: String,
+    name: String,
+    driver: String,
+    scope: String,
+    created: String,
+    flags: String,
+    ipam_driver: String,
+    ipam_configs: String,
+    options: String,
+    labels: String,
+    containers: String,
+    container_names: Vec<String>,
+}
+
+impl ScrollableInfoBlock for NetworkInfoBlock {
+    type Data = NetworkData;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if self.err.is_some() {
+            self.err = None;
+            return Ok(None);
+        }
+
+        let event = match key_event.code {
+            KeyCode::Char('c') => {
+                self.form = Some(ConnectionForm::Connect(new_connect_container_form(&self.data.name)));
+                None
+            }
+            KeyCode::Char('u') => {
+                self.open_disconnect_form();
+                None
+            }
+            _ => self.handle_nav_key_event(key_event)?,
+        };
+        Ok(event)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let title = format!("Network: {}", self.data.name);
+        let (footer_text, footer_style) = match &self.err {
+            Some(err) => (err.clone(), Some(Style::new().red())),
+            None => (FOOTER_TEXT.to_string(), None),
+        };
+        let result = self.draw_default(frame, area, title, get_content_as_lines(&self.data), footer_text, footer_style);
+
+        if let Some(form) = &self.form {
+            form.form().draw(frame, area);
+        }
+
+        result
+    }
+
+    fn tick(&mut self) -> Result<Option<AppEvent>> {
+        let event = if self.skipped_tick_count_for_refresh > REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh = 0;
+            Some(AppEvent::UpdateNetworkInfo(self.data.id.clone()))
+        } else {
+            self.skipped_tick_count_for_refresh += 1;
+            None
+        };
+        Ok(event)
+    }
+
+    fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    fn update_data(&mut self, data: Self::Data) {
+        self.data = data;
+    }
+
+    fn get_scroll_info(&mut self) -> &mut ScrollInfo {
+        &mut self.scroll_info
+    }
+}
+
+impl NetworkInfoBlock {
+    fn open_disconnect_form(&mut self) {
+        if self.data.container_names.is_empty() {
+            self.show_err("No containers are connected");
+            return;
+        }
+
+        let title = format!("Disconnect from {}", self.data.name);
+        let form = new_disconnect_form(&title, "Container", &self.data.container_names);
+        self.form = Some(ConnectionForm::Disconnect(form));
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let network = self.data.id.clone();
+        let connection_form = self.form.as_mut()?;
+
+        match connection_form.form_mut().handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => {
+                let event = match connection_form {
+                    ConnectionForm::Connect(form) => match parse_connect_form(form, Some(&network), None) {
+                        Ok(connection) => AppEvent::ConnectNetwork(connection),
+                        Err(err) => {
+                            form.set_err(&err);
+                            return None;
+                        }
+                    },
+                    ConnectionForm::Disconnect(form) => {
+                        let (container, force) = parse_disconnect_form(form);
+                        AppEvent::DisconnectNetwork(network, container, force)
+                    }
+                };
+                self.form = None;
+                Some(event)
+            }
+        }
+    }
+}
+
+fn get_content_as_lines(data: &NetworkData) -> Vec<Line<'static>> {
+    let spacer = ("".to_string(), "".to_string());
+
+    let mut lines = vec![
+        ("ID: ".to_string(), data.id.clone()),
+        ("Name: ".to_string(), data.name.clone()),
+        ("Driver: ".to_string(), data.driver.clone()),
+        ("Scope: ".to_string(), data.scope.clone()),
+        ("Created: ".to_string(), data.created.clone()),
+        ("Flags: ".to_string(), data.flags.clone()),
+        spacer.clone(),
+        ("IPAM Driver: ".to_string(), data.ipam_driver.clone()),
+    ];
+
+    if let Some(configs) = get_filtered_list(&data.ipam_configs) {
+        lines.extend(vec![spacer.clone(), ("IPAM Config:".to_string(), "".to_string())]);
+        lines.extend(configs);
+    }
+
+    if let Some(containers) = get_filtered_list(&data.containers) {
+        lines.extend(vec![spacer.clone(), ("Containers:".to_string(), "".to_string())]);
+        lines.extend(containers);
+    }
+
+    if let Some(options) = get_filtered_list(&data.options) {
+        lines.extend(vec![spacer.clone(), ("Options:".to_string(), "".to_string())]);
+        lines.extend(options);
+    }
+
+    if let Some(labels) = get_filtered_list(&data.labels) {
+        lines.extend(vec![spacer.clone(), ("Labels:".to_string(), "".to_string())]);
+        lines.extend(labels);
+    }
+
+    get_key_value_lines(lines)
+}
+
+impl NetworkData {
+    pub fn from(network: Network) -> Self {
+        let flags = [
+            ("internal", network.internal),
+            ("attachable", network.attachable),
+            ("ingress", network.ingress),
+            ("ipv6", network.enable_ipv6),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| enabled.unwrap_or_default())
+        .map(|(flag, _)| flag)
+        .collect::<Vec<&str>>();
+
+        let ipam = network.ipam.unwrap_or_default();
+        let ipam_configs = ipam.config.unwrap_or_default().into_iter()
+            .map(|config| {
+                [("subnet", config.subnet), ("gateway", config.gateway), ("range", config.ip_range)]
+                    .into_iter()
+                    .filter_map(|(key, value)| value.filter(|v| !v.is_empty()).map(|v| format!("{key} {v}")))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let network_containers = network.containers.unwrap_or_default();
+
+        let mut container_names = network_containers.values()
+            .filter_map(|container| container.name.clone())
+            .collect::<Vec<String>>();
+        container_names.sort();
+
+        let containers = network_containers.into_values()
+            .map(|container| {
+                let addresses = [
+                    ("IPv4", container.ipv4_address),
+                    ("IPv6", container.ipv6_address),
+                    ("MAC", container.mac_address),
+                ]
+                .into_iter()
+                .filter_map(|(key, value)| value.filter(|v| !v.is_empty()).map(|v| format!("{key} {v}")))
+                .collect::<Vec<String>>()
+                .join(", ");
+                format!("{}: {addresses}", container.name.unwrap_or_default())
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        Self {
+            id: network.id.unwrap_or_default(),
+            name: network.name.unwrap_or_default(),
+            driver: network.driver.unwrap_or_else(|| "-".to_string()),
+            scope: network.scope.unwrap_or_else(|| "-".to_string()),
+            created: network.created.unwrap_or_else(|| "-".to_string()),
+            flags: if flags.is_empty() { "-".to_string() } else { flags.join(", ") },
+            ipam_driver: ipam.driver.unwrap_or_else(|| "-".to_string()),
+            ipam_configs,
+            options: join_map(&network.options.unwrap_or_default(), "="),
+            labels: join_map(&network.labels.unwrap_or_default(), ": "),
+            containers,
+            container_names,
+        }
+    }
+}
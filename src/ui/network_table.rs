@@ -1,6 +1,9 @@
-use super::common::TableStyle;
+use super::common::{PopupAction, TableStyle};
 use super::common::render_footer;
+use super::input_form::InputForm;
+use super::network_form::{new_network_form, parse_network_form};
 use crate::event::AppEvent;
+use crate::ui::dependents_panel::{DependencyTarget, DependentsPanel, DependentsTable, handle_panel_key_event, split_panel_area};
 use crate::ui::resource_table::ResourceTable;
 use crate::ui::resource_table::ResourceTableInfo;
 use bollard::secret::Network;
@@ -20,14 +23,17 @@ const ROW_HEIGHT: usize = 3;
 const REFRESH_AFTER_TICK: u8 = 10;
 const REGEX_NETWORK_IN_USE: &str = r":(?:[^:]+:)?\s*([^\(]+)";
 const REGEX_NETWORK_CREATED_AT: &str = r"\.\d+";
-const DEFAULT_FOOTER: &str = " <Del/D> remove";
+const DEFAULT_FOOTER: &str = " <Ent> details | <C> create | <G> topology | <U> used by | <Del/D> remove";
 
 #[derive(Default)]
 pub struct NetworkTable {
     style: TableStyle,
     skipped_tick_count_for_refresh: u8,
     info: ResourceTableInfo<NetworkTableRow>,
+    dependents: Option<DependentsPanel>,
+    form: Option<InputForm>,
     err: Option<String>,
+    notice: Option<String>,
 }
 
 #[derive(Default)]
@@ -89,6 +95,11 @@ impl ResourceTable for NetworkTable {
         let mut border_style = None;
         let mut footer_text = DEFAULT_FOOTER.to_string();
 
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
+
         if let Some(err) = &self.err {
             border_style = Some(Style::new().red());
             footer_text = err.clone();
@@ -98,24 +109,61 @@ impl ResourceTable for NetworkTable {
     }
 }
 
+impl DependentsTable for NetworkTable {
+    fn get_dependents(&mut self) -> &mut Option<DependentsPanel> {
+        &mut self.dependents
+    }
+
+    fn selected_dependency_target(&mut self) -> Option<DependencyTarget> {
+        self.get_selected_row().map(|r| DependencyTarget::Network(r.name.clone()))
+    }
+}
+
 impl NetworkTable {
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
-        if self.err.is_some() {
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if let Some(event) = handle_panel_key_event(&mut self.dependents, key_event) {
+            return Ok(event);
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
             self.err = None;
+            self.notice = None;
             return Ok(None);
         }
 
         let event = match key_event.code {
             KeyCode::Delete | KeyCode::Char('d') => self.get_selected_row()
                 .map(|n| AppEvent::RemoveNetwork(n.name.clone())),
-            _ => self.handle_nav_key_event(key_event)?,
+            KeyCode::Enter => self.get_selected_row().map(|n| AppEvent::GoToNetworkDetails(n.name.clone())),
+            KeyCode::Char('c') => {
+                self.form = Some(new_network_form());
+                None
+            }
+            KeyCode::Char('g') => Some(AppEvent::ShowNetworkTopology),
+            KeyCode::Char('u') => self.open_dependents(),
+            _ => self.handle_nav_key_event(key_event)?.or_else(|| self.retarget_dependents()),
         };
 
         Ok(event)
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
-        self.draw_default(frame, area)
+        let (table_area, panel_area) = split_panel_area(area, self.dependents.is_some());
+        let result = self.draw_default(frame, table_area);
+
+        if let Some(dependents) = self.dependents.as_mut() {
+            dependents.draw(frame, panel_area);
+        }
+
+        if let Some(form) = &self.form {
+            form.draw(frame, area);
+        }
+
+        result
     }
 
     pub fn tick(&mut self) -> Result<Option<AppEvent>> {
@@ -137,6 +185,43 @@ impl NetworkTable {
 
         self.err = Some(format!("[ERR] {}", err_msg.trim()))
     }
+
+    pub fn is_form_open(&self) -> bool {
+        self.form.is_some()
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn show_created(&mut self, name: &str) {
+        self.notice = Some(format!("[OK] Created network {name}"));
+        if let Some(index) = self.info.items.iter().position(|n| n.name == name) {
+            self.select_row(index);
+        }
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let form = self.form.as_mut()?;
+
+        match form.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => match parse_network_form(form) {
+                Ok(options) => {
+                    self.form = None;
+                    Some(AppEvent::CreateNetwork(Box::new(options)))
+                }
+                Err(err) => {
+                    form.set_err(&err);
+                    None
+                }
+            },
+        }
+    }
 }
 
 impl NetworkTableRow {
@@ -0,0 +1,270 @@
+use std::{collections::HashSet, ops::Range};
+
+use bollard::secret::ContainerSummary;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize, palette::tailwind},
+    symbols::Marker,
+    text::Line,
+    widgets::{
+        Block, BorderType,
+        canvas::{Canvas, Context, Line as CanvasLine},
+    },
+};
+
+use crate::{event::AppEvent, utils::is_container_running};
+
+use super::common::render_footer;
+
+const REFRESH_AFTER_TICK: u8 = 30;
+const MIN_SLOT_WIDTH: usize = 16;
+const DEFAULT_FOOTER: &str = " <Esc/Q> back | <T> toggle stopped | <H/L> page";
+const BRIDGE_COLOR: Color = tailwind::AMBER.c400;
+const PORT_COLOR: Color = tailwind::GREEN.c400;
+const LINK_COLOR: Color = tailwind::SLATE.c500;
+const NETWORK_COLOR: Color = tailwind::BLUE.c400;
+
+/// Graph of the networks and the containers attached to them, drawn on a canvas with the host on
+/// top, containers in the middle and networks at the bottom.
+#[derive(Default)]
+pub struct NetworkTopology {
+    containers: Vec<TopologyContainer>,
+    networks: Vec<String>,
+    show_stopped: bool,
+    page: usize,
+    page_count: usize,
+    skipped_tick_count_for_refresh: u8,
+}
+
+#[derive(Clone)]
+struct TopologyContainer {
+    name: String,
+    is_running: bool,
+    networks: Vec<String>,
+    ports: Vec<String>,
+}
+
+impl NetworkTopology {
+    pub fn update(&mut self, containers: Vec<ContainerSummary>) {
+        let mut containers = containers.iter()
+            .map(TopologyContainer::from)
+            .filter(|c| !c.networks.is_empty())
+            .filter(|c| self.show_stopped || c.is_running)
+            .collect::<Vec<TopologyContainer>>();
+
+        let mut networks = containers.iter()
+            .flat_map(|c| c.networks.clone())
+            .collect::<Vec<String>>();
+        networks.sort();
+        networks.dedup();
+
+        // Ordering by first network keeps most links short and vertical.
+        containers.sort_by_key(|c| {
+            let first_network = networks.iter().position(|n| c.networks.contains(n)).unwrap_or_default();
+            (first_network, c.networks.len(), c.name.clone())
+        });
+
+        self.containers = containers;
+        self.networks = networks;
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        match key_event.code {
+            KeyCode::Esc | KeyCode::Char('q') => return Some(AppEvent::Back),
+            KeyCode::Char('t') => {
+                self.show_stopped = !self.show_stopped;
+                self.page = 0;
+                return Some(AppEvent::UpdateNetworkTopology);
+            }
+            KeyCode::Right | KeyCode::Char('l') => self.page = (self.page + 1).min(self.page_count.saturating_sub(1)),
+            KeyCode::Left | KeyCode::Char('h') => self.page = self.page.saturating_sub(1),
+            _ => {}
+        }
+        None
+    }
+
+    pub fn tick(&mut self) -> Option<AppEvent> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return None;
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Some(AppEvent::UpdateNetworkTopology)
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) {
+        use Constraint::{Length, Min};
+        let [canvas_area, footer_area] = Layout::vertical([Min(0), Length(3)]).areas(area);
+
+        let width = canvas_area.width.saturating_sub(2) as usize;
+        let pages = page_ranges(&self.containers, (width / MIN_SLOT_WIDTH).max(1));
+        self.page_count = pages.len().max(1);
+        self.page = self.page.min(self.page_count - 1);
+
+        let visible = pages.get(self.page)
+            .map_or(vec![], |range| self.containers[range.clone()].to_vec());
+
+        // Only the networks of the shown containers get a slot, so their labels stay readable.
+        let networks = self.networks.iter()
+            .filter(|network| visible.iter().any(|c| c.networks.contains(network)))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let title = format!(
+            "Network topology: {} network(s), {} container(s), page {}/{}",
+            self.networks.len(),
+            self.containers.len(),
+            self.page + 1,
+            self.page_count,
+        );
+
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(tailwind::BLUE.c400))
+            .title(Line::from(title).fg(tailwind::SLATE.c200))
+            .title_bottom(Line::from(vec![
+                " bridges networks ".fg(BRIDGE_COLOR),
+                "| ".fg(tailwind::SLATE.c400),
+                "published ports ".fg(PORT_COLOR),
+            ]));
+
+        let width = width as f64;
+        let height = canvas_area.height.saturating_sub(2) as f64;
+
+        let canvas = Canvas::default()
+            .block(block)
+            .marker(Marker::Braille)
+            .x_bounds([0.0, width])
+            .y_bounds([0.0, height])
+            .paint(move |ctx| paint_graph(ctx, width, height, &visible, &networks));
+
+        frame.render_widget(canvas, canvas_area);
+
+        let footer_text = match self.containers.is_empty() {
+            true => format!("{DEFAULT_FOOTER} | no containers attached to networks"),
+            false => DEFAULT_FOOTER.to_string(),
+        };
+        render_footer(frame, footer_area, footer_text, None);
+    }
+}
+
+/// Splits the containers into pages holding at most `page_size` containers and, unless a single
+/// container has more, `page_size` networks.
+fn page_ranges(containers: &[TopologyContainer], page_size: usize) -> Vec<Range<usize>> {
+    let mut pages = vec![];
+    let mut start = 0;
+    let mut networks = HashSet::new();
+
+    for (index, container) in containers.iter().enumerate() {
+        let new_networks = container.networks.iter().filter(|n| !networks.contains(n)).count();
+        if index > start && (index - start >= page_size || networks.len() + new_networks > page_size) {
+            pages.push(start..index);
+            start = index;
+            networks.clear();
+        }
+        networks.extend(container.networks.iter());
+    }
+
+    if start < containers.len() {
+        pages.push(start..containers.len());
+    }
+    pages
+}
+
+fn paint_graph(ctx: &mut Context, width: f64, height: f64, containers: &[TopologyContainer], networks: &[String]) {
+    let host = (width / 2.0, height - 1.0);
+    let container_y = (height * 0.55).round();
+    let network_y = 1.0;
+
+    let container_slot = width / containers.len().max(1) as f64;
+    let network_slot = width / networks.len().max(1) as f64;
+    let slot_x = |index: usize, slot: f64| index as f64 * slot + slot / 2.0;
+
+    for (index, container) in containers.iter().enumerate() {
+        let x = slot_x(index, container_slot);
+        let is_bridge = container.networks.len() > 1;
+
+        for network in &container.networks {
+            if let Some(network_index) = networks.iter().position(|n| n == network) {
+                let color = if is_bridge { BRIDGE_COLOR } else { LINK_COLOR };
+                let network_x = slot_x(network_index, network_slot);
+                ctx.draw(&CanvasLine::new(x, container_y - 2.0, network_x, network_y + 1.0, color));
+            }
+        }
+
+        if !container.ports.is_empty() {
+            ctx.draw(&CanvasLine::new(host.0, host.1 - 1.0, x, container_y + 1.0, PORT_COLOR));
+        }
+    }
+
+    ctx.layer();
+
+    print_centered(ctx, host.0, host.1, "[ host ]".to_string(), width, Style::new().bold().fg(PORT_COLOR));
+
+    for (index, container) in containers.iter().enumerate() {
+        let x = slot_x(index, container_slot);
+        let max_len = (container_slot as usize).saturating_sub(1);
+
+        let name_style = match (container.networks.len() > 1, container.is_running) {
+            (true, _) => Style::new().bold().fg(BRIDGE_COLOR),
+            (false, true) => Style::new().fg(tailwind::SLATE.c200),
+            (false, false) => Style::new().fg(tailwind::SLATE.c500),
+        };
+        print_centered(ctx, x, container_y, truncate(&container.name, max_len), width, name_style);
+
+        if !container.ports.is_empty() {
+            let ports = truncate(&container.ports.join(" "), max_len);
+            print_centered(ctx, x, container_y - 1.0, ports, width, Style::new().fg(PORT_COLOR));
+        }
+    }
+
+    for (index, network) in networks.iter().enumerate() {
+        let x = slot_x(index, network_slot);
+        let label = truncate(&format!("[ {network} ]"), (network_slot as usize).saturating_sub(1));
+        print_centered(ctx, x, network_y, label, width, Style::new().bold().fg(NETWORK_COLOR));
+    }
+}
+
+fn print_centered(ctx: &mut Context, x: f64, y: f64, text: String, width: f64, style: Style) {
+    let start = (x - text.chars().count() as f64 / 2.0).clamp(0.0, width);
+    ctx.print(start, y, Line::styled(text, style));
+}
+
+fn truncate(text: &str, max_len: usize) -> String {
+    if text.chars().count() <= max_len {
+        return text.to_string();
+    }
+    let truncated = text.chars().take(max_len.saturating_sub(1)).collect::<String>();
+    format!("{truncated}…")
+}
+
+impl TopologyContainer {
+    fn from(container: &ContainerSummary) -> Self {
+        let name = container.names.as_deref()
+            .and_then(|names| names.first())
+            .and_then(|name| name.strip_prefix("/"))
+            .map_or("NaN".to_string(), |name| name.to_string());
+
+        let mut networks = container.network_settings.as_ref()
+            .and_then(|settings| settings.networks.as_ref())
+            .map(|networks| networks.keys().cloned().collect::<Vec<String>>())
+            .unwrap_or_default();
+        networks.sort();
+
+        let mut ports = container.ports.iter().flatten()
+            .filter_map(|port| port.public_port.map(|public| format!("{public}→{}", port.private_port)))
+            .collect::<Vec<String>>();
+        ports.sort();
+        ports.dedup();
+
+        Self {
+            name,
+            is_running: is_container_running(&container.state.map(|state| state.to_string()).unwrap_or_default()),
+            networks,
+            ports,
+        }
+    }
+}
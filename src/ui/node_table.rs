@@ -0,0 +1,229 @@
+use super::common::{PopupAction, TableStyle, render_footer};
+use super::confirm_popup::ConfirmPopup;
+use crate::event::AppEvent;
+use crate::ui::resource_table::{ResourceTable, ResourceTableInfo};
+use bollard::secret::{Node, NodeSpecAvailabilityEnum, NodeState};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::Stylize;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Style, palette::tailwind},
+    text::Text,
+    widgets::{Cell, HighlightSpacing, Row, Table},
+};
+
+const ROW_HEIGHT: usize = 3;
+const REFRESH_AFTER_TICK: u8 = 10;
+const DEFAULT_FOOTER: &str = " <D> drain | <P> pause | <A> activate";
+
+#[derive(Default)]
+pub struct NodeTable {
+    style: TableStyle,
+    skipped_tick_count_for_refresh: u8,
+    info: ResourceTableInfo<NodeTableRow>,
+    drain_confirm: Option<(String, ConfirmPopup)>,
+    err: Option<String>,
+    notice: Option<String>,
+}
+
+pub struct NodeTableRow {
+    id: String,
+    short_id: String,
+    hostname: String,
+    status: String,
+    availability: String,
+    role: String,
+    engine: String,
+    is_ready: bool,
+}
+
+impl ResourceTable for NodeTable {
+    type RowType = NodeTableRow;
+
+    fn get_table_info(&mut self) -> &mut ResourceTableInfo<Self::RowType> {
+        &mut self.info
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["ID", "Hostname", "Status", "Availability", "Role", "Engine"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style)
+            .height(1);
+
+        self.info.row_heights.clear();
+
+        let rows = self.info.items.iter().enumerate().map(|(index, node)| {
+            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            let row_style = if node.is_ready { row_style } else { row_style.fg(tailwind::RED.c400) };
+
+            if index < self.info.items.len() - 1 {
+                self.info.row_heights.push(ROW_HEIGHT);
+            }
+
+            node.ref_array().into_iter()
+                .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                .collect::<Row>()
+                .style(row_style)
+                .height(ROW_HEIGHT as u16)
+        });
+
+        let widths = vec![
+            Constraint::Length(15),
+            Constraint::Min(15),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Length(18),
+            Constraint::Min(10),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(self.style.selected_row_style)
+            .highlight_symbol(Text::from(vec!["".into(), " ● ".into()]))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, area, &mut self.info.state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let mut border_style = None;
+        let mut footer_text = DEFAULT_FOOTER.to_string();
+
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
+
+        if let Some(err) = &self.err {
+            border_style = Some(Style::new().red());
+            footer_text = err.clone();
+        }
+
+        render_footer(frame, area, footer_text, border_style);
+    }
+}
+
+impl NodeTable {
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        if self.drain_confirm.is_some() {
+            return Ok(self.handle_confirm_key_event(key_event));
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
+            self.err = None;
+            self.notice = None;
+            return Ok(None);
+        }
+
+        let availability = match key_event.code {
+            KeyCode::Char('d') => {
+                self.open_drain_confirm();
+                return Ok(None);
+            }
+            KeyCode::Char('p') => NodeSpecAvailabilityEnum::PAUSE,
+            KeyCode::Char('a') => NodeSpecAvailabilityEnum::ACTIVE,
+            _ => return self.handle_nav_key_event(key_event),
+        };
+
+        Ok(self.get_selected_row()
+            .filter(|node| node.availability != availability.to_string())
+            .map(|node| AppEvent::SetNodeAvailability(node.id.clone(), availability)))
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let result = self.draw_default(frame, area);
+
+        if let Some((_, confirm)) = &self.drain_confirm {
+            confirm.draw(frame, area);
+        }
+
+        result
+    }
+
+    pub fn tick(&mut self) -> Result<Option<AppEvent>> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return Ok(None);
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Ok(Some(AppEvent::UpdateNodes))
+    }
+
+    pub fn is_form_open(&self) -> bool {
+        self.drain_confirm.is_some()
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn show_availability(&mut self, id: &str, availability: NodeSpecAvailabilityEnum) {
+        let hostname = self.info.items.iter().find(|n| n.id == id).map_or(id, |n| n.hostname.as_str());
+        self.notice = Some(format!("[OK] Set {hostname} to {availability}"));
+    }
+
+    /// Draining moves every task off the node, so it is confirmed first.
+    fn open_drain_confirm(&mut self) {
+        let drain = NodeSpecAvailabilityEnum::DRAIN.to_string();
+        let Some(node) = self.get_selected_row().filter(|node| node.availability != drain) else { return };
+
+        let title = format!("Drain {}", node.hostname);
+        let items = vec![format!("{} ({}, {}): its tasks move to the other nodes", node.hostname, node.role, node.status)];
+        self.drain_confirm = Some((node.id.clone(), ConfirmPopup::new(title, items, "drain")));
+    }
+
+    fn handle_confirm_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let (_, confirm) = self.drain_confirm.as_mut()?;
+        match confirm.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.drain_confirm = None;
+                None
+            }
+            PopupAction::Submit => {
+                let (id, _) = self.drain_confirm.take()?;
+                Some(AppEvent::SetNodeAvailability(id, NodeSpecAvailabilityEnum::DRAIN))
+            }
+        }
+    }
+}
+
+impl NodeTableRow {
+    const fn ref_array(&self) -> [&String; 6] {
+        [&self.short_id, &self.hostname, &self.status, &self.availability, &self.role, &self.engine]
+    }
+
+    pub fn from_list(nodes: Vec<Node>) -> Vec<Self> {
+        let mut result = nodes.iter().map(Self::from).collect::<Vec<Self>>();
+        result.sort_by_key(|n| n.hostname.clone());
+        result
+    }
+
+    fn from(node: &Node) -> Self {
+        let id = node.id.as_deref().unwrap_or("-");
+        let spec = node.spec.clone().unwrap_or_default();
+        let description = node.description.clone().unwrap_or_default();
+        let state = node.status.as_ref().and_then(|s| s.state);
+
+        let role = match (&node.manager_status, spec.role) {
+            (Some(manager), _) if manager.leader == Some(true) => "manager (leader)".to_string(),
+            (_, Some(role)) => role.to_string(),
+            (_, None) => "-".to_string(),
+        };
+
+        Self {
+            id: id.to_string(),
+            short_id: format!("{}...", &id[..id.len().min(12)]),
+            hostname: description.hostname.unwrap_or_else(|| "-".to_string()),
+            status: state.map_or("-".to_string(), |s| s.to_string()),
+            availability: spec.availability.map_or("-".to_string(), |a| a.to_string()),
+            role,
+            engine: description.engine.and_then(|e| e.engine_version).unwrap_or_else(|| "-".to_string()),
+            is_ready: state == Some(NodeState::READY),
+        }
+    }
+}
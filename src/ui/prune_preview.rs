@@ -0,0 +1,96 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize, palette::tailwind},
+    text::Line,
+    widgets::{Block, BorderType, Clear, Paragraph},
+};
+
+use super::common::{PopupAction, popup_area, size_string};
+
+pub struct PrunePreview {
+    title: String,
+    items: Vec<String>,
+    reclaimable: i64,
+    hint: String,
+    empty_text: String,
+    scroll: usize,
+}
+
+impl PrunePreview {
+    pub fn new(title: String, items: Vec<String>, reclaimable: i64) -> Self {
+        Self {
+            title,
+            items,
+            reclaimable,
+            hint: " <Ent/Y> prune | <Esc/Q> cancel".to_string(),
+            empty_text: " Nothing to prune".to_string(),
+            scroll: 0,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: &str) -> Self {
+        self.hint = hint.to_string();
+        self
+    }
+
+    /// Text shown instead of the list when there is nothing to prune. Confirming is disabled then.
+    pub fn with_empty_text(mut self, text: &str) -> Self {
+        self.empty_text = text.to_string();
+        self
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> PopupAction {
+        match key_event.code {
+            KeyCode::Enter | KeyCode::Char('y') if !self.items.is_empty() => PopupAction::Submit,
+            KeyCode::Esc | KeyCode::Char('q' | 'n') => PopupAction::Close,
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll = (self.scroll + 1).min(self.items.len().saturating_sub(1));
+                PopupAction::None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll = self.scroll.saturating_sub(1);
+                PopupAction::None
+            }
+            _ => PopupAction::None,
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        use Constraint::{Length, Min};
+
+        let area = popup_area(area, Constraint::Percentage(70), Constraint::Percentage(60));
+        frame.render_widget(Clear, area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(tailwind::AMBER.c400))
+            .title(Line::from(format!(" {} ", self.title)).fg(tailwind::SLATE.c200));
+
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [list_area, summary_area, hint_area] = Layout::vertical([Min(0), Length(2), Length(1)]).areas(inner_area);
+
+        let lines = if self.items.is_empty() {
+            vec![Line::from(self.empty_text.clone()).fg(tailwind::SLATE.c400)]
+        } else {
+            self.items.iter().map(|item| Line::from(format!(" - {item}"))).collect()
+        };
+
+        let list = Paragraph::new(lines)
+            .style(Style::new().fg(tailwind::SLATE.c200))
+            .scroll((self.scroll as u16, 0));
+        frame.render_widget(list, list_area);
+
+        let summary = Line::from_iter([
+            " Total: ".fg(Color::Green),
+            format!("{} item(s)", self.items.len()).into(),
+            " | Reclaimable: ".fg(Color::Green),
+            size_string(self.reclaimable).into(),
+        ]);
+        frame.render_widget(Paragraph::new(vec![Line::default(), summary]), summary_area);
+        frame.render_widget(Line::from(self.hint.clone()).fg(tailwind::SLATE.c400), hint_area);
+    }
+}
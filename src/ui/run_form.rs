@@ -0,0 +1,41 @@
+use crate::docker::run_config::{RunConfig, parse_bind, parse_env, parse_port, split_command};
+
+use super::input_form::InputForm;
+
+pub const RESTART_POLICIES: [&str; 4] = ["no", "always", "unless-stopped", "on-failure"];
+const MODES: [&str; 2] = ["detached", "interactive (-it)"];
+
+pub fn new_run_form(image: &str) -> InputForm {
+    InputForm::new(&format!("Run {image}"))
+        .with_field("name", "Name", "")
+        .with_field("cmd", "Command", "")
+        .with_field("env", "Env (KEY=VAL KEY2=\"a b\" ...)", "")
+        .with_field("ports", "Ports (8080:80/tcp, ...)", "")
+        .with_field("mounts", "Mounts (src:/dst[:ro], ...)", "")
+        .with_field("network", "Network", "")
+        .with_choice("restart", "Restart policy", &RESTART_POLICIES)
+        .with_choice("mode", "Mode", &MODES)
+}
+
+pub fn parse_run_form(image: &str, form: &InputForm) -> Result<RunConfig, String> {
+    Ok(RunConfig {
+        image: image.to_string(),
+        name: form.value("name"),
+        cmd: split_command(&form.value("cmd"))?,
+        // Values may contain commas, so variables are separated like command arguments instead.
+        env: split_command(&form.value("env"))?.iter().map(|v| parse_env(v)).collect::<Result<_, _>>()?,
+        ports: parse_list(&form.value("ports"), parse_port)?,
+        binds: parse_list(&form.value("mounts"), parse_bind)?,
+        network: form.value("network"),
+        restart_policy: form.value("restart"),
+        interactive: form.value("mode") == MODES[1],
+    })
+}
+
+fn parse_list<T>(value: &str, parse: fn(&str) -> Result<T, String>) -> Result<Vec<T>, String> {
+    value.split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(parse)
+        .collect()
+}
@@ -0,0 +1,336 @@
+use super::common::{PopupAction, TableStyle, render_footer};
+use super::confirm_popup::ConfirmPopup;
+use super::input_form::InputForm;
+use crate::event::AppEvent;
+use crate::ui::resource_table::{ResourceTable, ResourceTableInfo};
+use bollard::secret::Service;
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::Stylize;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Style, palette::tailwind},
+    text::Text,
+    widgets::{Cell, HighlightSpacing, Row, Table},
+};
+
+const ROW_HEIGHT: usize = 3;
+const REFRESH_AFTER_TICK: u8 = 10;
+const DEFAULT_FOOTER: &str = " <Ent> tasks | <S> scale | <I> update image | <B> rollback";
+
+#[derive(Default)]
+pub struct ServiceTable {
+    style: TableStyle,
+    skipped_tick_count_for_refresh: u8,
+    info: ResourceTableInfo<ServiceTableRow>,
+    form: Option<(ServiceForm, InputForm)>,
+    rollback_confirm: Option<(String, ConfirmPopup)>,
+    err: Option<String>,
+    notice: Option<String>,
+}
+
+/// The action a form was opened for, with the ID of the service it applies to.
+enum ServiceForm {
+    Scale(String),
+    UpdateImage(String),
+}
+
+pub struct ServiceTableRow {
+    id: String,
+    short_id: String,
+    name: String,
+    mode: String,
+    replicas: String,
+    image: String,
+    ports: String,
+    desired: Option<i64>,
+    is_converged: bool,
+}
+
+impl ResourceTable for ServiceTable {
+    type RowType = ServiceTableRow;
+
+    fn get_table_info(&mut self) -> &mut ResourceTableInfo<Self::RowType> {
+        &mut self.info
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["ID", "Name", "Mode", "Replicas", "Image", "Ports"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style)
+            .height(1);
+
+        self.info.row_heights.clear();
+
+        let rows = self.info.items.iter().enumerate().map(|(index, service)| {
+            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+
+            if index < self.info.items.len() - 1 {
+                self.info.row_heights.push(ROW_HEIGHT);
+            }
+
+            // Services still converging to their desired replica count stand out.
+            let replicas_style = match service.is_converged {
+                true => Style::new(),
+                false => Style::new().fg(tailwind::AMBER.c400),
+            };
+
+            service.ref_array().into_iter()
+                .enumerate()
+                .map(|(column, content)| {
+                    let cell = Cell::from(Text::from(format!("\n{content}\n")));
+                    if column == 3 { cell.style(replicas_style) } else { cell }
+                })
+                .collect::<Row>()
+                .style(row_style)
+                .height(ROW_HEIGHT as u16)
+        });
+
+        let widths = vec![
+            Constraint::Length(15),
+            Constraint::Min(15),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Min(20),
+            Constraint::Min(15),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(self.style.selected_row_style)
+            .highlight_symbol(Text::from(vec!["".into(), " ● ".into()]))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, area, &mut self.info.state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let mut border_style = None;
+        let mut footer_text = DEFAULT_FOOTER.to_string();
+
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
+
+        if let Some(err) = &self.err {
+            border_style = Some(Style::new().red());
+            footer_text = err.clone();
+        }
+
+        render_footer(frame, area, footer_text, border_style);
+    }
+}
+
+impl ServiceTable {
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if self.rollback_confirm.is_some() {
+            return Ok(self.handle_confirm_key_event(key_event));
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
+            self.err = None;
+            self.notice = None;
+            return Ok(None);
+        }
+
+        let event = match key_event.code {
+            KeyCode::Enter => self.get_selected_row().map(|s| AppEvent::ShowServiceTasks(s.id.clone())),
+            KeyCode::Char('s') => {
+                self.open_scale_form();
+                None
+            }
+            KeyCode::Char('i') => {
+                self.open_image_form();
+                None
+            }
+            KeyCode::Char('b') => {
+                self.open_rollback_confirm();
+                None
+            }
+            _ => self.handle_nav_key_event(key_event)?,
+        };
+
+        Ok(event)
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let result = self.draw_default(frame, area);
+
+        if let Some((_, form)) = &self.form {
+            form.draw(frame, area);
+        }
+
+        if let Some((_, confirm)) = &self.rollback_confirm {
+            confirm.draw(frame, area);
+        }
+
+        result
+    }
+
+    pub fn tick(&mut self) -> Result<Option<AppEvent>> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return Ok(None);
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Ok(Some(AppEvent::UpdateServices))
+    }
+
+    pub fn is_form_open(&self) -> bool {
+        self.form.is_some() || self.rollback_confirm.is_some()
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn show_notice(&mut self, msg: &str) {
+        self.notice = Some(format!("[OK] {msg}"));
+    }
+
+    pub fn service_name(&self, id: &str) -> Option<String> {
+        self.info.items.iter().find(|s| s.id == id).map(|s| s.name.clone())
+    }
+
+    fn open_scale_form(&mut self) {
+        let Some(service) = self.get_selected_row() else { return };
+        let (id, name, desired) = (service.id.clone(), service.name.clone(), service.desired);
+
+        match desired {
+            Some(replicas) => {
+                let form = InputForm::new(&format!("Scale {name}"))
+                    .with_field("replicas", "Replicas", &replicas.to_string());
+                self.form = Some((ServiceForm::Scale(id), form));
+            }
+            None => self.show_err(&format!("{name} is not replicated, only replicated services can be scaled")),
+        }
+    }
+
+    fn open_image_form(&mut self) {
+        let Some(service) = self.get_selected_row() else { return };
+        let form = InputForm::new(&format!("Update image of {}", service.name))
+            .with_field("image", "Image", &service.image);
+        self.form = Some((ServiceForm::UpdateImage(service.id.clone()), form));
+    }
+
+    fn open_rollback_confirm(&mut self) {
+        let Some(service) = self.get_selected_row() else { return };
+        let title = format!("Roll back {}", service.name);
+        let items = vec![format!("{} ({}): back to its previous spec", service.name, service.image)];
+        self.rollback_confirm = Some((service.id.clone(), ConfirmPopup::new(title, items, "roll back")));
+    }
+
+    fn handle_confirm_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let (_, confirm) = self.rollback_confirm.as_mut()?;
+        match confirm.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.rollback_confirm = None;
+                None
+            }
+            PopupAction::Submit => {
+                let (id, _) = self.rollback_confirm.take()?;
+                Some(AppEvent::RollbackService(id))
+            }
+        }
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let (kind, form) = self.form.as_mut()?;
+
+        match form.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => {
+                let event = match kind {
+                    ServiceForm::Scale(id) => match form.value("replicas").trim().parse::<u32>() {
+                        Ok(replicas) => AppEvent::ScaleService(id.clone(), replicas as i64),
+                        Err(_) => {
+                            form.set_err("Replicas must be a number of 0 or more");
+                            return None;
+                        }
+                    },
+                    ServiceForm::UpdateImage(id) => match form.value("image").trim() {
+                        "" => {
+                            form.set_err("Image is required");
+                            return None;
+                        }
+                        image => AppEvent::UpdateServiceImage(id.clone(), image.to_string()),
+                    },
+                };
+                self.form = None;
+                Some(event)
+            }
+        }
+    }
+}
+
+impl ServiceTableRow {
+    const fn ref_array(&self) -> [&String; 6] {
+        [&self.short_id, &self.name, &self.mode, &self.replicas, &self.image, &self.ports]
+    }
+
+    pub fn from_list(services: Vec<Service>) -> Vec<Self> {
+        let mut result = services.iter().map(Self::from).collect::<Vec<Self>>();
+        result.sort_by_key(|s| s.name.clone());
+        result
+    }
+
+    fn from(service: &Service) -> Self {
+        let id = service.id.as_deref().unwrap_or("-");
+        let spec = service.spec.clone().unwrap_or_default();
+        let mode = spec.mode.unwrap_or_default();
+
+        let (mode_name, desired) = match (&mode.replicated, &mode.replicated_job, &mode.global_job) {
+            (Some(replicated), _, _) => ("replicated", Some(replicated.replicas.unwrap_or(1))),
+            (_, Some(_), _) => ("replicated job", None),
+            (_, _, Some(_)) => ("global job", None),
+            _ => ("global", None),
+        };
+
+        let status = service.service_status.clone().unwrap_or_default();
+        let running = status.running_tasks.unwrap_or_default();
+        let desired_tasks = status.desired_tasks.unwrap_or_default();
+
+        // Images are pinned to a digest when deployed, which only adds noise here.
+        let image = spec.task_template
+            .and_then(|t| t.container_spec)
+            .and_then(|c| c.image)
+            .map(|image| image.split('@').next().unwrap_or_default().to_string())
+            .unwrap_or_else(|| "-".to_string());
+
+        let ports = service.endpoint.as_ref()
+            .and_then(|e| e.ports.clone())
+            .unwrap_or_default()
+            .iter()
+            .map(|p| {
+                let protocol = p.protocol.map_or("tcp".to_string(), |p| p.to_string());
+                format!("*:{}->{}/{protocol}", p.published_port.unwrap_or_default(), p.target_port.unwrap_or_default())
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        Self {
+            id: id.to_string(),
+            short_id: format!("{}...", &id[..id.len().min(12)]),
+            name: spec.name.unwrap_or_else(|| "-".to_string()),
+            mode: mode_name.to_string(),
+            replicas: format!("{running}/{desired_tasks}"),
+            image,
+            ports: if ports.is_empty() { "-".to_string() } else { ports },
+            desired,
+            is_converged: running == desired_tasks,
+        }
+    }
+}
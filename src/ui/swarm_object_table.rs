@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::common::{PopupAction, TableStyle, date_string, render_footer};
+use super::dependents_panel::split_panel_area;
+use super::input_form::InputForm;
+use crate::event::AppEvent;
+use crate::ui::resource_table::{ResourceTable, ResourceTableInfo};
+use crate::utils::parse_key_values;
+use bollard::secret::{Config, Secret, Service, TaskSpecContainerSpec};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::style::Stylize;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::{Style, palette::tailwind},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Cell, HighlightSpacing, Paragraph, Row, Table},
+};
+
+const ROW_HEIGHT: usize = 3;
+const REFRESH_AFTER_TICK: u8 = 10;
+const DEFAULT_FOOTER: &str = " <C> create | <U> used by | <Del/D> remove";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwarmObjectKind {
+    Secret,
+    Config,
+}
+
+impl fmt::Display for SwarmObjectKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Secret => write!(f, "secret"),
+            Self::Config => write!(f, "config"),
+        }
+    }
+}
+
+/// A secret or config to create, its data read from `file` or typed in as `value`.
+#[derive(Clone)]
+pub struct NewSwarmObject {
+    pub name: String,
+    pub file: Option<String>,
+    pub value: Option<String>,
+    pub labels: HashMap<String, String>,
+}
+
+/// Leaves the typed value out, so a secret never ends up in a debug print of the events.
+impl fmt::Debug for NewSwarmObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NewSwarmObject")
+            .field("name", &self.name)
+            .field("file", &self.file)
+            .field("value", &self.value.as_ref().map(|_| "<redacted>"))
+            .field("labels", &self.labels)
+            .finish()
+    }
+}
+
+/// Secrets or configs of the swarm, both only holding a name, labels and data that is never shown.
+pub struct SwarmObjectTable {
+    kind: SwarmObjectKind,
+    style: TableStyle,
+    skipped_tick_count_for_refresh: u8,
+    info: ResourceTableInfo<SwarmObjectTableRow>,
+    form: Option<InputForm>,
+    show_used_by: bool,
+    err: Option<String>,
+    notice: Option<String>,
+}
+
+pub struct SwarmObjectTableRow {
+    id: String,
+    short_id: String,
+    name: String,
+    created_at: String,
+    updated_at: String,
+    labels: String,
+    used_by: Vec<String>,
+}
+
+impl ResourceTable for SwarmObjectTable {
+    type RowType = SwarmObjectTableRow;
+
+    fn get_table_info(&mut self) -> &mut ResourceTableInfo<Self::RowType> {
+        &mut self.info
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["ID", "Name", "Created At", "Updated At", "Labels", "Services"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style)
+            .height(1);
+
+        self.info.row_heights.clear();
+
+        let rows = self.info.items.iter().enumerate().map(|(index, object)| {
+            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+
+            if index < self.info.items.len() - 1 {
+                self.info.row_heights.push(ROW_HEIGHT);
+            }
+
+            let service_count = object.used_by.len().to_string();
+            object.ref_array().into_iter()
+                .chain([&service_count])
+                .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                .collect::<Row>()
+                .style(row_style)
+                .height(ROW_HEIGHT as u16)
+        });
+
+        let widths = vec![
+            Constraint::Length(15),
+            Constraint::Min(15),
+            Constraint::Length(20),
+            Constraint::Length(20),
+            Constraint::Min(15),
+            Constraint::Length(9),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(self.style.selected_row_style)
+            .highlight_symbol(Text::from(vec!["".into(), " ● ".into()]))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, area, &mut self.info.state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let mut border_style = None;
+        let mut footer_text = DEFAULT_FOOTER.to_string();
+
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
+
+        if let Some(err) = &self.err {
+            border_style = Some(Style::new().red());
+            footer_text = err.clone();
+        }
+
+        render_footer(frame, area, footer_text, border_style);
+    }
+}
+
+impl SwarmObjectTable {
+    pub fn new(kind: SwarmObjectKind) -> Self {
+        Self {
+            kind,
+            style: TableStyle::default(),
+            skipped_tick_count_for_refresh: 0,
+            info: ResourceTableInfo::default(),
+            form: None,
+            show_used_by: false,
+            err: None,
+            notice: None,
+        }
+    }
+
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
+            self.err = None;
+            self.notice = None;
+            return Ok(None);
+        }
+
+        let event = match key_event.code {
+            KeyCode::Char('c') => {
+                self.open_create_form();
+                None
+            }
+            KeyCode::Char('u') => {
+                self.show_used_by = !self.show_used_by;
+                None
+            }
+            KeyCode::Esc if self.show_used_by => {
+                self.show_used_by = false;
+                None
+            }
+            KeyCode::Delete | KeyCode::Char('d') => {
+                let kind = self.kind;
+                self.get_selected_row().map(|object| AppEvent::RemoveSwarmObject(kind, object.id.clone()))
+            }
+            _ => self.handle_nav_key_event(key_event)?,
+        };
+
+        Ok(event)
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let (table_area, panel_area) = split_panel_area(area, self.show_used_by);
+        let result = self.draw_default(frame, table_area);
+
+        if self.show_used_by {
+            self.render_used_by(frame, panel_area);
+        }
+
+        if let Some(form) = &self.form {
+            form.draw(frame, area);
+        }
+
+        result
+    }
+
+    pub fn tick(&mut self) -> Result<Option<AppEvent>> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return Ok(None);
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Ok(Some(AppEvent::UpdateSwarmObjects(self.kind)))
+    }
+
+    pub fn is_form_open(&self) -> bool {
+        self.form.is_some()
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn show_created(&mut self, name: &str) {
+        self.notice = Some(format!("[OK] Created {} {name}", self.kind));
+        if let Some(index) = self.info.items.iter().position(|o| o.name == name) {
+            self.select_row(index);
+        }
+    }
+
+    pub fn show_removed(&mut self, id: &str) {
+        let name = self.info.items.iter().find(|o| o.id == id).map_or(id, |o| o.name.as_str());
+        self.notice = Some(format!("[OK] Removed {} {name}", self.kind));
+    }
+
+    fn render_used_by(&self, frame: &mut Frame, area: Rect) {
+        let services = self.info.state.selected()
+            .and_then(|index| self.info.items.get(index))
+            .map_or(vec![], |object| object.used_by.clone());
+
+        let lines = match services.is_empty() {
+            true => vec![Line::from(" Not used by any service").fg(tailwind::SLATE.c400)],
+            false => services.into_iter().map(|name| Line::from(format!(" {name}"))).collect(),
+        };
+
+        let block = Block::bordered()
+            .border_type(BorderType::Plain)
+            .border_style(Style::new().fg(tailwind::SLATE.c600))
+            .title(Line::from(" Used by services ").fg(tailwind::SLATE.c200))
+            .title_bottom(Line::from(" <U> close ").fg(tailwind::SLATE.c400));
+
+        frame.render_widget(Paragraph::new(lines).style(self.style.row_style).block(block), area);
+    }
+
+    /// The value of a secret is typed into a masked field, a config's value is shown as typed.
+    fn open_create_form(&mut self) {
+        let form = InputForm::new(&format!("Create {}", self.kind))
+            .with_field("name", "Name", "")
+            .with_field("file", "From file", "");
+
+        let form = match self.kind {
+            SwarmObjectKind::Secret => form.with_secret_field("value", "Or value"),
+            SwarmObjectKind::Config => form.with_field("value", "Or value", ""),
+        };
+
+        self.form = Some(form.with_field("labels", "Labels (k=v)", ""));
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let form = self.form.as_mut()?;
+
+        match form.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => match parse_create_form(form) {
+                Ok(object) => {
+                    self.form = None;
+                    Some(AppEvent::CreateSwarmObject(self.kind, Box::new(object)))
+                }
+                Err(err) => {
+                    form.set_err(&err);
+                    None
+                }
+            },
+        }
+    }
+}
+
+fn parse_create_form(form: &InputForm) -> Result<NewSwarmObject, String> {
+    let name = form.value("name");
+    if name.is_empty() {
+        return Err("Name is required".to_string());
+    }
+
+    let file = Some(form.value("file")).filter(|f| !f.is_empty());
+    let value = Some(form.value("value")).filter(|v| !v.is_empty());
+    if file.is_some() == value.is_some() {
+        return Err("Either a file or a value is required, not both".to_string());
+    }
+
+    Ok(NewSwarmObject { name, file, value, labels: parse_key_values(&form.value("labels"))? })
+}
+
+impl SwarmObjectTableRow {
+    const fn ref_array(&self) -> [&String; 5] {
+        [&self.short_id, &self.name, &self.created_at, &self.updated_at, &self.labels]
+    }
+
+    pub fn from_secrets(secrets: Vec<Secret>, services: &[Service]) -> Vec<Self> {
+        let rows = secrets.into_iter().map(|secret| {
+            let spec = secret.spec.unwrap_or_default();
+            let id = secret.id.unwrap_or_default();
+            let name = spec.name.unwrap_or_default();
+            let used_by = services_using(services, |c| {
+                c.secrets.iter().flatten().any(|s| s.secret_id.as_ref() == Some(&id) || s.secret_name.as_ref() == Some(&name))
+            });
+            Self::from(id, name, secret.created_at, secret.updated_at, spec.labels, used_by)
+        });
+        sorted(rows.collect())
+    }
+
+    pub fn from_configs(configs: Vec<Config>, services: &[Service]) -> Vec<Self> {
+        let rows = configs.into_iter().map(|config| {
+            let spec = config.spec.unwrap_or_default();
+            let id = config.id.unwrap_or_default();
+            let name = spec.name.unwrap_or_default();
+            let used_by = services_using(services, |c| {
+                c.configs.iter().flatten().any(|s| s.config_id.as_ref() == Some(&id) || s.config_name.as_ref() == Some(&name))
+            });
+            Self::from(id, name, config.created_at, config.updated_at, spec.labels, used_by)
+        });
+        sorted(rows.collect())
+    }
+
+    fn from(
+        id: String,
+        name: String,
+        created_at: Option<String>,
+        updated_at: Option<String>,
+        labels: Option<HashMap<String, String>>,
+        used_by: Vec<String>,
+    ) -> Self {
+        let mut labels = labels.unwrap_or_default().into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<String>>();
+        labels.sort();
+
+        Self {
+            short_id: format!("{}...", &id[..id.len().min(12)]),
+            id,
+            name,
+            created_at: date_string(created_at.as_deref().unwrap_or_default()),
+            updated_at: date_string(updated_at.as_deref().unwrap_or_default()),
+            labels: if labels.is_empty() { "-".to_string() } else { labels.join(", ") },
+            used_by,
+        }
+    }
+}
+
+fn sorted(mut rows: Vec<SwarmObjectTableRow>) -> Vec<SwarmObjectTableRow> {
+    rows.sort_by_key(|r| r.name.clone());
+    rows
+}
+
+/// Names of the services whose container spec matches `uses`, sorted.
+fn services_using(services: &[Service], uses: impl Fn(&TaskSpecContainerSpec) -> bool) -> Vec<String> {
+    let mut names = services.iter()
+        .filter_map(|service| {
+            let spec = service.spec.as_ref()?;
+            let container_spec = spec.task_template.as_ref()?.container_spec.as_ref()?;
+            uses(container_spec).then(|| spec.name.clone().unwrap_or_default())
+        })
+        .collect::<Vec<String>>();
+    names.sort();
+    names
+}
@@ -0,0 +1,489 @@
+use std::collections::HashSet;
+
+use bollard::secret::{BuildCache, ContainerSummary, ImageSummary, Network, SystemDataUsageResponse, Volume};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Style, Stylize, palette::tailwind},
+    text::{Line, Text},
+    widgets::{Block, BorderType, Cell, Gauge, HighlightSpacing, Row, Table},
+};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+use crate::event::AppEvent;
+
+use super::common::{PopupAction, TableStyle, render_footer, size_string};
+use super::prune_preview::PrunePreview;
+use super::resource_table::{ResourceTable, ResourceTableInfo};
+
+const ROW_HEIGHT: usize = 3;
+const REFRESH_AFTER_TICK: u8 = 30;
+const DEFAULT_FOOTER: &str = " <Ent/P> prune preview | <R> refresh";
+const DANGLING_TAG: &str = "<none>:<none>";
+const PREDEFINED_NETWORKS: [&str; 3] = ["bridge", "host", "none"];
+
+/// Disk usage of the daemon as reported by `system df`, with a prune action per resource type.
+#[derive(Default)]
+pub struct SystemView {
+    style: TableStyle,
+    skipped_tick_count_for_refresh: u8,
+    info: ResourceTableInfo<PruneTargetRow>,
+    usage: Vec<UsageRow>,
+    prune_preview: Option<(PruneTarget, PrunePreview)>,
+    err: Option<String>,
+    notice: Option<String>,
+}
+
+#[derive(Display, EnumIter, Clone, Copy, Debug, PartialEq)]
+pub enum PruneTarget {
+    #[strum(to_string = "Stopped containers")]
+    StoppedContainers,
+
+    #[strum(to_string = "Dangling images")]
+    DanglingImages,
+
+    #[strum(to_string = "Unused images")]
+    UnusedImages,
+
+    #[strum(to_string = "Unused volumes")]
+    UnusedVolumes,
+
+    #[strum(to_string = "Unused networks")]
+    UnusedNetworks,
+
+    #[strum(to_string = "Build cache")]
+    BuildCache,
+}
+
+pub struct PruneTargetRow {
+    target: PruneTarget,
+    items: Vec<String>,
+    reclaimable: i64,
+}
+
+struct UsageRow {
+    name: &'static str,
+    total: usize,
+    active: usize,
+    size: i64,
+    reclaimable: i64,
+}
+
+impl ResourceTable for SystemView {
+    type RowType = PruneTargetRow;
+
+    fn get_table_info(&mut self) -> &mut ResourceTableInfo<Self::RowType> {
+        &mut self.info
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["Prune", "Items", "Reclaimable", "Equivalent"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style)
+            .height(1);
+
+        self.info.row_heights.clear();
+
+        let rows = self.info.items.iter().enumerate().map(|(index, row)| {
+            let mut row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            if row.items.is_empty() {
+                row_style = row_style.fg(tailwind::SLATE.c500);
+            }
+
+            if index < self.info.items.len() - 1 {
+                self.info.row_heights.push(ROW_HEIGHT);
+            }
+
+            [
+                row.target.to_string(),
+                row.items.len().to_string(),
+                size_string(row.reclaimable),
+                row.target.command().to_string(),
+            ]
+            .into_iter()
+            .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+            .collect::<Row>()
+            .style(row_style)
+            .height(ROW_HEIGHT as u16)
+        });
+
+        let widths = [Constraint::Length(22), Constraint::Length(8), Constraint::Length(14), Constraint::Min(20)];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(self.style.selected_row_style)
+            .highlight_symbol(Text::from(vec!["".into(), " ● ".into()]))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, area, &mut self.info.state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let mut border_style = None;
+        let mut footer_text = DEFAULT_FOOTER.to_string();
+
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
+
+        if let Some(err) = &self.err {
+            border_style = Some(Style::new().red());
+            footer_text = err.clone();
+        }
+
+        render_footer(frame, area, footer_text, border_style);
+    }
+}
+
+impl SystemView {
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        if self.prune_preview.is_some() {
+            return Ok(self.handle_prune_key_event(key_event));
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
+            self.err = None;
+            self.notice = None;
+            return Ok(None);
+        }
+
+        let event = match key_event.code {
+            KeyCode::Enter | KeyCode::Char('p') => {
+                self.open_prune_preview();
+                None
+            }
+            KeyCode::Char('r') => Some(AppEvent::UpdateSystem),
+            _ => self.handle_nav_key_event(key_event)?,
+        };
+        Ok(event)
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        use Constraint::{Length, Min};
+        let [gauges_area, usage_area, targets_area] = Layout::vertical([Length(3), Length(6), Min(0)]).areas(area);
+
+        self.render_gauges(frame, gauges_area);
+        self.render_usage_table(frame, usage_area);
+        let result = self.draw_default(frame, targets_area);
+
+        if let Some((_, preview)) = &self.prune_preview {
+            preview.draw(frame, area);
+        }
+
+        result
+    }
+
+    pub fn tick(&mut self) -> Result<Option<AppEvent>> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return Ok(None);
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Ok(Some(AppEvent::UpdateSystem))
+    }
+
+    pub fn update(&mut self, df: SystemDataUsageResponse, networks: Vec<Network>) {
+        let images = df.images.unwrap_or_default();
+        let containers = df.containers.unwrap_or_default();
+        let volumes = df.volumes.unwrap_or_default();
+        let build_cache = df.build_cache.unwrap_or_default();
+
+        self.usage = vec![
+            UsageRow::from_images(&images, df.layers_size),
+            UsageRow::from_containers(&containers),
+            UsageRow::from_volumes(&volumes),
+            UsageRow::from_build_cache(&build_cache),
+        ];
+
+        let rows = PruneTarget::iter()
+            .map(|target| match target {
+                PruneTarget::StoppedContainers => PruneTargetRow::from_containers(&containers),
+                PruneTarget::DanglingImages | PruneTarget::UnusedImages => PruneTargetRow::from_images(target, &images),
+                PruneTarget::UnusedVolumes => PruneTargetRow::from_volumes(&volumes),
+                PruneTarget::UnusedNetworks => PruneTargetRow::from_networks(&networks, &containers),
+                PruneTarget::BuildCache => PruneTargetRow::from_build_cache(&build_cache),
+            })
+            .collect::<Vec<PruneTargetRow>>();
+        self.update_with_items(rows);
+    }
+
+    pub fn show_prune_result(&mut self, target: PruneTarget, deleted_count: usize, space_reclaimed: i64) {
+        self.notice = Some(format!(
+            "[OK] {target}: removed {deleted_count} item(s), reclaimed {}",
+            size_string(space_reclaimed)
+        ));
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    fn open_prune_preview(&mut self) {
+        let Some(row) = self.get_selected_row() else { return };
+        let title = format!("Prune {}", row.target.to_string().to_lowercase());
+        let preview = PrunePreview::new(title, row.items.clone(), row.reclaimable);
+        self.prune_preview = Some((row.target, preview));
+    }
+
+    fn handle_prune_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let (target, preview) = self.prune_preview.as_mut()?;
+        match preview.handle_key_event(key_event) {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.prune_preview = None;
+                None
+            }
+            PopupAction::Submit => {
+                let target = *target;
+                self.prune_preview = None;
+                Some(AppEvent::SystemPrune(target))
+            }
+        }
+    }
+
+    fn render_gauges(&self, frame: &mut Frame, area: Rect) {
+        let areas = Layout::horizontal(vec![Constraint::Ratio(1, 4); 4]).split(area);
+
+        for (usage, area) in self.usage.iter().zip(areas.iter()) {
+            let ratio = if usage.size > 0 { (usage.reclaimable as f64 / usage.size as f64).clamp(0.0, 1.0) } else { 0.0 };
+            let label = format!("{} / {} reclaimable", size_string(usage.reclaimable), size_string(usage.size));
+
+            let block = Block::bordered()
+                .border_type(BorderType::Plain)
+                .border_style(Style::new().fg(tailwind::BLUE.c400))
+                .title(Line::from(format!(" {} ", usage.name)).fg(tailwind::SLATE.c200));
+
+            let gauge = Gauge::default()
+                .block(block)
+                .gauge_style(Style::new().fg(tailwind::AMBER.c400).bg(tailwind::SLATE.c800))
+                .ratio(ratio)
+                .label(label);
+
+            frame.render_widget(gauge, *area);
+        }
+    }
+
+    fn render_usage_table(&self, frame: &mut Frame, area: Rect) {
+        let header = ["Type", "Total", "Active", "Size", "Reclaimable"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style)
+            .height(1);
+
+        let rows = self.usage.iter().enumerate().map(|(index, usage)| {
+            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            let percent = if usage.size > 0 { usage.reclaimable * 100 / usage.size } else { 0 };
+
+            [
+                usage.name.to_string(),
+                usage.total.to_string(),
+                usage.active.to_string(),
+                size_string(usage.size),
+                format!("{} ({percent}%)", size_string(usage.reclaimable)),
+            ]
+            .into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(row_style)
+        });
+
+        let widths = [
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Min(18),
+        ];
+
+        frame.render_widget(Table::new(rows, widths).header(header), area);
+    }
+}
+
+impl PruneTarget {
+    fn command(self) -> &'static str {
+        match self {
+            Self::StoppedContainers => "docker container prune",
+            Self::DanglingImages => "docker image prune",
+            Self::UnusedImages => "docker image prune -a",
+            Self::UnusedVolumes => "docker volume prune -a",
+            Self::UnusedNetworks => "docker network prune",
+            Self::BuildCache => "docker builder prune -a",
+        }
+    }
+}
+
+impl UsageRow {
+    fn from_images(images: &[ImageSummary], layers_size: Option<i64>) -> Self {
+        // Same as the docker CLI: everything but the layers unique to images in use is reclaimable.
+        let size = layers_size.unwrap_or_else(|| images.iter().map(|i| i.size).sum());
+        let used = images.iter()
+            .filter(|i| i.containers > 0 && i.shared_size >= 0)
+            .map(|i| i.size - i.shared_size)
+            .sum::<i64>();
+
+        Self {
+            name: "Images",
+            total: images.len(),
+            active: images.iter().filter(|i| i.containers > 0).count(),
+            size,
+            reclaimable: (size - used).max(0),
+        }
+    }
+
+    fn from_containers(containers: &[ContainerSummary]) -> Self {
+        Self {
+            name: "Containers",
+            total: containers.len(),
+            active: containers.iter().filter(|c| !is_container_stopped(c)).count(),
+            size: containers.iter().filter_map(|c| c.size_rw).sum(),
+            reclaimable: containers.iter().filter(|c| is_container_stopped(c)).filter_map(|c| c.size_rw).sum(),
+        }
+    }
+
+    fn from_volumes(volumes: &[Volume]) -> Self {
+        let usage = volumes.iter()
+            .filter_map(|v| v.usage_data.as_ref())
+            .filter(|u| u.size >= 0)
+            .collect::<Vec<_>>();
+
+        Self {
+            name: "Local Volumes",
+            total: volumes.len(),
+            active: volumes.iter().filter(|v| v.usage_data.as_ref().is_some_and(|u| u.ref_count > 0)).count(),
+            size: usage.iter().map(|u| u.size).sum(),
+            reclaimable: usage.iter().filter(|u| u.ref_count == 0).map(|u| u.size).sum(),
+        }
+    }
+
+    fn from_build_cache(build_cache: &[BuildCache]) -> Self {
+        let unshared = build_cache.iter()
+            .filter(|c| !c.shared.unwrap_or_default())
+            .collect::<Vec<&BuildCache>>();
+
+        Self {
+            name: "Build Cache",
+            total: build_cache.len(),
+            active: build_cache.iter().filter(|c| c.in_use.unwrap_or_default()).count(),
+            size: unshared.iter().filter_map(|c| c.size).sum(),
+            reclaimable: unshared.iter().filter(|c| !c.in_use.unwrap_or_default()).filter_map(|c| c.size).sum(),
+        }
+    }
+}
+
+impl PruneTargetRow {
+    fn from_containers(containers: &[ContainerSummary]) -> Self {
+        let stopped = containers.iter().filter(|c| is_container_stopped(c)).collect::<Vec<&ContainerSummary>>();
+
+        let items = stopped.iter()
+            .map(|c| {
+                let name = c.names.iter().flatten().next().map_or("-", |name| name.trim_start_matches('/'));
+                format!("{name} ({})", size_string(c.size_rw.unwrap_or_default()))
+            })
+            .collect();
+
+        Self {
+            target: PruneTarget::StoppedContainers,
+            items,
+            reclaimable: stopped.iter().filter_map(|c| c.size_rw).sum(),
+        }
+    }
+
+    /// Mirrors the daemon's prune rules: dangling-only prune removes untagged leaf images without
+    /// containers, a full prune removes every image without containers.
+    fn from_images(target: PruneTarget, images: &[ImageSummary]) -> Self {
+        let parents = images.iter().map(|i| i.parent_id.as_str()).collect::<HashSet<&str>>();
+
+        let candidates = images.iter()
+            .filter(|i| i.containers == 0)
+            .filter(|i| target == PruneTarget::UnusedImages || (is_untagged(i) && !parents.contains(i.id.as_str())))
+            .collect::<Vec<&ImageSummary>>();
+
+        let unique_size = |image: &ImageSummary| match image.shared_size {
+            shared if shared >= 0 => image.size - shared,
+            _ => image.size,
+        };
+
+        let items = candidates.iter()
+            .map(|i| {
+                let id = i.id.trim_start_matches("sha256:");
+                let tags = if is_untagged(i) { DANGLING_TAG.to_string() } else { i.repo_tags.join(", ") };
+                format!("{} {tags} ({})", &id[..id.len().min(12)], size_string(unique_size(i)))
+            })
+            .collect();
+
+        Self {
+            target,
+            items,
+            reclaimable: candidates.iter().map(|i| unique_size(i)).sum(),
+        }
+    }
+
+    fn from_volumes(volumes: &[Volume]) -> Self {
+        let unused = volumes.iter()
+            .filter(|v| v.usage_data.as_ref().is_some_and(|u| u.ref_count == 0))
+            .collect::<Vec<&Volume>>();
+
+        let size = |volume: &Volume| volume.usage_data.as_ref().map_or(0, |u| u.size.max(0));
+
+        Self {
+            target: PruneTarget::UnusedVolumes,
+            items: unused.iter().map(|v| format!("{} ({})", v.name, size_string(size(v)))).collect(),
+            reclaimable: unused.iter().map(|v| size(v)).sum(),
+        }
+    }
+
+    /// Networks without containers, the predefined and swarm ingress networks being kept by the daemon.
+    fn from_networks(networks: &[Network], containers: &[ContainerSummary]) -> Self {
+        let attached = containers.iter()
+            .filter_map(|c| c.network_settings.as_ref().and_then(|s| s.networks.as_ref()))
+            .flat_map(|networks| networks.keys().map(String::as_str))
+            .collect::<HashSet<&str>>();
+
+        let mut items = networks.iter()
+            .filter_map(|n| n.name.as_deref().map(|name| (name, n)))
+            .filter(|(name, _)| !PREDEFINED_NETWORKS.contains(name) && !attached.contains(name))
+            .filter(|(_, network)| !network.ingress.unwrap_or_default())
+            .map(|(name, network)| format!("{name} ({})", network.driver.as_deref().unwrap_or("-")))
+            .collect::<Vec<String>>();
+        items.sort();
+
+        Self { target: PruneTarget::UnusedNetworks, items, reclaimable: 0 }
+    }
+
+    fn from_build_cache(build_cache: &[BuildCache]) -> Self {
+        let unused = build_cache.iter()
+            .filter(|c| !c.in_use.unwrap_or_default())
+            .collect::<Vec<&BuildCache>>();
+
+        let items = unused.iter()
+            .map(|c| {
+                let id = c.id.as_deref().unwrap_or("-");
+                let typ = c.typ.map_or("-".to_string(), |typ| typ.to_string());
+                let description = c.description.as_deref().unwrap_or_default();
+                format!("{} {typ} {description} ({})", &id[..id.len().min(12)], size_string(c.size.unwrap_or_default()))
+            })
+            .collect();
+
+        Self {
+            target: PruneTarget::BuildCache,
+            items,
+            reclaimable: unused.iter().filter(|c| !c.shared.unwrap_or_default()).filter_map(|c| c.size).sum(),
+        }
+    }
+}
+
+/// Whether the container would be removed by a container prune.
+fn is_container_stopped(container: &ContainerSummary) -> bool {
+    let state = container.state.map(|state| state.to_string()).unwrap_or_default();
+    !["running", "paused", "restarting"].contains(&state.as_str())
+}
+
+fn is_untagged(image: &ImageSummary) -> bool {
+    image.repo_tags.iter().all(|tag| tag == DANGLING_TAG)
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use super::common::{TableStyle, date_string, render_footer};
+use crate::event::AppEvent;
+use crate::ui::resource_table::{ResourceTable, ResourceTableInfo};
+use bollard::secret::{Node, Service, Task, TaskState};
+use color_eyre::eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Rect},
+    style::palette::tailwind,
+    text::Text,
+    widgets::{Cell, HighlightSpacing, Row, Table},
+};
+
+const ROW_HEIGHT: usize = 3;
+const REFRESH_AFTER_TICK: u8 = 10;
+
+/// Tasks of the swarm, those of a single service when opened from the services tab.
+#[derive(Default)]
+pub struct TaskTable {
+    style: TableStyle,
+    skipped_tick_count_for_refresh: u8,
+    info: ResourceTableInfo<TaskTableRow>,
+    service: Option<(String, String)>,
+    show_all: bool,
+}
+
+pub struct TaskTableRow {
+    id: String,
+    name: String,
+    image: String,
+    node: String,
+    desired_state: String,
+    state: String,
+    updated_at: String,
+    is_failed: bool,
+}
+
+impl ResourceTable for TaskTable {
+    type RowType = TaskTableRow;
+
+    fn get_table_info(&mut self) -> &mut ResourceTableInfo<Self::RowType> {
+        &mut self.info
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let header = ["ID", "Name", "Image", "Node", "Desired", "Current State", "Updated At"].into_iter()
+            .map(Cell::from)
+            .collect::<Row>()
+            .style(self.style.header_style)
+            .height(1);
+
+        self.info.row_heights.clear();
+
+        let rows = self.info.items.iter().enumerate().map(|(index, task)| {
+            let row_style = if index % 2 == 0 { self.style.row_style } else { self.style.alt_row_style };
+            let row_style = if task.is_failed { row_style.fg(tailwind::RED.c400) } else { row_style };
+
+            if index < self.info.items.len() - 1 {
+                self.info.row_heights.push(ROW_HEIGHT);
+            }
+
+            task.ref_array().into_iter()
+                .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
+                .collect::<Row>()
+                .style(row_style)
+                .height(ROW_HEIGHT as u16)
+        });
+
+        let widths = vec![
+            Constraint::Length(15),
+            Constraint::Min(15),
+            Constraint::Min(15),
+            Constraint::Min(12),
+            Constraint::Length(10),
+            Constraint::Min(20),
+            Constraint::Length(20),
+        ];
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(self.style.selected_row_style)
+            .highlight_symbol(Text::from(vec!["".into(), " ● ".into()]))
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(table, area, &mut self.info.state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let toggle_text = if self.show_all { "All" } else { "Current" };
+        let footer_text = match &self.service {
+            Some((_, name)) => format!(" <T> {toggle_text} | <A> all services | service: {name}"),
+            None => format!(" <T> {toggle_text}"),
+        };
+        render_footer(frame, area, footer_text, None);
+    }
+}
+
+impl TaskTable {
+    pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        let event = match key_event.code {
+            KeyCode::Char('t') => {
+                self.show_all = !self.show_all;
+                Some(AppEvent::UpdateTasks)
+            }
+            KeyCode::Char('a') => {
+                self.service = None;
+                Some(AppEvent::UpdateTasks)
+            }
+            _ => self.handle_nav_key_event(key_event)?,
+        };
+
+        Ok(event)
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        self.draw_default(frame, area)
+    }
+
+    pub fn tick(&mut self) -> Result<Option<AppEvent>> {
+        if self.skipped_tick_count_for_refresh <= REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh += 1;
+            return Ok(None);
+        }
+
+        self.skipped_tick_count_for_refresh = 0;
+        Ok(Some(AppEvent::UpdateTasks))
+    }
+
+    /// Limits the table to the tasks of a service, given as (id, name).
+    pub fn filter_service(&mut self, service: Option<(String, String)>) {
+        self.service = service;
+        self.info.items.clear();
+    }
+
+    pub fn service_id(&self) -> Option<&str> {
+        self.service.as_ref().map(|(id, _)| id.as_str())
+    }
+
+    /// Shows the current tasks only, unless all are toggled on, which includes the history of
+    /// tasks that were shut down by updates or failures.
+    pub fn update_tasks(&mut self, tasks: Vec<Task>, services: &[Service], nodes: &[Node]) {
+        let service_names = services.iter()
+            .filter_map(|s| Some((s.id.clone()?, s.spec.as_ref()?.name.clone()?)))
+            .collect::<HashMap<String, String>>();
+
+        let node_names = nodes.iter()
+            .filter_map(|n| Some((n.id.clone()?, n.description.as_ref()?.hostname.clone()?)))
+            .collect::<HashMap<String, String>>();
+
+        let mut rows = tasks.iter()
+            .filter(|t| self.show_all || t.desired_state != Some(TaskState::SHUTDOWN))
+            .map(|t| TaskTableRow::from(t, &service_names, &node_names))
+            .collect::<Vec<TaskTableRow>>();
+        rows.sort_by(|a, b| a.name.cmp(&b.name).then(b.updated_at.cmp(&a.updated_at)));
+
+        self.update_with_items(rows);
+    }
+}
+
+impl TaskTableRow {
+    const fn ref_array(&self) -> [&String; 7] {
+        [&self.id, &self.name, &self.image, &self.node, &self.desired_state, &self.state, &self.updated_at]
+    }
+
+    fn from(task: &Task, service_names: &HashMap<String, String>, node_names: &HashMap<String, String>) -> Self {
+        let id = task.id.as_deref().unwrap_or("-");
+        let service_id = task.service_id.clone().unwrap_or_default();
+        let service = service_names.get(&service_id).cloned().unwrap_or(service_id);
+        let node_id = task.node_id.clone().unwrap_or_default();
+
+        // Replicated tasks are numbered by slot, global ones are named after their node.
+        let name = match task.slot {
+            Some(slot) => format!("{service}.{slot}"),
+            None => format!("{service}.{}", &node_id[..node_id.len().min(12)]),
+        };
+
+        let image = task.spec.as_ref()
+            .and_then(|s| s.container_spec.as_ref())
+            .and_then(|c| c.image.as_deref())
+            .map_or("-".to_string(), |image| image.split('@').next().unwrap_or_default().to_string());
+
+        let status = task.status.clone().unwrap_or_default();
+        let state = status.state.map_or("-".to_string(), |s| s.to_string());
+        let is_failed = matches!(status.state, Some(TaskState::FAILED | TaskState::REJECTED));
+        let state = match status.err.filter(|e| !e.is_empty()) {
+            Some(err) => format!("{state}: {err}"),
+            None => state,
+        };
+
+        Self {
+            id: format!("{}...", &id[..id.len().min(12)]),
+            name,
+            image,
+            node: node_names.get(&node_id).cloned().or(Some(node_id).filter(|n| !n.is_empty())).unwrap_or_else(|| "-".to_string()),
+            desired_state: task.desired_state.map_or("-".to_string(), |s| s.to_string()),
+            state,
+            updated_at: date_string(task.updated_at.as_deref().unwrap_or_default()),
+            is_failed,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Stylize, palette::tailwind},
+    text::Line,
+};
+
+use super::common::size_string;
+
+pub struct TransferStatus {
+    label: String,
+    bytes: u64,
+    result: Option<Result<String, String>>,
+}
+
+impl TransferStatus {
+    pub fn new(label: String) -> Self {
+        Self { label, bytes: 0, result: None }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.result.is_none()
+    }
+
+    pub fn update(&mut self, bytes: u64) {
+        self.bytes = bytes;
+    }
+
+    pub fn finish(&mut self, result: Result<String, String>) {
+        self.result = Some(result);
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let line = match &self.result {
+            None if self.bytes == 0 => Line::from(format!(" {}...", self.label)).fg(tailwind::BLUE.c400),
+            None => Line::from(format!(" {}... {}", self.label, size_string(self.bytes as i64))).fg(tailwind::BLUE.c400),
+            Some(Ok(msg)) => Line::from(format!(" [OK] {msg}")).green(),
+            Some(Err(err)) => Line::from(format!(" [ERR] {}: {err}", self.label)).red(),
+        };
+        frame.render_widget(line, area);
+    }
+}
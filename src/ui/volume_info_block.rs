@@ -0,0 +1,130 @@
+use bollard::secret::Volume;
+use color_eyre::eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::{Frame, layout::Rect, text::Line};
+
+use crate::event::AppEvent;
+
+use super::common::size_string;
+use super::info_block::{ScrollInfo, ScrollableInfoBlock, get_filtered_list, get_key_value_lines, join_map};
+
+// The on-disk size comes from `system df`, which walks every volume, so refresh less often.
+const REFRESH_AFTER_TICK: u8 = 90;
+const FOOTER_TEXT: &str = " <Esc/Q> back";
+
+#[derive(Default, Clone)]
+pub struct VolumeInfoBlock {
+    data: VolumeData,
+    scroll_info: ScrollInfo,
+    skipped_tick_count_for_refresh: u8,
+}
+
+#[derive(Default, Clone)]
+pub struct VolumeData {
+    name: String,
+    driver: String,
+    mountpoint: String,
+    scope: String,
+    created_at: String,
+    status: String,
+    size: String,
+    ref_count: String,
+    options: String,
+    labels: String,
+}
+
+impl ScrollableInfoBlock for VolumeInfoBlock {
+    type Data = VolumeData;
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
+        self.handle_nav_key_event(key_event)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let title = format!("Volume: {}", self.data.name);
+        self.draw_default(frame, area, title, get_content_as_lines(&self.data), FOOTER_TEXT.to_string(), None)
+    }
+
+    fn tick(&mut self) -> Result<Option<AppEvent>> {
+        let event = if self.skipped_tick_count_for_refresh > REFRESH_AFTER_TICK {
+            self.skipped_tick_count_for_refresh = 0;
+            Some(AppEvent::UpdateVolumeInfo(self.data.name.clone()))
+        } else {
+            self.skipped_tick_count_for_refresh += 1;
+            None
+        };
+        Ok(event)
+    }
+
+    fn update_data(&mut self, data: Self::Data) {
+        self.data = data;
+    }
+
+    fn get_scroll_info(&mut self) -> &mut ScrollInfo {
+        &mut self.scroll_info
+    }
+}
+
+fn get_content_as_lines(data: &VolumeData) -> Vec<Line<'static>> {
+    let spacer = ("".to_string(), "".to_string());
+
+    let mut lines = vec![
+        ("Name: ".to_string(), data.name.clone()),
+        ("Driver: ".to_string(), data.driver.clone()),
+        ("Scope: ".to_string(), data.scope.clone()),
+        ("Created: ".to_string(), data.created_at.clone()),
+        ("Mountpoint: ".to_string(), data.mountpoint.clone()),
+        spacer.clone(),
+        ("Size: ".to_string(), data.size.clone()),
+        ("Used by: ".to_string(), data.ref_count.clone()),
+    ];
+
+    if let Some(status) = get_filtered_list(&data.status) {
+        lines.extend(vec![spacer.clone(), ("Status:".to_string(), "".to_string())]);
+        lines.extend(status);
+    }
+
+    if let Some(options) = get_filtered_list(&data.options) {
+        lines.extend(vec![spacer.clone(), ("Driver Options:".to_string(), "".to_string())]);
+        lines.extend(options);
+    }
+
+    if let Some(labels) = get_filtered_list(&data.labels) {
+        lines.extend(vec![spacer.clone(), ("Labels:".to_string(), "".to_string())]);
+        lines.extend(labels);
+    }
+
+    get_key_value_lines(lines)
+}
+
+impl VolumeData {
+    /// Builds the data from the inspected volume and, if available, its `system df` entry.
+    pub fn from(volume: Volume, usage: Option<&Volume>) -> Self {
+        let usage_data = usage.and_then(|v| v.usage_data.as_ref());
+
+        let (size, ref_count) = match usage_data {
+            Some(u) => (
+                size_string(u.size),
+                if u.ref_count >= 0 { format!("{} container(s)", u.ref_count) } else { "-".to_string() },
+            ),
+            None => ("-".to_string(), "-".to_string()),
+        };
+
+        let status = volume.status
+            .map(|s| s.keys().cloned().collect::<Vec<String>>().join("\n"))
+            .unwrap_or_default();
+
+        Self {
+            name: volume.name,
+            driver: volume.driver,
+            mountpoint: volume.mountpoint,
+            scope: volume.scope.map_or("-".to_string(), |s| s.to_string()),
+            created_at: volume.created_at.unwrap_or_else(|| "-".to_string()),
+            status,
+            size,
+            ref_count,
+            options: join_map(&volume.options, "="),
+            labels: join_map(&volume.labels, ": "),
+        }
+    }
+}
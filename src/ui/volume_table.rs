@@ -1,4 +1,4 @@
-use bollard::secret::Volume;
+use bollard::secret::{Volume, VolumeCreateOptions};
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
@@ -7,20 +7,41 @@ use ratatui::{
 use regex::Regex;
 
 use crate::{
-    event::AppEvent, ui::{common::{render_footer, TableStyle}, resource_table::{ResourceTable, ResourceTableInfo}}
+    docker::archive::ARCHIVE_EXTENSIONS,
+    event::AppEvent,
+    utils::{expand_home, is_valid_name, parse_key_values},
+    ui::{
+        common::{render_footer, PopupAction, TableStyle},
+        input_form::InputForm,
+        dependents_panel::{DependencyTarget, DependentsPanel, DependentsTable, handle_panel_key_event, split_panel_area},
+        resource_table::{ResourceTable, ResourceTableInfo},
+    },
 };
 
 const ROW_HEIGHT: usize = 3;
 const REFRESH_AFTER_TICK: u8 = 10;
 const REGEX_VOLUME_IN_USE: &str = r"\[([a-z0-9]+)\]";
-const DEFAULT_FOOTER: &str = " <Del/D> remove | <F> force remove";
+const DEFAULT_FOOTER: &str = " <Ent> details | <B> browse | <C> create | <E> backup | <I> restore | <N> clone/rename | <U> used by | <Del/D> remove | <F> force remove";
+const RESTORE_MODES: [&str; 2] = ["merge", "replace"];
+const CLONE_CONTAINER_MODES: [&str; 2] = ["keep", "rewire stopped"];
+const CLONE_SOURCE_MODES: [&str; 2] = ["keep", "remove"];
 
 #[derive(Default)]
 pub struct VolumeTable {
     style: TableStyle,
     skipped_tick_count_for_refresh: u8,
     info: ResourceTableInfo<VolumeTableRow>,
+    dependents: Option<DependentsPanel>,
+    form: Option<VolumeForm>,
     err: Option<String>,
+    notice: Option<String>,
+}
+
+enum VolumeForm {
+    Create(InputForm),
+    Backup(String, InputForm),
+    Restore(InputForm),
+    Clone(String, InputForm),
 }
 
 #[derive(Default)]
@@ -80,6 +101,11 @@ impl ResourceTable for VolumeTable {
         let mut border_style = None;
         let mut footer_text = DEFAULT_FOOTER.to_string();
 
+        if let Some(notice) = &self.notice {
+            border_style = Some(Style::new().green());
+            footer_text = notice.clone();
+        }
+
         if let Some(err) = &self.err {
             border_style = Some(Style::new().red());
             footer_text = err.clone();
@@ -89,10 +115,29 @@ impl ResourceTable for VolumeTable {
     }
 }
 
+impl DependentsTable for VolumeTable {
+    fn get_dependents(&mut self) -> &mut Option<DependentsPanel> {
+        &mut self.dependents
+    }
+
+    fn selected_dependency_target(&mut self) -> Option<DependencyTarget> {
+        self.get_selected_row().map(|r| DependencyTarget::Volume(r.name.clone()))
+    }
+}
+
 impl VolumeTable {
     pub fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<Option<AppEvent>> {
-        if self.err.is_some() {
+        if self.form.is_some() {
+            return Ok(self.handle_form_key_event(key_event));
+        }
+
+        if let Some(event) = handle_panel_key_event(&mut self.dependents, key_event) {
+            return Ok(event);
+        }
+
+        if self.err.is_some() || self.notice.is_some() {
             self.err = None;
+            self.notice = None;
             return Ok(None);
         }
 
@@ -103,14 +148,49 @@ impl VolumeTable {
             KeyCode::Char('f') => {
                 self.get_selected_row().map(|volume| AppEvent::RemoveVolume(volume.name.clone(), true))
             }
-            _ => self.handle_nav_key_event(key_event)?
+            KeyCode::Enter => self.get_selected_row().map(|volume| AppEvent::GoToVolumeDetails(volume.name.clone())),
+            KeyCode::Char('c') => {
+                self.form = Some(VolumeForm::Create(new_create_form()));
+                None
+            }
+            KeyCode::Char('e') => {
+                self.form = self.get_selected_row()
+                    .map(|volume| volume.name.clone())
+                    .map(|name| VolumeForm::Backup(name.clone(), new_backup_form(&name)));
+                None
+            }
+            KeyCode::Char('n') => {
+                self.form = self.get_selected_row()
+                    .map(|volume| volume.name.clone())
+                    .map(|name| VolumeForm::Clone(name.clone(), new_clone_form(&name)));
+                None
+            }
+            KeyCode::Char('i') => {
+                let name = self.get_selected_row().map(|volume| volume.name.clone()).unwrap_or_default();
+                self.form = Some(VolumeForm::Restore(new_restore_form(&name)));
+                None
+            }
+            KeyCode::Char('b') => self.get_selected_row().map(|volume| AppEvent::BrowseVolume(volume.name.clone())),
+            KeyCode::Char('u') => self.open_dependents(),
+            _ => self.handle_nav_key_event(key_event)?.or_else(|| self.retarget_dependents()),
         };
 
         Ok(event)
     }
 
     pub fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
-        self.draw_default(frame, area)
+        let (table_area, panel_area) = split_panel_area(area, self.dependents.is_some());
+        let result = self.draw_default(frame, table_area);
+
+        if let Some(dependents) = self.dependents.as_mut() {
+            dependents.draw(frame, panel_area);
+        }
+
+        if let Some(VolumeForm::Create(form) | VolumeForm::Backup(_, form) | VolumeForm::Restore(form) | VolumeForm::Clone(_, form)) = &self.form {
+            form.draw(frame, area);
+        }
+
+        result
     }
 
     pub fn tick(&mut self) -> Result<Option<AppEvent>> {
@@ -132,6 +212,60 @@ impl VolumeTable {
 
         self.err = Some(format!("[ERR] {}", err_msg))
     }
+
+    pub fn is_form_open(&self) -> bool {
+        self.form.is_some()
+    }
+
+    pub fn show_err(&mut self, err: &str) {
+        self.err = Some(format!("[ERR] {err}"));
+    }
+
+    pub fn show_created(&mut self, name: &str) {
+        self.notice = Some(format!("[OK] Created volume {name}"));
+        if let Some(index) = self.info.items.iter().position(|v| v.name == name) {
+            self.select_row(index);
+        }
+    }
+
+    fn handle_form_key_event(&mut self, key_event: KeyEvent) -> Option<AppEvent> {
+        let action = match self.form.as_mut()? {
+            VolumeForm::Create(form) | VolumeForm::Backup(_, form) | VolumeForm::Restore(form) | VolumeForm::Clone(_, form) => {
+                form.handle_key_event(key_event)
+            }
+        };
+
+        match action {
+            PopupAction::None => None,
+            PopupAction::Close => {
+                self.form = None;
+                None
+            }
+            PopupAction::Submit => self.submit_form(),
+        }
+    }
+
+    fn submit_form(&mut self) -> Option<AppEvent> {
+        let result = match self.form.as_ref()? {
+            VolumeForm::Create(form) => parse_create_form(form).map(|options| AppEvent::CreateVolume(Box::new(options))),
+            VolumeForm::Backup(name, form) => parse_backup_form(form).map(|path| AppEvent::BackupVolume(name.clone(), path)),
+            VolumeForm::Restore(form) => parse_restore_form(form),
+            VolumeForm::Clone(name, form) => parse_clone_form(name, form),
+        };
+
+        match result {
+            Ok(event) => {
+                self.form = None;
+                Some(event)
+            }
+            Err(err) => {
+                if let Some(VolumeForm::Create(form) | VolumeForm::Backup(_, form) | VolumeForm::Restore(form) | VolumeForm::Clone(_, form)) = self.form.as_mut() {
+                    form.set_err(&err);
+                }
+                None
+            }
+        }
+    }
 }
 
 impl VolumeTableRow {
@@ -153,3 +287,87 @@ impl VolumeTableRow {
         }
     }
 }
+
+fn new_create_form() -> InputForm {
+    InputForm::new("Create volume")
+        .with_field("name", "Name", "")
+        .with_field("driver", "Driver", "local")
+        .with_field("options", "Driver options (key=value ...)", "")
+        .with_field("labels", "Labels (key=value ...)", "")
+}
+
+fn new_backup_form(name: &str) -> InputForm {
+    InputForm::new(&format!("Back up volume {name}"))
+        .with_field("path", "Archive path", &format!("./{name}.tar.gz"))
+}
+
+fn new_restore_form(name: &str) -> InputForm {
+    InputForm::new("Restore volume")
+        .with_field("path", "Archive path", &format!("./{name}.tar.gz"))
+        .with_field("volume", "Into volume (created if missing)", name)
+        .with_choice("mode", "Existing content", &RESTORE_MODES)
+}
+
+fn new_clone_form(name: &str) -> InputForm {
+    InputForm::new(&format!("Clone or rename volume {name}"))
+        .with_field("name", "New name", &format!("{name}-copy"))
+        .with_choice("containers", "Containers using it", &CLONE_CONTAINER_MODES)
+        .with_choice("original", "Original volume", &CLONE_SOURCE_MODES)
+}
+
+fn parse_create_form(form: &InputForm) -> Result<VolumeCreateOptions, String> {
+    let name = form.value("name");
+    if !name.is_empty() && !is_valid_name(&name) {
+        return Err("Name must match [a-zA-Z0-9][a-zA-Z0-9_.-]+".to_string());
+    }
+
+    let driver = form.value("driver");
+    if driver.is_empty() {
+        return Err("Driver is required".to_string());
+    }
+
+    Ok(VolumeCreateOptions {
+        name: Some(name).filter(|n| !n.is_empty()),
+        driver: Some(driver),
+        driver_opts: Some(parse_key_values(&form.value("options"))?),
+        labels: Some(parse_key_values(&form.value("labels"))?),
+        ..Default::default()
+    })
+}
+
+fn parse_backup_form(form: &InputForm) -> Result<String, String> {
+    let path = expand_home(&form.value("path"));
+    if !ARCHIVE_EXTENSIONS.iter().any(|ext| path.ends_with(ext)) {
+        return Err("Path must end with .tar, .tar.gz or .tgz".to_string());
+    }
+    Ok(path)
+}
+
+fn parse_restore_form(form: &InputForm) -> Result<AppEvent, String> {
+    let path = expand_home(&form.value("path"));
+    if !std::path::Path::new(&path).is_file() {
+        return Err("File not found".to_string());
+    }
+
+    let name = form.value("volume");
+    if !is_valid_name(&name) {
+        return Err("Volume name must match [a-zA-Z0-9][a-zA-Z0-9_.-]+".to_string());
+    }
+
+    let replace = form.value("mode") == RESTORE_MODES[1];
+    Ok(AppEvent::RestoreVolume(name, path, replace))
+}
+
+fn parse_clone_form(source: &str, form: &InputForm) -> Result<AppEvent, String> {
+    let target = form.value("name");
+    if !is_valid_name(&target) {
+        return Err("Name must match [a-zA-Z0-9][a-zA-Z0-9_.-]+".to_string());
+    }
+    if target == source {
+        return Err("New name must differ from the current one".to_string());
+    }
+
+    let rewire = form.value("containers") == CLONE_CONTAINER_MODES[1];
+    let remove_source = form.value("original") == CLONE_SOURCE_MODES[1];
+    Ok(AppEvent::CloneVolume(source.to_string(), target, rewire, remove_source))
+}
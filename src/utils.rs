@@ -1,3 +1,168 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use regex::Regex;
+
+const REGEX_NAME: &str = r"^[a-zA-Z0-9][a-zA-Z0-9_.-]+$";
+
 pub fn is_container_running(state: &str) -> bool {
     state == "running"
 }
+
+/// Whether `name` is valid for a container, volume or network, the daemon checking all three
+/// against the same pattern.
+pub fn is_valid_name(name: &str) -> bool {
+    Regex::new(REGEX_NAME).is_ok_and(|re| re.is_match(name))
+}
+
+/// Expands a leading `~/` in `path` to the current user's home directory.
+pub fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{home}/{rest}"),
+        _ => path.to_string(),
+    }
+}
+
+/// Parses whitespace separated `key=value` pairs, as used for driver options and labels.
+pub fn parse_key_values(value: &str) -> Result<HashMap<String, String>, String> {
+    value.split_whitespace()
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+            _ => Err(format!("Expected key=value, got: {pair}")),
+        })
+        .collect()
+}
+
+/// Parses an `address/prefix` CIDR block, e.g. `172.20.0.0/16` or `fd00::/64`.
+pub fn parse_cidr(value: &str) -> Result<(IpAddr, u8), String> {
+    let invalid = || format!("Invalid CIDR: {value}");
+    let (address, prefix) = value.split_once('/').ok_or_else(invalid)?;
+    let address = address.parse::<IpAddr>().map_err(|_| invalid())?;
+    let prefix = prefix.parse::<u8>().map_err(|_| invalid())?;
+
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    if prefix > max_prefix {
+        return Err(invalid());
+    }
+    Ok((address, prefix))
+}
+
+/// Checks whether `address` lies within the `network/prefix` block of the same IP version.
+pub fn cidr_contains((network, prefix): (IpAddr, u8), address: IpAddr) -> bool {
+    let (network, address, bits) = match (network, address) {
+        (IpAddr::V4(n), IpAddr::V4(a)) => (u32::from(n) as u128, u32::from(a) as u128, 32),
+        (IpAddr::V6(n), IpAddr::V6(a)) => (u128::from(n), u128::from(a), 128),
+        _ => return false,
+    };
+
+    let host_bits = bits - prefix as u32;
+    host_bits >= bits || (network >> host_bits) == (address >> host_bits)
+}
+
+/// Parses a memory size the way docker's `--memory` flag does: a number with an optional
+/// `b`, `k`, `m` or `g` suffix, in powers of 1024.
+pub fn parse_memory(value: &str) -> Result<i64, String> {
+    let invalid = || format!("Invalid memory size: {value}");
+    let lower = value.to_lowercase();
+    let (number, unit) = match lower.trim_end_matches('b').char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&lower[..i], c),
+        _ => (lower.trim_end_matches('b'), 'b'),
+    };
+
+    let multiplier = match unit {
+        'b' => 1,
+        'k' => 1 << 10,
+        'm' => 1 << 20,
+        'g' => 1 << 30,
+        _ => return Err(invalid()),
+    };
+    let number = number.parse::<f64>().map_err(|_| invalid())?;
+    if !number.is_finite() || number < 0.0 {
+        return Err(invalid());
+    }
+    Ok((number * multiplier as f64).round() as i64)
+}
+
+/// Formats a memory size in the largest unit that divides it evenly, so it parses back unchanged.
+pub fn memory_string(bytes: i64) -> String {
+    [('g', 1 << 30), ('m', 1 << 20), ('k', 1 << 10)]
+        .into_iter()
+        .find(|(_, size)| bytes > 0 && bytes % size == 0)
+        .map(|(unit, size)| format!("{}{unit}", bytes / size))
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_cidr_reads_ipv4_and_ipv6() {
+        assert_eq!(parse_cidr("172.20.0.0/16"), Ok((ip("172.20.0.0"), 16)));
+        assert_eq!(parse_cidr("fd00::/64"), Ok((ip("fd00::"), 64)));
+    }
+
+    #[test]
+    fn parse_cidr_rejects_invalid_blocks() {
+        assert!(parse_cidr("172.20.0.0").is_err());
+        assert!(parse_cidr("172.20.0.0/33").is_err());
+        assert!(parse_cidr("fd00::/129").is_err());
+        assert!(parse_cidr("172.20.0/16").is_err());
+        assert!(parse_cidr("172.20.0.0/x").is_err());
+    }
+
+    #[test]
+    fn cidr_contains_checks_the_prefix() {
+        let block = (ip("172.20.0.0"), 16);
+        assert!(cidr_contains(block, ip("172.20.5.1")));
+        assert!(!cidr_contains(block, ip("172.21.0.1")));
+
+        let block = (ip("fd00::"), 64);
+        assert!(cidr_contains(block, ip("fd00::1")));
+        assert!(!cidr_contains(block, ip("fd00:0:0:1::1")));
+    }
+
+    #[test]
+    fn cidr_contains_handles_edge_prefixes() {
+        assert!(cidr_contains((ip("0.0.0.0"), 0), ip("10.1.2.3")));
+        assert!(cidr_contains((ip("10.0.0.1"), 32), ip("10.0.0.1")));
+        assert!(!cidr_contains((ip("10.0.0.1"), 32), ip("10.0.0.2")));
+    }
+
+    #[test]
+    fn cidr_contains_rejects_other_ip_version() {
+        assert!(!cidr_contains((ip("0.0.0.0"), 0), ip("::1")));
+    }
+
+    #[test]
+    fn parse_memory_reads_units() {
+        assert_eq!(parse_memory("512"), Ok(512));
+        assert_eq!(parse_memory("512b"), Ok(512));
+        assert_eq!(parse_memory("4k"), Ok(4 << 10));
+        assert_eq!(parse_memory("512m"), Ok(512 << 20));
+        assert_eq!(parse_memory("2G"), Ok(2 << 30));
+        assert_eq!(parse_memory("1gb"), Ok(1 << 30));
+        assert_eq!(parse_memory("1.5g"), Ok(3 << 29));
+    }
+
+    #[test]
+    fn parse_memory_rejects_invalid_sizes() {
+        assert!(parse_memory("").is_err());
+        assert!(parse_memory("12t").is_err());
+        assert!(parse_memory("-1m").is_err());
+        assert!(parse_memory("m").is_err());
+        assert!(parse_memory("inf").is_err());
+    }
+
+    #[test]
+    fn memory_string_parses_back() {
+        for bytes in [512 << 20, 3 << 29, 2 << 30, 1000] {
+            assert_eq!(parse_memory(&memory_string(bytes)), Ok(bytes));
+        }
+        assert_eq!(memory_string(512 << 20), "512m");
+    }
+}